@@ -1,18 +1,175 @@
 use clap::{Parser, Subcommand};
+use clap_complete::ArgValueCompleter;
+use strikes_core::name::Name;
+
+use crate::completion::complete_names;
+
+use crate::export::ImportMode;
+use crate::output::OutputFormat;
 
 #[derive(Subcommand, Clone, Debug)]
 pub enum Command {
     #[command(about = "Add a strike", alias = "s")]
     Strike {
-        #[arg(help = "Name of the tarnished", value_parser = parse_username)]
-        name: String,
+        #[arg(
+            help = "Name of the tarnished",
+            value_parser = parse_username,
+            add = ArgValueCompleter::new(complete_names)
+        )]
+        name: Name,
+        #[arg(short, long, help = "Reason for the strike")]
+        reason: Option<String>,
+        #[arg(
+            short,
+            long,
+            value_enum,
+            default_value_t,
+            help = "Format of the output"
+        )]
+        output: OutputFormat,
+    },
+    #[command(about = "Remove the latest strike", alias = "u")]
+    Unstrike {
+        #[arg(
+            help = "Name of the tarnished",
+            value_parser = parse_username,
+            add = ArgValueCompleter::new(complete_names)
+        )]
+        name: Name,
     },
     #[command(about = "List all strikes")]
-    Ls,
+    Ls {
+        #[arg(short, long, help = "Show the reasons of each strike")]
+        verbose: bool,
+        #[arg(long, help = "List the strikes of every board")]
+        all_boards: bool,
+        #[arg(long, help = "Include strikes that have already expired")]
+        include_expired: bool,
+        #[arg(
+            short,
+            long,
+            value_enum,
+            default_value_t,
+            help = "Format of the output"
+        )]
+        output: OutputFormat,
+    },
+    #[command(about = "Show the history of strikes of a tarnished")]
+    History {
+        #[arg(
+            help = "Name of the tarnished",
+            value_parser = parse_username,
+            add = ArgValueCompleter::new(complete_names)
+        )]
+        name: Name,
+    },
     #[command(about = "Clear strikes", alias = "c")]
-    Clear,
+    Clear {
+        #[arg(
+            help = "Name of the tarnished",
+            value_parser = parse_username,
+            add = ArgValueCompleter::new(complete_names),
+            required_unless_present = "all",
+            conflicts_with = "all"
+        )]
+        name: Option<Name>,
+        #[arg(short, long, help = "Clear the strikes of everyone")]
+        all: bool,
+    },
+    #[command(about = "Export all strikes of a board")]
+    Export {
+        #[arg(short, long, help = "Write the export to a file instead of stdout")]
+        file: Option<std::path::PathBuf>,
+    },
+    #[command(about = "Import strikes from an export")]
+    Import {
+        #[arg(help = "Path to the export")]
+        file: std::path::PathBuf,
+        #[arg(
+            short,
+            long,
+            value_enum,
+            default_value_t,
+            help = "Merge with or replace the existing strikes"
+        )]
+        mode: ImportMode,
+    },
+    #[command(about = "Send strikes that were queued while the server was unavailable")]
+    Sync,
+    #[command(about = "Serve the local strikes to remote clients over HTTP")]
+    Serve {
+        #[arg(long, help = "Address to listen on, defaults to server.host")]
+        host: Option<String>,
+        #[arg(long, help = "Port to listen on, defaults to server.port")]
+        port: Option<u16>,
+    },
     #[command(about = "Check health of the client", alias = "h")]
     CheckHealth,
+    #[command(about = "Create, inspect, change and validate the configuration")]
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+    #[command(about = "Manage the aliases of the tarnished on a board")]
+    Alias {
+        #[command(subcommand)]
+        command: AliasCommand,
+    },
+    #[command(about = "Print the completion script for a shell")]
+    Completions {
+        #[arg(value_enum, help = "Shell to complete in")]
+        shell: clap_complete::Shell,
+    },
+    #[command(about = "Print the man page")]
+    Man {
+        #[arg(short, long, help = "Write a man page per command into this directory")]
+        dir: Option<std::path::PathBuf>,
+    },
+}
+
+#[derive(Subcommand, Clone, Debug)]
+pub enum ConfigCommand {
+    #[command(about = "Create a configuration file step by step and check it")]
+    Init {
+        #[arg(short, long, help = "Overwrite an existing configuration file")]
+        force: bool,
+    },
+    #[command(about = "Show the configuration merged from all layers")]
+    Show {
+        #[arg(long, help = "Show where each value comes from")]
+        origin: bool,
+    },
+    #[command(about = "Set a value in the configuration file, e.g. remote.base_url")]
+    Set { key: String, value: String },
+    #[command(about = "Check the configuration and report the first error")]
+    Validate,
+}
+
+#[derive(Subcommand, Clone, Debug)]
+pub enum AliasCommand {
+    #[command(about = "Strike a tarnished under another handle, e.g. alias add guenther gue")]
+    Add {
+        #[arg(
+            help = "Name of the tarnished",
+            value_parser = parse_username,
+            add = ArgValueCompleter::new(complete_names)
+        )]
+        name: Name,
+        #[arg(help = "Alias of the tarnished", value_parser = parse_username)]
+        alias: Name,
+    },
+    #[command(about = "List the aliases of the board")]
+    Ls,
+    #[command(about = "Remove an alias, strikes already given are kept")]
+    Rm {
+        #[arg(help = "Alias to remove", value_parser = parse_username)]
+        alias: Name,
+    },
+    #[command(about = "Move the strikes recorded under an alias to its name")]
+    Merge {
+        #[arg(help = "Alias to merge", value_parser = parse_username)]
+        alias: Name,
+    },
 }
 
 #[derive(Clone, Debug, Parser)]
@@ -27,18 +184,34 @@ pub struct Cli {
     #[arg(short, long, help = "Specify the path to the configuration file")]
     pub config_path: Option<std::path::PathBuf>,
 
+    #[arg(
+        short,
+        long,
+        global = true,
+        help = "Specify the board to use",
+        value_parser = parse_board
+    )]
+    pub board: Option<String>,
+
+    #[arg(
+        short,
+        long,
+        global = true,
+        help = "Specify the configuration profile to use, defaults to STRIKES_PROFILE"
+    )]
+    pub profile: Option<String>,
+
     #[command(subcommand)]
     pub command: Option<Command>,
 }
 
-fn parse_username(s: &str) -> Result<String, Box<dyn std::error::Error + Send + Sync + 'static>> {
-    if s.is_empty() {
-        return Err("Username cannot be empty".into());
-    }
-
-    if s.len() > 20 {
-        return Err("Username cannot be longer than 20 characters".into());
-    }
+fn parse_username(s: &str) -> Result<Name, Box<dyn std::error::Error + Send + Sync + 'static>> {
+    Name::parse(s).map_err(|err| format!("Username {}", err).into())
+}
 
-    Ok(s.to_lowercase())
+// Boards follow the same rules as names of the tarnished.
+fn parse_board(s: &str) -> Result<String, Box<dyn std::error::Error + Send + Sync + 'static>> {
+    Name::parse(s)
+        .map(String::from)
+        .map_err(|err| format!("Board {}", err).into())
 }
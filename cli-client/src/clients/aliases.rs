@@ -0,0 +1,98 @@
+use std::collections::BTreeMap;
+
+use super::local_client::Board;
+use crate::{error::StrikesError, strike::Strike};
+
+// Maps each alias of a board to the canonical name it stands for.
+pub type Aliases = BTreeMap<String, String>;
+
+pub(crate) fn resolve<'a>(aliases: &'a Aliases, name: &'a str) -> &'a str {
+    aliases.get(name).map_or(name, String::as_str)
+}
+
+// The name followed by every alias of it, the strikes of a name may be recorded under each.
+pub(crate) fn spellings<'a>(aliases: &'a Aliases, name: &'a str) -> Vec<&'a str> {
+    std::iter::once(name)
+        .chain(
+            aliases
+                .iter()
+                .filter(|(_, canonical)| canonical.as_str() == name)
+                .map(|(alias, _)| alias.as_str()),
+        )
+        .collect()
+}
+
+// Aliases always point at a canonical name, so resolving never has to follow a chain.
+pub(crate) fn check_alias(
+    aliases: &Aliases,
+    name: &str,
+    alias: &str,
+) -> Result<String, StrikesError> {
+    let name = resolve(aliases, name);
+    if name == alias {
+        return Err(StrikesError::Validation(format!(
+            "{} can't be an alias of itself",
+            alias
+        )));
+    }
+    if aliases.values().any(|canonical| canonical == alias) {
+        return Err(StrikesError::Validation(format!(
+            "{} already has aliases of its own",
+            alias
+        )));
+    }
+
+    Ok(name.to_string())
+}
+
+// Strikes recorded under an alias before it was registered count for the canonical name.
+pub(crate) fn fold_aliases(board: Board, aliases: &Aliases) -> Board {
+    let mut folded = Board::new();
+    for (name, strikes) in board {
+        folded
+            .entry(resolve(aliases, &name).to_string())
+            .or_default()
+            .extend(strikes);
+    }
+
+    folded
+        .into_iter()
+        .map(|(name, strikes)| (name, Strike::sort_asc_by_timestamp(strikes)))
+        .collect()
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    #[test]
+    fn it_should_fold_the_strikes_of_an_alias() {
+        let aliases = Aliases::from([("gue".to_string(), "guenther".to_string())]);
+        let board = Board::from([
+            ("gue".to_string(), vec![Strike::default()]),
+            ("guenther".to_string(), vec![Strike::default(); 2]),
+            ("heinz".to_string(), vec![Strike::default()]),
+        ]);
+
+        let folded = fold_aliases(board, &aliases);
+
+        assert_eq!(folded.len(), 2);
+        assert_eq!(folded["guenther"].len(), 3);
+        assert_eq!(folded["heinz"].len(), 1);
+    }
+
+    #[test]
+    fn it_should_reject_chained_aliases() {
+        let aliases = Aliases::from([("gue".to_string(), "guenther".to_string())]);
+
+        assert_eq!(check_alias(&aliases, "gue", "g").unwrap(), "guenther");
+        assert!(matches!(
+            check_alias(&aliases, "heinz", "guenther"),
+            Err(StrikesError::Validation(_))
+        ));
+        assert!(matches!(
+            check_alias(&aliases, "gue", "guenther"),
+            Err(StrikesError::Validation(_))
+        ));
+    }
+}
@@ -1,11 +1,28 @@
 use async_trait::async_trait;
+use std::collections::BTreeMap;
 
-use crate::tarnished::Tarnished;
+use super::aliases::Aliases;
+use super::outbox::SyncReport;
+use crate::{error::StrikesError, export::ExportedTarnished, strike::Strike, tarnished::Tarnished};
 
+pub const DEFAULT_BOARD: &str = "default";
+
+// Send and Sync so `strikes serve` can share a client between requests.
 #[async_trait]
-pub trait StrikeClient {
-    async fn add_strike(&self, name: &str) -> Result<u8, String>;
-    async fn get_tarnished(&self) -> Result<Vec<Tarnished>, String>;
-    async fn clear_strikes(&self) -> Result<(), String>;
-    async fn check_health(&self) -> Result<(), String>;
+pub trait StrikeClient: Send + Sync {
+    async fn add_strike(&self, name: &str, reason: Option<&str>) -> Result<u32, StrikesError>;
+    async fn remove_strike(&self, name: &str) -> Result<u32, StrikesError>;
+    async fn get_tarnished(&self) -> Result<Vec<Tarnished>, StrikesError>;
+    async fn get_boards(&self) -> Result<BTreeMap<String, Vec<Tarnished>>, StrikesError>;
+    async fn get_history(&self, name: &str) -> Result<Vec<Strike>, StrikesError>;
+    async fn clear_strikes(&self) -> Result<(), StrikesError>;
+    async fn clear_tarnished(&self, name: &str) -> Result<(), StrikesError>;
+    async fn import_strikes(&self, tarnished: &[ExportedTarnished]) -> Result<usize, StrikesError>;
+    async fn add_alias(&self, name: &str, alias: &str) -> Result<(), StrikesError>;
+    async fn remove_alias(&self, alias: &str) -> Result<(), StrikesError>;
+    async fn get_aliases(&self) -> Result<Aliases, StrikesError>;
+    // Moves the strikes recorded under an alias to its name and returns how many were moved.
+    async fn merge_alias(&self, alias: &str) -> Result<u32, StrikesError>;
+    async fn sync(&self) -> Result<SyncReport, StrikesError>;
+    async fn check_health(&self) -> Result<(), StrikesError>;
 }
@@ -1,52 +1,434 @@
 use async_trait::async_trait;
-use serde_json::json;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
 
-use super::client::StrikeClient;
-use crate::tarnished::Tarnished;
+use super::aliases::{check_alias, fold_aliases, resolve, spellings, Aliases};
+use super::client::{StrikeClient, DEFAULT_BOARD};
+use super::outbox::SyncReport;
+use crate::{error::StrikesError, export::ExportedTarnished, strike::Strike, tarnished::Tarnished};
 
 pub struct LocalClient {
     pub db_path: std::path::PathBuf,
+    pub board: String,
+    pub expire_after_days: Option<u32>,
+}
+
+// Older databases only stored a counter per name, so both shapes are accepted when reading.
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum Record {
+    Count(u32),
+    Strikes(Vec<Strike>),
+}
+
+impl Record {
+    fn into_strikes(self) -> Vec<Strike> {
+        match self {
+            Record::Count(count) => vec![Strike::default(); count as usize],
+            Record::Strikes(strikes) => strikes,
+        }
+    }
+
+    fn into_board(records: HashMap<String, Record>) -> Board {
+        records
+            .into_iter()
+            .map(|(name, record)| (name, record.into_strikes()))
+            .collect()
+    }
+}
+
+pub(crate) type Board = HashMap<String, Vec<Strike>>;
+
+// Version 2 added aliases, which older clients would drop when writing the database back.
+const DB_VERSION: u32 = 2;
+
+#[derive(serde::Serialize)]
+struct Db {
+    version: u32,
+    boards: HashMap<String, Board>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    aliases: HashMap<String, Aliases>,
+}
+
+impl Default for Db {
+    fn default() -> Self {
+        Db {
+            version: DB_VERSION,
+            boards: HashMap::new(),
+            aliases: HashMap::new(),
+        }
+    }
+}
+
+// Databases without a version predate it. The first ones were a bare {name: count} map
+// holding a single board, later ones wrapped several boards without saying so.
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum StoredDb {
+    Versioned {
+        version: u32,
+        boards: HashMap<String, HashMap<String, Record>>,
+        #[serde(default)]
+        aliases: HashMap<String, Aliases>,
+    },
+    Boards {
+        boards: HashMap<String, HashMap<String, Record>>,
+    },
+    Legacy(HashMap<String, Record>),
+}
+
+fn into_boards(boards: HashMap<String, HashMap<String, Record>>) -> HashMap<String, Board> {
+    boards
+        .into_iter()
+        .map(|(board, records)| (board, Record::into_board(records)))
+        .collect()
+}
+
+fn read_db(db_path: &std::path::Path) -> Result<Db, StrikesError> {
+    let raw = match std::fs::read_to_string(db_path) {
+        Ok(raw) => raw,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Db::default()),
+        Err(err) => return Err(err.into()),
+    };
+    let stored: StoredDb = serde_json::from_str(&raw)
+        .map_err(|err| StrikesError::Storage(format!("Invalid database: {}", err)))?;
+
+    let boards = match stored {
+        StoredDb::Versioned { version, .. } if version > DB_VERSION => {
+            return Err(StrikesError::Storage(format!(
+                "Database version {} is newer than the supported version {}",
+                version, DB_VERSION
+            )))
+        }
+        StoredDb::Versioned {
+            boards, aliases, ..
+        } => {
+            return Ok(Db {
+                version: DB_VERSION,
+                boards: into_boards(boards),
+                aliases,
+            })
+        }
+        StoredDb::Boards { boards } => into_boards(boards),
+        StoredDb::Legacy(records) if records.is_empty() => HashMap::new(),
+        StoredDb::Legacy(records) => {
+            HashMap::from([(DEFAULT_BOARD.to_string(), Record::into_board(records))])
+        }
+    };
+
+    let db = Db {
+        version: DB_VERSION,
+        boards,
+        aliases: HashMap::new(),
+    };
+    write_db(db_path, &db)?;
+
+    Ok(db)
+}
+
+pub(super) fn write_db(
+    db_path: &std::path::Path,
+    db: &impl serde::Serialize,
+) -> Result<(), StrikesError> {
+    if !db_path.exists() {
+        std::fs::create_dir_all(db_path.parent().unwrap())?;
+    }
+
+    // Writing next to the database and renaming over it means a crash leaves either the old
+    // or the new file behind, never a truncated one.
+    let tmp_path = sibling(db_path, "tmp");
+    let mut tmp = std::fs::File::create(&tmp_path)?;
+    tmp.write_all(serde_json::to_string_pretty(db).unwrap().as_bytes())
+        .and_then(|_| tmp.sync_all())?;
+
+    std::fs::rename(&tmp_path, db_path).map_err(StrikesError::from)
+}
+
+// Held for the whole read-modify-write of an operation so parallel invocations don't lose
+// updates. The lock lives on a separate file because the database itself is replaced on write.
+pub(super) fn lock_db(db_path: &std::path::Path) -> Result<std::fs::File, StrikesError> {
+    if let Some(parent) = db_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let lock = std::fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(sibling(db_path, "lock"))?;
+    lock.lock()?;
+
+    Ok(lock)
+}
+
+fn sibling(db_path: &std::path::Path, extension: &str) -> std::path::PathBuf {
+    let mut name = db_path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(extension);
+    db_path.with_file_name(name)
+}
+
+// Used to move an existing JSON database over to another backend.
+#[allow(clippy::type_complexity)]
+pub(crate) fn read_boards(
+    db_path: &std::path::Path,
+) -> Result<(HashMap<String, Board>, HashMap<String, Aliases>), StrikesError> {
+    let _lock = lock_db(db_path)?;
+
+    read_db(db_path).map(|db| (db.boards, db.aliases))
+}
+
+impl LocalClient {
+    fn active(&self, strikes: &[Strike]) -> Vec<Strike> {
+        strikes
+            .iter()
+            .filter(|strike| {
+                self.expire_after_days
+                    .is_none_or(|days| !strike.is_expired(days))
+            })
+            .cloned()
+            .collect()
+    }
+
+    fn active_board(&self, board: Board) -> Board {
+        board
+            .into_iter()
+            .map(|(name, strikes)| (name, self.active(&strikes)))
+            .filter(|(_, strikes)| !strikes.is_empty())
+            .collect()
+    }
 }
 
 #[async_trait]
 impl StrikeClient for LocalClient {
-    async fn add_strike(&self, name: &str) -> Result<u8, String> {
-        let db_path = &self.db_path;
-        if !db_path.exists() {
-            std::fs::create_dir_all(db_path.parent().unwrap()).unwrap();
+    async fn add_strike(&self, name: &str, reason: Option<&str>) -> Result<u32, StrikesError> {
+        let _lock = lock_db(&self.db_path)?;
+        let db = &mut read_db(&self.db_path)?;
+        let aliases = db.aliases.get(&self.board).cloned().unwrap_or_default();
+        let name = resolve(&aliases, name);
+        let board = db.boards.entry(self.board.clone()).or_default();
+        board
+            .entry(name.to_string())
+            .or_default()
+            .push(Strike::now(reason));
+        let count = fold_aliases(board.clone(), &aliases)
+            .get(name)
+            .map_or(0, |strikes| self.active(strikes).len() as u32);
+
+        write_db(&self.db_path, db)?;
+
+        Ok(count)
+    }
+
+    async fn remove_strike(&self, name: &str) -> Result<u32, StrikesError> {
+        let _lock = lock_db(&self.db_path)?;
+        let db = &mut read_db(&self.db_path)?;
+        let aliases = db.aliases.get(&self.board).cloned().unwrap_or_default();
+        let name = resolve(&aliases, name);
+        let Some(board) = db.boards.get_mut(&self.board) else {
+            return Ok(0);
+        };
+        // The latest strike may still be recorded under an alias.
+        let Some(spelling) = spellings(&aliases, name)
+            .into_iter()
+            .filter_map(|spelling| {
+                board
+                    .get(spelling)
+                    .and_then(|strikes| strikes.last())
+                    .map(|strike| (strike.timestamp, spelling))
+            })
+            .max_by_key(|(timestamp, _)| *timestamp)
+            .map(|(_, spelling)| spelling.to_string())
+        else {
+            return Ok(0);
+        };
+
+        if let Some(strikes) = board.get_mut(&spelling) {
+            strikes.pop();
+            if strikes.is_empty() {
+                board.remove(&spelling);
+            }
         }
+        let count = fold_aliases(board.clone(), &aliases)
+            .get(name)
+            .map_or(0, |strikes| self.active(strikes).len() as u32);
 
-        let raw = std::fs::read_to_string(db_path).unwrap_or_else(|_| json!({}).to_string());
-        let db: &mut HashMap<String, u8> = &mut serde_json::from_str(&raw).unwrap();
-        let count = db.get(name).unwrap_or(&0);
-        db.insert(name.to_string(), count + 1);
+        write_db(&self.db_path, db)?;
 
-        std::fs::write(db_path, serde_json::to_string_pretty(&db).unwrap()).unwrap();
+        Ok(count)
+    }
 
-        Ok(*db.get(name).unwrap())
+    async fn get_tarnished(&self) -> Result<Vec<Tarnished>, StrikesError> {
+        let _lock = lock_db(&self.db_path)?;
+        let db = read_db(&self.db_path)?;
+        let board = db.boards.get(&self.board).cloned().unwrap_or_default();
+        let aliases = db.aliases.get(&self.board).cloned().unwrap_or_default();
+        let board = fold_aliases(board, &aliases);
+
+        Ok(
+            Tarnished::sort_desc_by_strike(Tarnished::from_db(self.active_board(board)))
+                .into_iter()
+                .collect(),
+        )
     }
 
-    async fn get_tarnished(&self) -> Result<Vec<Tarnished>, String> {
-        let db_path = &self.db_path;
-        let raw = std::fs::read_to_string(db_path).unwrap_or_else(|_| json!({}).to_string());
-        let db: HashMap<String, u8> = serde_json::from_str(&raw).unwrap_or(HashMap::new());
+    async fn get_boards(&self) -> Result<BTreeMap<String, Vec<Tarnished>>, StrikesError> {
+        let _lock = lock_db(&self.db_path)?;
+        let db = read_db(&self.db_path)?;
 
-        Ok(Tarnished::sort_desc_by_strike(Tarnished::from_map(db))
+        Ok(db
+            .boards
             .into_iter()
+            .map(|(board, strikes)| {
+                let aliases = db.aliases.get(&board).cloned().unwrap_or_default();
+                let strikes = fold_aliases(strikes, &aliases);
+                (
+                    board,
+                    Tarnished::sort_desc_by_strike(Tarnished::from_db(self.active_board(strikes))),
+                )
+            })
             .collect())
     }
 
-    async fn clear_strikes(&self) -> Result<(), String> {
-        let db_path = &self.db_path;
-        if db_path.exists() {
-            std::fs::write(db_path, json!({}).to_string()).unwrap();
+    async fn get_history(&self, name: &str) -> Result<Vec<Strike>, StrikesError> {
+        let _lock = lock_db(&self.db_path)?;
+        let db = read_db(&self.db_path)?;
+
+        let aliases = db.aliases.get(&self.board).cloned().unwrap_or_default();
+        let board = db.boards.get(&self.board).cloned().unwrap_or_default();
+
+        Ok(fold_aliases(board, &aliases)
+            .remove(resolve(&aliases, name))
+            .unwrap_or_default())
+    }
+
+    async fn clear_strikes(&self) -> Result<(), StrikesError> {
+        let _lock = lock_db(&self.db_path)?;
+        let db = &mut read_db(&self.db_path)?;
+        if db.boards.remove(&self.board).is_some() {
+            write_db(&self.db_path, db)?;
         }
 
         Ok(())
     }
 
-    async fn check_health(&self) -> Result<(), String> {
+    async fn clear_tarnished(&self, name: &str) -> Result<(), StrikesError> {
+        let _lock = lock_db(&self.db_path)?;
+        let db = &mut read_db(&self.db_path)?;
+        let aliases = db.aliases.get(&self.board).cloned().unwrap_or_default();
+        let Some(board) = db.boards.get_mut(&self.board) else {
+            return Ok(());
+        };
+        let mut removed = false;
+        for spelling in spellings(&aliases, resolve(&aliases, name)) {
+            removed |= board.remove(spelling).is_some();
+        }
+        if removed {
+            write_db(&self.db_path, db)?;
+        }
+
+        Ok(())
+    }
+
+    async fn import_strikes(&self, tarnished: &[ExportedTarnished]) -> Result<usize, StrikesError> {
+        let _lock = lock_db(&self.db_path)?;
+        let db = &mut read_db(&self.db_path)?;
+        let aliases = db.aliases.get(&self.board).cloned().unwrap_or_default();
+        let board = db.boards.entry(self.board.clone()).or_default();
+        let mut imported = 0;
+
+        for entry in tarnished.iter().filter(|entry| !entry.strikes.is_empty()) {
+            let strikes = board
+                .entry(resolve(&aliases, &entry.name).to_string())
+                .or_default();
+            // Strikes without a timestamp can't be told apart, so their count is authoritative:
+            // only as many are added as the name has fewer of them than the export.
+            let untimed = strikes.iter().filter(|strike| strike.timestamp.is_none());
+            let missing = entry
+                .strikes
+                .iter()
+                .filter(|strike| strike.timestamp.is_none())
+                .skip(untimed.count())
+                .cloned()
+                .collect::<Vec<_>>();
+            for strike in &entry.strikes {
+                if strike.timestamp.is_some() && !strikes.contains(strike) {
+                    strikes.push(strike.clone());
+                    imported += 1;
+                }
+            }
+            imported += missing.len();
+            strikes.extend(missing);
+            *strikes = Strike::sort_asc_by_timestamp(std::mem::take(strikes));
+        }
+
+        write_db(&self.db_path, db)?;
+
+        Ok(imported)
+    }
+
+    async fn add_alias(&self, name: &str, alias: &str) -> Result<(), StrikesError> {
+        let _lock = lock_db(&self.db_path)?;
+        let db = &mut read_db(&self.db_path)?;
+        let aliases = db.aliases.entry(self.board.clone()).or_default();
+        let name = check_alias(aliases, name, alias)?;
+        aliases.insert(alias.to_string(), name);
+
+        write_db(&self.db_path, db)
+    }
+
+    async fn remove_alias(&self, alias: &str) -> Result<(), StrikesError> {
+        let _lock = lock_db(&self.db_path)?;
+        let db = &mut read_db(&self.db_path)?;
+        let removed = db
+            .aliases
+            .get_mut(&self.board)
+            .and_then(|aliases| aliases.remove(alias));
+        if removed.is_none() {
+            return Err(StrikesError::NotFound(format!("No alias {}", alias)));
+        }
+        db.aliases.retain(|_, aliases| !aliases.is_empty());
+
+        write_db(&self.db_path, db)
+    }
+
+    async fn get_aliases(&self) -> Result<Aliases, StrikesError> {
+        let _lock = lock_db(&self.db_path)?;
+        let db = read_db(&self.db_path)?;
+
+        Ok(db.aliases.get(&self.board).cloned().unwrap_or_default())
+    }
+
+    async fn merge_alias(&self, alias: &str) -> Result<u32, StrikesError> {
+        let _lock = lock_db(&self.db_path)?;
+        let db = &mut read_db(&self.db_path)?;
+        let Some(name) = db
+            .aliases
+            .get(&self.board)
+            .and_then(|aliases| aliases.get(alias))
+            .cloned()
+        else {
+            return Err(StrikesError::NotFound(format!("No alias {}", alias)));
+        };
+        let board = db.boards.entry(self.board.clone()).or_default();
+        let Some(moved) = board.remove(alias) else {
+            return Ok(0);
+        };
+        let count = moved.len() as u32;
+        let strikes = board.entry(name).or_default();
+        strikes.extend(moved);
+        *strikes = Strike::sort_asc_by_timestamp(std::mem::take(strikes));
+
+        write_db(&self.db_path, db)?;
+
+        Ok(count)
+    }
+
+    async fn sync(&self) -> Result<SyncReport, StrikesError> {
+        Ok(SyncReport::default())
+    }
+
+    async fn check_health(&self) -> Result<(), StrikesError> {
         println!("Checking health for local client");
         Ok(())
     }
@@ -61,11 +443,13 @@ mod unit_tests {
         let file = assert_fs::NamedTempFile::new("./tests/fixtures/db.json")?;
         let client = LocalClient {
             db_path: file.to_path_buf(),
+            board: DEFAULT_BOARD.to_string(),
+            expire_after_days: None,
         };
 
-        let _ = client.add_strike("guenther").await?;
-        let _ = client.add_strike("guenther").await?;
-        let strikes = client.add_strike("guenther").await?;
+        let _ = client.add_strike("guenther", None).await?;
+        let _ = client.add_strike("guenther", None).await?;
+        let strikes = client.add_strike("guenther", None).await?;
 
         assert_eq!(strikes, 3,);
 
@@ -81,7 +465,7 @@ mod unit_tests {
         ]
         .iter()
         .cloned()
-        .collect::<HashMap<String, u8>>();
+        .collect::<HashMap<String, u32>>();
         let tarnished = Tarnished::sort_desc_by_strike(Tarnished::from_map(raw.clone()));
 
         assert_eq!(
@@ -89,15 +473,18 @@ mod unit_tests {
             vec![
                 Tarnished {
                     name: "hans".to_string(),
-                    strikes: 3
+                    strikes: 3,
+                    reasons: vec![],
                 },
                 Tarnished {
                     name: "guenther".to_string(),
-                    strikes: 2
+                    strikes: 2,
+                    reasons: vec![],
                 },
                 Tarnished {
                     name: "heinz".to_string(),
-                    strikes: 1
+                    strikes: 1,
+                    reasons: vec![],
                 }
             ]
         );
@@ -106,8 +493,15 @@ mod unit_tests {
 
 #[cfg(test)]
 mod integration_tests {
+    use assert_fs::fixture::FileWriteStr;
+
     use crate::{
-        clients::local_client::{LocalClient, StrikeClient as _},
+        clients::{
+            client::DEFAULT_BOARD,
+            local_client::{LocalClient, StrikeClient as _},
+        },
+        error::StrikesError,
+        export::{export_board, import_board, ImportMode},
         tarnished::Tarnished,
     };
 
@@ -116,16 +510,19 @@ mod integration_tests {
         let file = assert_fs::NamedTempFile::new("./tests/fixtures/db.json")?;
         let client = LocalClient {
             db_path: file.to_path_buf(),
+            board: DEFAULT_BOARD.to_string(),
+            expire_after_days: None,
         };
 
-        let _ = client.add_strike("guenther").await?;
+        let _ = client.add_strike("guenther", None).await?;
         let strikes = client.get_tarnished().await.unwrap();
 
         assert_eq!(
             strikes,
             vec![Tarnished {
                 name: "guenther".to_string(),
-                strikes: 1
+                strikes: 1,
+                reasons: vec![],
             }]
         );
         Ok(())
@@ -136,11 +533,13 @@ mod integration_tests {
         let file = assert_fs::NamedTempFile::new("./tests/fixtures/db.json")?;
         let client = LocalClient {
             db_path: file.to_path_buf(),
+            board: DEFAULT_BOARD.to_string(),
+            expire_after_days: None,
         };
 
-        let _ = client.add_strike("guenther").await?;
-        let _ = client.add_strike("heinz").await?;
-        let _ = client.add_strike("guenther").await?;
+        let _ = client.add_strike("guenther", None).await?;
+        let _ = client.add_strike("heinz", None).await?;
+        let _ = client.add_strike("guenther", None).await?;
 
         let strikes = client.get_tarnished().await.unwrap();
 
@@ -149,11 +548,13 @@ mod integration_tests {
             vec![
                 Tarnished {
                     name: "guenther".to_string(),
-                    strikes: 2
+                    strikes: 2,
+                    reasons: vec![],
                 },
                 Tarnished {
                     name: "heinz".to_string(),
-                    strikes: 1
+                    strikes: 1,
+                    reasons: vec![],
                 }
             ]
         );
@@ -161,15 +562,247 @@ mod integration_tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn it_should_keep_the_reasons_of_strikes() -> Result<(), Box<dyn std::error::Error>> {
+        let file = assert_fs::NamedTempFile::new("./tests/fixtures/db.json")?;
+        let client = LocalClient {
+            db_path: file.to_path_buf(),
+            board: DEFAULT_BOARD.to_string(),
+            expire_after_days: None,
+        };
+
+        let _ = client
+            .add_strike("guenther", Some("broke the build"))
+            .await?;
+        let _ = client.add_strike("guenther", None).await?;
+        let _ = client
+            .add_strike("guenther", Some("forgot the retro"))
+            .await?;
+
+        let strikes = client.get_tarnished().await.unwrap();
+
+        assert_eq!(
+            strikes,
+            vec![Tarnished {
+                name: "guenther".to_string(),
+                strikes: 3,
+                reasons: vec![
+                    "broke the build".to_string(),
+                    "forgot the retro".to_string()
+                ],
+            }]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_should_record_the_history_of_strikes() -> Result<(), Box<dyn std::error::Error>> {
+        let file = assert_fs::NamedTempFile::new("./tests/fixtures/db.json")?;
+        let client = LocalClient {
+            db_path: file.to_path_buf(),
+            board: DEFAULT_BOARD.to_string(),
+            expire_after_days: None,
+        };
+
+        let _ = client
+            .add_strike("guenther", Some("broke the build"))
+            .await?;
+        let _ = client.add_strike("heinz", None).await?;
+        let _ = client.add_strike("guenther", None).await?;
+
+        let history = client.get_history("guenther").await?;
+
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].reason, Some("broke the build".to_string()));
+        assert_eq!(history[1].reason, None);
+        assert!(history[0].timestamp <= history[1].timestamp);
+        assert!(history.iter().all(|strike| strike.timestamp.is_some()));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_should_read_a_legacy_db() -> Result<(), Box<dyn std::error::Error>> {
+        let file = assert_fs::NamedTempFile::new("./tests/fixtures/db.json")?;
+        file.write_str("{\"guenther\": 2}")?;
+        let client = LocalClient {
+            db_path: file.to_path_buf(),
+            board: DEFAULT_BOARD.to_string(),
+            expire_after_days: None,
+        };
+
+        let strikes = client.add_strike("guenther", Some("late again")).await?;
+
+        assert_eq!(strikes, 3);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_should_migrate_a_legacy_db_with_large_counts(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let file = assert_fs::NamedTempFile::new("./tests/fixtures/db.json")?;
+        file.write_str("{\"guenther\": 300}")?;
+        let client = LocalClient {
+            db_path: file.to_path_buf(),
+            board: DEFAULT_BOARD.to_string(),
+            expire_after_days: None,
+        };
+
+        assert_eq!(client.get_tarnished().await?[0].strikes, 300);
+
+        let migrated: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(file.path())?)?;
+        assert_eq!(migrated["version"], 2);
+        assert_eq!(
+            migrated["boards"][DEFAULT_BOARD]["guenther"]
+                .as_array()
+                .map(Vec::len),
+            Some(300)
+        );
+        assert_eq!(client.add_strike("guenther", None).await?, 301);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_should_refuse_a_newer_db_version() -> Result<(), Box<dyn std::error::Error>> {
+        let file = assert_fs::NamedTempFile::new("./tests/fixtures/db.json")?;
+        file.write_str("{\"version\": 99, \"boards\": {}}")?;
+        let client = LocalClient {
+            db_path: file.to_path_buf(),
+            board: DEFAULT_BOARD.to_string(),
+            expire_after_days: None,
+        };
+
+        let err = client.add_strike("guenther", None).await.unwrap_err();
+
+        assert!(
+            matches!(err, StrikesError::Storage(err) if err.contains("newer than the supported version"))
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_should_not_take_an_unreadable_db_for_an_empty_one(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let file = assert_fs::NamedTempFile::new("./tests/fixtures/db.json")?;
+        file.write_str("{\"guenther\": ")?;
+        let dir = assert_fs::TempDir::new()?;
+        let corrupt = LocalClient {
+            db_path: file.to_path_buf(),
+            board: DEFAULT_BOARD.to_string(),
+            expire_after_days: None,
+        };
+        let unreadable = LocalClient {
+            db_path: dir.to_path_buf(),
+            board: DEFAULT_BOARD.to_string(),
+            expire_after_days: None,
+        };
+
+        assert!(matches!(
+            corrupt.get_tarnished().await,
+            Err(StrikesError::Storage(_))
+        ));
+        assert!(matches!(
+            unreadable.add_strike("guenther", None).await,
+            Err(StrikesError::Storage(_))
+        ));
+        assert_eq!(std::fs::read_to_string(file.path())?, "{\"guenther\": ");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_should_ignore_expired_strikes() -> Result<(), Box<dyn std::error::Error>> {
+        let file = assert_fs::NamedTempFile::new("./tests/fixtures/db.json")?;
+        file.write_str(
+            r#"{"boards": {"default": {
+                "guenther": [{"reason": "ancient history", "timestamp": "2020-01-01T00:00:00Z"}],
+                "heinz": [{"timestamp": "2020-01-01T00:00:00Z"}, {"reason": "no timestamp"}]
+            }}}"#,
+        )?;
+        let client = LocalClient {
+            db_path: file.to_path_buf(),
+            board: DEFAULT_BOARD.to_string(),
+            expire_after_days: Some(30),
+        };
+
+        assert_eq!(
+            client.get_tarnished().await?,
+            vec![Tarnished {
+                name: "heinz".to_string(),
+                strikes: 1,
+                reasons: vec!["no timestamp".to_string()],
+            }]
+        );
+        assert_eq!(client.add_strike("guenther", None).await?, 1);
+        assert_eq!(client.get_history("guenther").await?.len(), 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_should_remove_the_latest_strike() -> Result<(), Box<dyn std::error::Error>> {
+        let file = assert_fs::NamedTempFile::new("./tests/fixtures/db.json")?;
+        let client = LocalClient {
+            db_path: file.to_path_buf(),
+            board: DEFAULT_BOARD.to_string(),
+            expire_after_days: None,
+        };
+
+        let _ = client
+            .add_strike("guenther", Some("broke the build"))
+            .await?;
+        let _ = client.add_strike("guenther", Some("typo")).await?;
+
+        let strikes = client.remove_strike("guenther").await?;
+
+        assert_eq!(strikes, 1);
+        assert_eq!(
+            client.get_tarnished().await.unwrap(),
+            vec![Tarnished {
+                name: "guenther".to_string(),
+                strikes: 1,
+                reasons: vec!["broke the build".to_string()],
+            }]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_should_remove_the_tarnished_without_strikes(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let file = assert_fs::NamedTempFile::new("./tests/fixtures/db.json")?;
+        let client = LocalClient {
+            db_path: file.to_path_buf(),
+            board: DEFAULT_BOARD.to_string(),
+            expire_after_days: None,
+        };
+
+        let _ = client.add_strike("guenther", None).await?;
+
+        assert_eq!(client.remove_strike("guenther").await?, 0);
+        assert_eq!(client.remove_strike("guenther").await?, 0);
+        assert!(client.get_tarnished().await.unwrap().is_empty());
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn it_should_clear_strikes() -> Result<(), Box<dyn std::error::Error>> {
         let file = assert_fs::NamedTempFile::new("./tests/fixtures/db.json")?;
         let client = LocalClient {
             db_path: file.to_path_buf(),
+            board: DEFAULT_BOARD.to_string(),
+            expire_after_days: None,
         };
 
-        let _ = client.add_strike("guenther");
-        let _ = client.add_strike("heinz");
+        let _ = client.add_strike("guenther", None).await?;
+        let _ = client.add_strike("heinz", None).await?;
 
         let _ = client.clear_strikes().await;
 
@@ -179,4 +812,206 @@ mod integration_tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn it_should_clear_the_strikes_of_one_tarnished() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let file = assert_fs::NamedTempFile::new("./tests/fixtures/db.json")?;
+        let client = LocalClient {
+            db_path: file.to_path_buf(),
+            board: DEFAULT_BOARD.to_string(),
+            expire_after_days: None,
+        };
+
+        let _ = client.add_strike("guenther", None).await?;
+        let _ = client.add_strike("guenther", None).await?;
+        let _ = client.add_strike("heinz", None).await?;
+
+        client.clear_tarnished("guenther").await?;
+
+        let strikes = client.get_tarnished().await.unwrap();
+
+        assert_eq!(
+            strikes,
+            vec![Tarnished {
+                name: "heinz".to_string(),
+                strikes: 1,
+                reasons: vec![],
+            }]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_should_keep_boards_apart() -> Result<(), Box<dyn std::error::Error>> {
+        let file = assert_fs::NamedTempFile::new("./tests/fixtures/db.json")?;
+        let coffee = LocalClient {
+            db_path: file.to_path_buf(),
+            board: "coffee".to_string(),
+            expire_after_days: None,
+        };
+        let builds = LocalClient {
+            db_path: file.to_path_buf(),
+            board: "builds".to_string(),
+            expire_after_days: None,
+        };
+
+        let _ = coffee.add_strike("guenther", None).await?;
+        let _ = builds.add_strike("heinz", None).await?;
+        let _ = builds.add_strike("heinz", None).await?;
+        coffee.clear_strikes().await?;
+
+        assert!(coffee.get_tarnished().await?.is_empty());
+        assert_eq!(
+            builds.get_boards().await?,
+            std::collections::BTreeMap::from([(
+                "builds".to_string(),
+                vec![Tarnished {
+                    name: "heinz".to_string(),
+                    strikes: 2,
+                    reasons: vec![],
+                }]
+            )])
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_should_read_a_legacy_db_into_the_default_board(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let file = assert_fs::NamedTempFile::new("./tests/fixtures/db.json")?;
+        file.write_str("{\"guenther\": 2}")?;
+        let client = LocalClient {
+            db_path: file.to_path_buf(),
+            board: "coffee".to_string(),
+            expire_after_days: None,
+        };
+
+        let boards = client.get_boards().await?;
+
+        assert_eq!(boards.keys().collect::<Vec<_>>(), vec![DEFAULT_BOARD]);
+        assert!(client.get_tarnished().await?.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_should_import_an_export_without_duplicates(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let source_file = assert_fs::NamedTempFile::new("./tests/fixtures/db.json")?;
+        let target_file = assert_fs::NamedTempFile::new("./tests/fixtures/db.json")?;
+        target_file.write_str("{\"heinz\": 1}")?;
+        let source = LocalClient {
+            db_path: source_file.to_path_buf(),
+            board: "coffee".to_string(),
+            expire_after_days: None,
+        };
+        let target = LocalClient {
+            db_path: target_file.to_path_buf(),
+            board: DEFAULT_BOARD.to_string(),
+            expire_after_days: None,
+        };
+
+        source.add_strike("guenther", Some("no refill")).await?;
+        source.add_strike("guenther", None).await?;
+        let export = export_board(&source, "coffee").await?;
+
+        assert_eq!(import_board(&target, &export, ImportMode::Merge).await?, 2);
+        assert_eq!(import_board(&target, &export, ImportMode::Merge).await?, 0);
+        assert_eq!(
+            target.get_history("guenther").await?,
+            source.get_history("guenther").await?
+        );
+        assert_eq!(target.get_tarnished().await?.len(), 2);
+
+        // "heinz" was counted before any history was kept, so the export has no timestamps.
+        let own = export_board(&target, DEFAULT_BOARD).await?;
+        assert_eq!(import_board(&target, &own, ImportMode::Merge).await?, 0);
+        assert_eq!(target.get_tarnished().await?[1].strikes, 1);
+
+        assert_eq!(
+            import_board(&target, &export, ImportMode::Replace).await?,
+            2
+        );
+        assert_eq!(
+            target.get_tarnished().await?,
+            vec![Tarnished {
+                name: "guenther".to_string(),
+                strikes: 2,
+                reasons: vec!["no refill".to_string()],
+            }]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_should_count_the_strikes_of_an_alias_for_its_name(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let file = assert_fs::NamedTempFile::new("./tests/fixtures/db.json")?;
+        let client = LocalClient {
+            db_path: file.to_path_buf(),
+            board: DEFAULT_BOARD.to_string(),
+            expire_after_days: None,
+        };
+
+        client.add_strike("gue", Some("typo")).await?;
+        client.add_strike("guenther", None).await?;
+        client.add_alias("guenther", "gue").await?;
+
+        assert_eq!(client.add_strike("gue", None).await?, 3);
+        assert_eq!(client.get_history("guenther").await?.len(), 3);
+        assert_eq!(
+            client.get_tarnished().await?,
+            vec![Tarnished {
+                name: "guenther".to_string(),
+                strikes: 3,
+                reasons: vec!["typo".to_string()],
+            }]
+        );
+
+        assert_eq!(client.merge_alias("gue").await?, 1);
+        assert_eq!(client.get_history("gue").await?.len(), 3);
+
+        client.remove_alias("gue").await?;
+        assert!(client.get_aliases().await?.is_empty());
+        assert!(matches!(
+            client.merge_alias("gue").await,
+            Err(StrikesError::NotFound(_))
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_should_take_back_and_clear_strikes_given_under_an_alias(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let file = assert_fs::NamedTempFile::new("./tests/fixtures/db.json")?;
+        let client = LocalClient {
+            db_path: file.to_path_buf(),
+            board: DEFAULT_BOARD.to_string(),
+            expire_after_days: None,
+        };
+
+        client.add_strike("gue", Some("typo")).await?;
+        client.add_alias("guenther", "gue").await?;
+        client.add_strike("gue", None).await?;
+        client.add_strike("guenther", None).await?;
+
+        assert_eq!(client.remove_strike("gue").await?, 2);
+        assert_eq!(
+            client.get_history("gue").await?,
+            client.get_history("guenther").await?
+        );
+        assert_eq!(client.get_history("gue").await?.len(), 2);
+
+        client.clear_tarnished("gue").await?;
+
+        assert!(client.get_tarnished().await?.is_empty());
+        assert!(client.get_history("guenther").await?.is_empty());
+
+        Ok(())
+    }
 }
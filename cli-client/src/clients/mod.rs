@@ -1,3 +1,69 @@
+pub mod aliases;
 pub mod client;
 pub mod local_client;
+pub mod outbox;
 pub mod remote_client;
+pub mod sqlite_client;
+
+use crate::configuration::{LocalBackend, LocalSettings, Settings};
+use client::StrikeClient;
+use local_client::LocalClient;
+use outbox::Outbox;
+use remote_client::RemoteClient;
+use sqlite_client::SqliteClient;
+
+pub fn create_client(
+    settings: &Settings,
+    board: &str,
+    include_expired: bool,
+) -> Box<dyn StrikeClient> {
+    settings.remote.as_ref().map_or_else(
+        || {
+            create_local_client(
+                settings.local.as_ref().unwrap(),
+                board,
+                settings
+                    .decay
+                    .as_ref()
+                    .filter(|_| !include_expired)
+                    .map(|decay| decay.expire_after_days),
+            )
+        },
+        |remote| {
+            Box::new(RemoteClient {
+                api_key: remote.api_key.clone(),
+                base_url: remote.base_url.clone(),
+                board: board.to_string(),
+                include_expired,
+                outbox: Outbox::for_remote(
+                    &settings
+                        .local
+                        .as_ref()
+                        .or(Settings::default().local.as_ref())
+                        .unwrap()
+                        .db_path,
+                    &remote.base_url,
+                ),
+            }) as Box<dyn StrikeClient>
+        },
+    )
+}
+
+pub fn create_local_client(
+    local: &LocalSettings,
+    board: &str,
+    expire_after_days: Option<u32>,
+) -> Box<dyn StrikeClient> {
+    match local.backend {
+        LocalBackend::Json => Box::new(LocalClient {
+            db_path: local.db_path.clone(),
+            board: board.to_string(),
+            expire_after_days,
+        }),
+        LocalBackend::Sqlite => Box::new(SqliteClient {
+            db_path: local.db_path.clone(),
+            board: board.to_string(),
+            expire_after_days,
+        }),
+    }
+}
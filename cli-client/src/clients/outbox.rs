@@ -0,0 +1,155 @@
+use super::local_client::{lock_db, write_db};
+use crate::error::StrikesError;
+use std::collections::BTreeMap;
+
+// Operations that could not reach the remote server, kept in the order they were issued.
+// Clearing a whole board is never queued, a late replay would wipe strikes added meanwhile.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "operation", rename_all = "snake_case")]
+pub enum QueuedOperation {
+    AddStrike {
+        board: String,
+        name: String,
+        reason: Option<String>,
+    },
+    RemoveStrike {
+        board: String,
+        name: String,
+    },
+    ClearTarnished {
+        board: String,
+        name: String,
+    },
+}
+
+impl QueuedOperation {
+    pub fn describe(&self) -> String {
+        match self {
+            QueuedOperation::AddStrike { board, name, .. } => {
+                format!("strike for {} on {}", name, board)
+            }
+            QueuedOperation::RemoveStrike { board, name } => {
+                format!("unstrike of {} on {}", name, board)
+            }
+            QueuedOperation::ClearTarnished { board, name } => {
+                format!("clearing {} on {}", name, board)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct SyncReport {
+    pub replayed: usize,
+    pub pending: usize,
+    pub conflicts: Vec<String>,
+    pub counts: BTreeMap<String, BTreeMap<String, u32>>,
+}
+
+pub struct Outbox {
+    pub path: std::path::PathBuf,
+}
+
+impl Outbox {
+    // Each server gets an outbox of its own, so a profile never replays what was queued for
+    // another one.
+    pub fn for_remote(db_path: &std::path::Path, base_url: &str) -> Self {
+        let server = base_url
+            .trim_end_matches('/')
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect::<String>();
+
+        Outbox {
+            path: db_path.with_file_name(format!("outbox-{}.json", server)),
+        }
+    }
+
+    // Held while the outbox is read and rewritten, so parallel invocations neither replay the
+    // same operations twice nor drop the ones queued meanwhile.
+    pub fn lock(&self) -> Result<std::fs::File, StrikesError> {
+        lock_db(&self.path)
+    }
+
+    pub fn read(&self) -> Result<Vec<QueuedOperation>, StrikesError> {
+        match std::fs::read_to_string(&self.path) {
+            Ok(raw) => serde_json::from_str(&raw)
+                .map_err(|err| StrikesError::Storage(format!("Invalid outbox: {}", err))),
+            Err(_) => Ok(vec![]),
+        }
+    }
+
+    pub fn write(&self, operations: &[QueuedOperation]) -> Result<(), StrikesError> {
+        if operations.is_empty() {
+            return match self.path.exists() {
+                true => Ok(std::fs::remove_file(&self.path)?),
+                false => Ok(()),
+            };
+        }
+
+        write_db(&self.path, &operations)
+    }
+
+    pub fn push(&self, operation: QueuedOperation) -> Result<(), StrikesError> {
+        let _lock = self.lock()?;
+        let mut operations = self.read()?;
+        operations.push(operation);
+        self.write(&operations)
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    #[test]
+    fn it_should_keep_an_outbox_per_server() {
+        let db_path = std::path::Path::new("/home/strikes/.config/strikes/db.json");
+
+        assert_eq!(
+            Outbox::for_remote(db_path, "https://strikes.example.com/").path,
+            db_path.with_file_name("outbox-https___strikes_example_com.json")
+        );
+        assert_ne!(
+            Outbox::for_remote(db_path, "https://strikes.example.com").path,
+            Outbox::for_remote(db_path, "https://other.example.com").path
+        );
+    }
+
+    #[test]
+    fn it_should_keep_operations_in_order() -> Result<(), Box<dyn std::error::Error>> {
+        let file = assert_fs::NamedTempFile::new("outbox.json")?;
+        let outbox = Outbox {
+            path: file.to_path_buf(),
+        };
+
+        outbox.push(QueuedOperation::RemoveStrike {
+            board: "default".to_string(),
+            name: "guenther".to_string(),
+        })?;
+        outbox.push(QueuedOperation::ClearTarnished {
+            board: "coffee".to_string(),
+            name: "heinz".to_string(),
+        })?;
+
+        assert_eq!(
+            outbox.read()?,
+            vec![
+                QueuedOperation::RemoveStrike {
+                    board: "default".to_string(),
+                    name: "guenther".to_string(),
+                },
+                QueuedOperation::ClearTarnished {
+                    board: "coffee".to_string(),
+                    name: "heinz".to_string(),
+                },
+            ]
+        );
+
+        outbox.write(&[])?;
+
+        assert!(!file.path().exists());
+
+        Ok(())
+    }
+}
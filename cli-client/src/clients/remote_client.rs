@@ -1,66 +1,326 @@
 use async_trait::async_trait;
 use reqwest;
+use std::collections::{BTreeMap, BTreeSet};
 
+use super::aliases::Aliases;
 use super::client::StrikeClient;
-use crate::tarnished::Tarnished;
+use super::outbox::{Outbox, QueuedOperation, SyncReport};
+use crate::configuration::RemoteSettings;
+use crate::{error::StrikesError, export::ExportedTarnished, strike::Strike, tarnished::Tarnished};
+use strikes_core::wire::{
+    AliasRequest, AliasResponse, BoardStrikesResponse, DeleteResponse, ImportResponse,
+    MergeResponse, StrikeCountResponse, StrikeRequest, StrikesPage, StrikesResponse,
+    DEFAULT_PAGE_SIZE,
+};
 
 pub struct RemoteClient {
     pub api_key: String,
     pub base_url: String,
+    pub board: String,
+    pub include_expired: bool,
+    pub outbox: Outbox,
+}
+
+// Servers from before pagination ignore the limit and answer with the whole list.
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum StrikesListing {
+    All(Vec<StrikesResponse>),
+    Page(StrikesPage),
 }
 
 struct HttpClient {
     base_url: String,
     api_key: String,
+    board: String,
 }
 
-#[derive(serde::Deserialize)]
-pub struct StrikesResponse {
-    pub name: String,
-    pub strike_count: u8,
+// A body that can't be read after a successful status means the request went through, so it
+// must not look like an unavailable server and get queued again.
+async fn parse_response<T: serde::de::DeserializeOwned>(
+    response: reqwest::Response,
+) -> Result<T, StrikesError> {
+    let body = response.text().await.map_err(|err| {
+        StrikesError::Validation(format!("Failed to read response body: {}", err))
+    })?;
+
+    serde_json::from_str(&body)
+        .map_err(|err| StrikesError::Validation(format!("Failed to parse response: {}", err)))
 }
 
-#[async_trait]
-impl StrikeClient for RemoteClient {
-    async fn add_strike(&self, username: &str) -> Result<u8, String> {
-        let client = HttpClient {
+// Read-only and bounded by the timeout, for shell completion that must neither replay the outbox
+// nor hang while the server is down.
+pub async fn peek_tarnished(
+    remote: &RemoteSettings,
+    board: &str,
+    timeout: std::time::Duration,
+) -> Result<Vec<Tarnished>, StrikesError> {
+    HttpClient {
+        base_url: remote.base_url.clone(),
+        api_key: remote.api_key.clone(),
+        board: board.to_string(),
+    }
+    .get_strikes_within(false, Some(timeout))
+    .await
+}
+
+impl RemoteClient {
+    fn http_client(&self, board: &str) -> HttpClient {
+        HttpClient {
             base_url: self.base_url.clone(),
             api_key: self.api_key.clone(),
-        };
+            board: board.to_string(),
+        }
+    }
 
-        client.put_strike(username).await
+    fn queue(&self, operation: QueuedOperation, err: StrikesError) -> StrikesError {
+        let description = operation.describe();
+        match self.outbox.push(operation) {
+            Ok(()) => StrikesError::Network(format!(
+                "{} is unavailable ({}), queued the {} in {}",
+                self.base_url,
+                err,
+                description,
+                self.outbox.path.display()
+            )),
+            Err(outbox_err) => {
+                StrikesError::Storage(format!("{} and queueing failed: {}", err, outbox_err))
+            }
+        }
     }
 
-    async fn get_tarnished(&self) -> Result<Vec<Tarnished>, String> {
-        let client = HttpClient {
-            base_url: self.base_url.clone(),
-            api_key: self.api_key.clone(),
-        };
+    async fn replay_outbox(&self) -> Result<SyncReport, StrikesError> {
+        let _lock = self.outbox.lock()?;
+        let operations = self.outbox.read()?;
+        let mut report = SyncReport::default();
+        let mut touched: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+
+        for (index, operation) in operations.iter().enumerate() {
+            let result = match operation {
+                // Goes through the same endpoint as a live strike, so thresholds and aliases apply.
+                QueuedOperation::AddStrike {
+                    board,
+                    name,
+                    reason,
+                } => {
+                    touched
+                        .entry(board.clone())
+                        .or_default()
+                        .insert(name.clone());
+                    self.http_client(board)
+                        .put_strike(name, reason.as_deref())
+                        .await
+                        .map(|_| ())
+                }
+                QueuedOperation::RemoveStrike { board, name } => {
+                    touched
+                        .entry(board.clone())
+                        .or_default()
+                        .insert(name.clone());
+                    self.http_client(board)
+                        .delete_latest_strike(name)
+                        .await
+                        .map(|_| ())
+                }
+                QueuedOperation::ClearTarnished { board, name } => {
+                    touched
+                        .entry(board.clone())
+                        .or_default()
+                        .insert(name.clone());
+                    self.http_client(board).delete_user_strikes(name).await
+                }
+            };
 
-        client.get_strikes().await
+            match result {
+                Ok(()) => report.replayed += 1,
+                Err(StrikesError::Network(_)) => {
+                    report.pending = operations.len() - index;
+                    self.outbox.write(&operations[index..])?;
+                    return Ok(report);
+                }
+                Err(err) => report.conflicts.push(format!(
+                    "The {} was rejected: {}",
+                    operation.describe(),
+                    err
+                )),
+            }
+        }
+
+        self.outbox.write(&[])?;
+
+        for (board, names) in touched {
+            let tarnished = self
+                .http_client(&board)
+                .get_strikes(self.include_expired)
+                .await?;
+            let counts = names
+                .into_iter()
+                .map(|name| {
+                    let strikes = tarnished
+                        .iter()
+                        .find(|tarnished| tarnished.name == name)
+                        .map_or(0, |tarnished| tarnished.strikes);
+                    (name, strikes)
+                })
+                .collect();
+            report.counts.insert(board, counts);
+        }
+
+        Ok(report)
     }
 
-    async fn clear_strikes(&self) -> Result<(), String> {
-        let client = HttpClient {
-            base_url: self.base_url.clone(),
-            api_key: self.api_key.clone(),
-        };
+    async fn replay_pending(&self) {
+        if !self.outbox.path.exists() {
+            return;
+        }
 
-        client.delete_strikes().await
+        match self.replay_outbox().await {
+            Ok(report) => {
+                if report.replayed > 0 {
+                    eprintln!("Replayed {} queued operations", report.replayed);
+                }
+                for conflict in report.conflicts {
+                    eprintln!("{}", conflict);
+                }
+            }
+            Err(err) => eprintln!("Failed to replay queued operations: {}", err),
+        }
     }
+}
 
-    async fn check_health(&self) -> Result<(), String> {
-        let client = HttpClient {
-            base_url: self.base_url.clone(),
-            api_key: self.api_key.clone(),
-        };
+#[async_trait]
+impl StrikeClient for RemoteClient {
+    async fn add_strike(&self, username: &str, reason: Option<&str>) -> Result<u32, StrikesError> {
+        self.replay_pending().await;
+
+        match self
+            .http_client(&self.board)
+            .put_strike(username, reason)
+            .await
+        {
+            Err(err @ StrikesError::Network(_)) => Err(self.queue(
+                QueuedOperation::AddStrike {
+                    board: self.board.clone(),
+                    name: username.to_string(),
+                    reason: reason.map(str::to_string),
+                },
+                err,
+            )),
+            result => result,
+        }
+    }
+
+    async fn remove_strike(&self, username: &str) -> Result<u32, StrikesError> {
+        self.replay_pending().await;
+
+        match self
+            .http_client(&self.board)
+            .delete_latest_strike(username)
+            .await
+        {
+            Err(err @ StrikesError::Network(_)) => Err(self.queue(
+                QueuedOperation::RemoveStrike {
+                    board: self.board.clone(),
+                    name: username.to_string(),
+                },
+                err,
+            )),
+            result => result,
+        }
+    }
+
+    async fn get_tarnished(&self) -> Result<Vec<Tarnished>, StrikesError> {
+        self.replay_pending().await;
+
+        self.http_client(&self.board)
+            .get_strikes(self.include_expired)
+            .await
+    }
+
+    async fn get_boards(&self) -> Result<BTreeMap<String, Vec<Tarnished>>, StrikesError> {
+        self.replay_pending().await;
+
+        self.http_client(&self.board)
+            .get_all_strikes(self.include_expired)
+            .await
+    }
+
+    async fn get_history(&self, username: &str) -> Result<Vec<Strike>, StrikesError> {
+        self.replay_pending().await;
+
+        self.http_client(&self.board)
+            .get_strike_history(username)
+            .await
+    }
+
+    async fn clear_strikes(&self) -> Result<(), StrikesError> {
+        self.replay_pending().await;
+
+        self.http_client(&self.board).delete_strikes().await
+    }
+
+    async fn clear_tarnished(&self, username: &str) -> Result<(), StrikesError> {
+        self.replay_pending().await;
+
+        match self
+            .http_client(&self.board)
+            .delete_user_strikes(username)
+            .await
+        {
+            Err(err @ StrikesError::Network(_)) => Err(self.queue(
+                QueuedOperation::ClearTarnished {
+                    board: self.board.clone(),
+                    name: username.to_string(),
+                },
+                err,
+            )),
+            result => result,
+        }
+    }
+
+    async fn import_strikes(&self, tarnished: &[ExportedTarnished]) -> Result<usize, StrikesError> {
+        self.replay_pending().await;
 
-        client.get_health().await
+        self.http_client(&self.board).post_strikes(tarnished).await
+    }
+
+    async fn add_alias(&self, name: &str, alias: &str) -> Result<(), StrikesError> {
+        self.http_client(&self.board).put_alias(name, alias).await
+    }
+
+    async fn remove_alias(&self, alias: &str) -> Result<(), StrikesError> {
+        self.http_client(&self.board).delete_alias(alias).await
+    }
+
+    async fn get_aliases(&self) -> Result<Aliases, StrikesError> {
+        self.http_client(&self.board).get_aliases().await
+    }
+
+    async fn merge_alias(&self, alias: &str) -> Result<u32, StrikesError> {
+        self.replay_pending().await;
+
+        self.http_client(&self.board).merge_alias(alias).await
+    }
+
+    async fn sync(&self) -> Result<SyncReport, StrikesError> {
+        self.replay_outbox().await
+    }
+
+    async fn check_health(&self) -> Result<(), StrikesError> {
+        self.http_client(&self.board).get_health().await
     }
 }
 
 impl HttpClient {
-    async fn get_health(&self) -> Result<(), String> {
+    fn strikes_url(&self) -> String {
+        format!("{}/boards/{}/strikes", &self.base_url, &self.board)
+    }
+
+    fn aliases_url(&self) -> String {
+        format!("{}/boards/{}/aliases", &self.base_url, &self.board)
+    }
+
+    async fn get_health(&self) -> Result<(), StrikesError> {
         println!("Checking health for remote client");
 
         println!("Ping URL: {}/health", &self.base_url);
@@ -69,78 +329,294 @@ impl HttpClient {
             .get(format!("{}/health", &self.base_url))
             .header("x-api-key", &self.api_key)
             .send()
-            .await
-            .expect("Failed to execute request");
+            .await?;
         println!("Response: {:?}", response.status());
 
         match response.status() {
             reqwest::StatusCode::OK => Ok(()),
-            err => Err(err.to_string()),
+            status => Err(status.into()),
         }
     }
 
-    async fn put_strike(&self, username: &str) -> Result<u8, String> {
+    async fn put_strike(&self, username: &str, reason: Option<&str>) -> Result<u32, StrikesError> {
         let client = reqwest::Client::new();
         let response = client
-            .put(format!("{}/strikes/{}", &self.base_url, username))
+            .put(format!("{}/{}", self.strikes_url(), username))
             .header("x-api-key", &self.api_key)
+            .body(
+                serde_json::to_string(&StrikeRequest {
+                    reason: reason.map(str::to_string),
+                })
+                .unwrap(),
+            )
             .send()
-            .await
-            .expect("Failed to execute request");
+            .await?;
+
+        match response.status() {
+            reqwest::StatusCode::OK => Ok(parse_response::<StrikeCountResponse>(response)
+                .await?
+                .strike_count),
+            status => Err(status.into()),
+        }
+    }
+
+    async fn post_strikes(&self, tarnished: &[ExportedTarnished]) -> Result<usize, StrikesError> {
+        let client = reqwest::Client::new();
+        let response = client
+            .post(self.strikes_url())
+            .header("x-api-key", &self.api_key)
+            .body(serde_json::json!(tarnished).to_string())
+            .send()
+            .await?;
 
         match response.status() {
             reqwest::StatusCode::OK => {
-                let body = response.text().await.expect("Failed to read response body");
-                Ok(serde_json::from_str::<StrikesResponse>(&body)
-                    .expect("Failed to parse response")
-                    .strike_count)
+                Ok(parse_response::<ImportResponse>(response).await?.imported)
             }
-            err => Err(err.to_string()),
+            status => Err(status.into()),
         }
     }
 
-    async fn get_strikes(&self) -> Result<Vec<Tarnished>, String> {
+    async fn delete_latest_strike(&self, username: &str) -> Result<u32, StrikesError> {
         let client = reqwest::Client::new();
         let response = client
-            .get(format!("{}/strikes", &self.base_url))
+            .delete(format!("{}/{}/latest", self.strikes_url(), username))
             .header("x-api-key", &self.api_key)
             .send()
-            .await
-            .expect("Failed to execute request");
+            .await?;
+
+        match response.status() {
+            reqwest::StatusCode::OK => Ok(parse_response::<StrikeCountResponse>(response)
+                .await?
+                .strike_count),
+            status => Err(status.into()),
+        }
+    }
+
+    async fn get_strikes(&self, include_expired: bool) -> Result<Vec<Tarnished>, StrikesError> {
+        self.get_strikes_within(include_expired, None).await
+    }
+
+    async fn get_strikes_within(
+        &self,
+        include_expired: bool,
+        timeout: Option<std::time::Duration>,
+    ) -> Result<Vec<Tarnished>, StrikesError> {
+        let mut builder = reqwest::Client::builder();
+        if let Some(timeout) = timeout {
+            builder = builder.timeout(timeout);
+        }
+        let client = builder.build()?;
+        let mut strikes = vec![];
+        let mut cursor = None;
+
+        loop {
+            let mut request = client
+                .get(self.strikes_url())
+                .header("x-api-key", &self.api_key)
+                .query(&[("include_expired", include_expired)])
+                .query(&[("limit", DEFAULT_PAGE_SIZE)]);
+            if let Some(cursor) = &cursor {
+                request = request.query(&[("cursor", cursor)]);
+            }
+            let response = request.send().await?;
+
+            match response.status() {
+                reqwest::StatusCode::OK => {
+                    match parse_response::<StrikesListing>(response).await? {
+                        StrikesListing::Page(page) => {
+                            strikes.extend(page.strikes);
+                            match page.next_cursor {
+                                Some(next_cursor) => cursor = Some(next_cursor),
+                                None => break,
+                            }
+                        }
+                        StrikesListing::All(all) => {
+                            strikes.extend(all);
+                            break;
+                        }
+                    }
+                }
+                status => return Err(status.into()),
+            }
+        }
+
+        Ok(Tarnished::from_vec(strikes))
+    }
+
+    async fn get_all_strikes(
+        &self,
+        include_expired: bool,
+    ) -> Result<BTreeMap<String, Vec<Tarnished>>, StrikesError> {
+        let client = reqwest::Client::new();
+        let response = client
+            .get(format!("{}/boards", &self.base_url))
+            .header("x-api-key", &self.api_key)
+            .query(&[("include_expired", include_expired)])
+            .send()
+            .await?;
 
         match response.status() {
             reqwest::StatusCode::OK => {
-                let body = response.text().await.expect("Failed to read response body");
-                Ok(Tarnished::from_vec(
-                    serde_json::from_str::<Vec<StrikesResponse>>(&body)
-                        .expect("Faild to parse response"),
-                ))
+                let strikes = parse_response::<Vec<BoardStrikesResponse>>(response).await?;
+
+                Ok(strikes
+                    .into_iter()
+                    .fold(BTreeMap::new(), |mut boards, entry| {
+                        boards
+                            .entry(entry.board)
+                            .or_insert_with(Vec::new)
+                            .extend(Tarnished::from_vec(vec![entry.strikes]));
+                        boards
+                    }))
             }
-            err => Err(err.to_string()),
+            status => Err(status.into()),
         }
     }
 
-    async fn delete_strikes(&self) -> Result<(), String> {
+    async fn get_strike_history(&self, username: &str) -> Result<Vec<Strike>, StrikesError> {
         let client = reqwest::Client::new();
         let response = client
-            .delete(format!("{}/strikes", &self.base_url))
+            .get(format!("{}/{}/history", self.strikes_url(), username))
             .header("x-api-key", &self.api_key)
             .send()
-            .await
-            .expect("Failed to execute request");
+            .await?;
+
+        match response.status() {
+            reqwest::StatusCode::OK => parse_response::<Vec<Strike>>(response).await,
+            status => Err(status.into()),
+        }
+    }
+
+    async fn delete_user_strikes(&self, username: &str) -> Result<(), StrikesError> {
+        let client = reqwest::Client::new();
+        let response = client
+            .delete(format!("{}/{}", self.strikes_url(), username))
+            .header("x-api-key", &self.api_key)
+            .send()
+            .await?;
+
+        match response.status() {
+            reqwest::StatusCode::OK => Ok(()),
+            status => Err(status.into()),
+        }
+    }
+
+    async fn delete_strikes(&self) -> Result<(), StrikesError> {
+        let client = reqwest::Client::new();
+        let response = client
+            .delete(self.strikes_url())
+            .header("x-api-key", &self.api_key)
+            .send()
+            .await?;
+
+        match response.status() {
+            reqwest::StatusCode::OK => Ok(()),
+            // Tells how far the clear got before the storage failed.
+            status @ reqwest::StatusCode::INTERNAL_SERVER_ERROR => {
+                match parse_response::<DeleteResponse>(response).await {
+                    Ok(DeleteResponse { removed, failed }) if failed > 0 => {
+                        Err(StrikesError::Storage(format!(
+                            "Removed {} entries, but {} could not be removed. Clear again to remove the rest",
+                            removed, failed
+                        )))
+                    }
+                    _ => Err(status.into()),
+                }
+            }
+            status => Err(status.into()),
+        }
+    }
+
+    async fn put_alias(&self, name: &str, alias: &str) -> Result<(), StrikesError> {
+        let client = reqwest::Client::new();
+        let response = client
+            .put(format!("{}/{}", self.aliases_url(), alias))
+            .header("x-api-key", &self.api_key)
+            .body(
+                serde_json::to_string(&AliasRequest {
+                    name: name.to_string(),
+                })
+                .unwrap(),
+            )
+            .send()
+            .await?;
+
+        match response.status() {
+            reqwest::StatusCode::OK => Ok(()),
+            status => Err(status.into()),
+        }
+    }
+
+    async fn delete_alias(&self, alias: &str) -> Result<(), StrikesError> {
+        let client = reqwest::Client::new();
+        let response = client
+            .delete(format!("{}/{}", self.aliases_url(), alias))
+            .header("x-api-key", &self.api_key)
+            .send()
+            .await?;
 
         match response.status() {
             reqwest::StatusCode::OK => Ok(()),
-            err => Err(err.to_string()),
+            status => Err(status.into()),
+        }
+    }
+
+    async fn get_aliases(&self) -> Result<Aliases, StrikesError> {
+        let client = reqwest::Client::new();
+        let response = client
+            .get(self.aliases_url())
+            .header("x-api-key", &self.api_key)
+            .send()
+            .await?;
+
+        match response.status() {
+            reqwest::StatusCode::OK => Ok(parse_response::<Vec<AliasResponse>>(response)
+                .await?
+                .into_iter()
+                .map(|entry| (entry.alias, entry.name))
+                .collect()),
+            status => Err(status.into()),
+        }
+    }
+
+    async fn merge_alias(&self, alias: &str) -> Result<u32, StrikesError> {
+        let client = reqwest::Client::new();
+        let response = client
+            .post(format!("{}/{}/merge", self.aliases_url(), alias))
+            .header("x-api-key", &self.api_key)
+            .send()
+            .await?;
+
+        match response.status() {
+            reqwest::StatusCode::OK => Ok(parse_response::<MergeResponse>(response).await?.merged),
+            status => Err(status.into()),
         }
     }
 }
 
 #[cfg(test)]
 mod unit_tests {
-    use wiremock::{matchers::any, Mock, MockServer, ResponseTemplate};
+    use wiremock::{
+        matchers::{any, body_json, method, path, query_param, query_param_is_missing},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use std::collections::BTreeMap;
 
-    use crate::{clients::remote_client::HttpClient, tarnished::Tarnished};
+    use crate::{
+        clients::{
+            client::StrikeClient,
+            outbox::{Outbox, QueuedOperation, SyncReport},
+            remote_client::{peek_tarnished, HttpClient, RemoteClient},
+        },
+        configuration::RemoteSettings,
+        error::StrikesError,
+        export::ExportedTarnished,
+        strike::Strike,
+        tarnished::Tarnished,
+    };
+    use strikes_core::wire::StrikesResponse;
 
     #[tokio::test]
     async fn it_should_add_a_strike() -> Result<(), Box<dyn std::error::Error>> {
@@ -157,21 +633,99 @@ mod unit_tests {
         let client = HttpClient {
             api_key: "abc".to_string(),
             base_url: mock_server.uri(),
+            board: "default".to_string(),
         };
 
-        let strike_count = client.put_strike("guenther").await?;
+        let strike_count = client.put_strike("guenther", None).await?;
 
         assert_eq!(3, strike_count);
 
         Ok(())
     }
 
+    #[tokio::test]
+    async fn it_should_report_an_unexpected_response() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(any())
+            .respond_with(ResponseTemplate::new(200).set_body_string("<html>"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = HttpClient {
+            api_key: "abc".to_string(),
+            base_url: mock_server.uri(),
+            board: "default".to_string(),
+        };
+
+        let err = client.put_strike("guenther", None).await.unwrap_err();
+
+        assert!(matches!(err, StrikesError::Validation(err) if err.contains("Failed to parse")));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_should_send_the_reason_of_a_strike() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/boards/default/strikes/guenther"))
+            .and(body_json(serde_json::json!({"reason": "broke the build"})))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"name": "guenther", "strike_count": 1})),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = HttpClient {
+            api_key: "abc".to_string(),
+            base_url: mock_server.uri(),
+            board: "default".to_string(),
+        };
+
+        let strike_count = client
+            .put_strike("guenther", Some("broke the build"))
+            .await?;
+
+        assert_eq!(1, strike_count);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_should_remove_the_latest_strike() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("DELETE"))
+            .and(path("/boards/default/strikes/guenther/latest"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"name": "guenther", "strike_count": 2})),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = HttpClient {
+            api_key: "abc".to_string(),
+            base_url: mock_server.uri(),
+            board: "default".to_string(),
+        };
+
+        let strike_count = client.delete_latest_strike("guenther").await?;
+
+        assert_eq!(2, strike_count);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn it_should_fetch_all_strikes() -> Result<(), Box<dyn std::error::Error>> {
         let mock_server = MockServer::start().await;
         Mock::given(any())
             .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
-                {"name": "guenther", "strike_count": 2},
+                {"name": "guenther", "strike_count": 2, "reasons": ["broke the build"]},
                 {"name": "heinz", "strike_count": 3},
             ])))
             .expect(1)
@@ -181,19 +735,22 @@ mod unit_tests {
         let client = HttpClient {
             api_key: "abc".to_string(),
             base_url: mock_server.uri(),
+            board: "default".to_string(),
         };
 
-        let strikes = client.get_strikes().await?;
+        let strikes = client.get_strikes(false).await?;
 
         assert_eq!(
             vec![
                 Tarnished {
                     name: "guenther".to_string(),
                     strikes: 2,
+                    reasons: vec!["broke the build".to_string()],
                 },
                 Tarnished {
                     name: "heinz".to_string(),
                     strikes: 3,
+                    reasons: vec![],
                 }
             ],
             strikes
@@ -203,21 +760,582 @@ mod unit_tests {
     }
 
     #[tokio::test]
-    async fn it_should_delete_all_strikes() -> Result<(), Box<dyn std::error::Error>> {
+    async fn it_should_fetch_strikes_page_by_page() -> Result<(), Box<dyn std::error::Error>> {
         let mock_server = MockServer::start().await;
-        Mock::given(any())
-            .respond_with(ResponseTemplate::new(200))
+        Mock::given(method("GET"))
+            .and(path("/boards/default/strikes"))
+            .and(query_param("limit", "100"))
+            .and(query_param_is_missing("cursor"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "strikes": [{"name": "heinz", "strike_count": 3}],
+                "next_cursor": "1",
+            })))
+            .expect(1)
             .mount(&mock_server)
             .await;
-
-        let client = HttpClient {
+        Mock::given(method("GET"))
+            .and(path("/boards/default/strikes"))
+            .and(query_param("cursor", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "strikes": [{"name": "guenther", "strike_count": 2}],
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = HttpClient {
             api_key: "abc".to_string(),
             base_url: mock_server.uri(),
+            board: "default".to_string(),
         };
 
-        let strikes = client.delete_strikes().await?;
+        let strikes = client.get_strikes(false).await?;
+
+        assert_eq!(
+            strikes
+                .iter()
+                .map(|tarnished| tarnished.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["heinz", "guenther"]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_should_give_up_peeking_at_a_slow_server() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!([]))
+                    .set_delay(std::time::Duration::from_secs(5)),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let err = peek_tarnished(
+            &RemoteSettings {
+                api_key: "abc".to_string(),
+                base_url: mock_server.uri(),
+            },
+            "default",
+            std::time::Duration::from_millis(100),
+        )
+        .await
+        .unwrap_err();
+
+        assert!(matches!(err, StrikesError::Network(_)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_should_parse_old_and_new_strike_responses() {
+        let old = serde_json::from_str::<Vec<StrikesResponse>>(
+            r#"[{"name": "guenther", "strike_count": 3}]"#,
+        )
+        .unwrap();
+        let new = serde_json::from_str::<Vec<StrikesResponse>>(
+            r#"[{"name": "guenther", "strike_count": 70000, "reasons": ["typo"], "threshold_reached": false}]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            Tarnished::from_vec(old),
+            vec![Tarnished {
+                name: "guenther".to_string(),
+                strikes: 3,
+                reasons: vec![],
+            }]
+        );
+        assert_eq!(
+            Tarnished::from_vec(new),
+            vec![Tarnished {
+                name: "guenther".to_string(),
+                strikes: 70000,
+                reasons: vec!["typo".to_string()],
+            }]
+        );
+    }
 
-        assert_eq!((), strikes);
+    #[tokio::test]
+    async fn it_should_import_strikes_in_one_request() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/boards/default/strikes"))
+            .and(body_json(serde_json::json!([
+                {"name": "guenther", "strikes": [{"reason": "broke the build"}, {}]},
+                {"name": "heinz", "strikes": [{}]},
+            ])))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({"imported": 3})),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = HttpClient {
+            api_key: "abc".to_string(),
+            base_url: mock_server.uri(),
+            board: "default".to_string(),
+        };
+
+        let imported = client
+            .post_strikes(&[
+                ExportedTarnished {
+                    name: "guenther".to_string(),
+                    strikes: vec![
+                        Strike {
+                            reason: Some("broke the build".to_string()),
+                            timestamp: None,
+                        },
+                        Strike::default(),
+                    ],
+                },
+                ExportedTarnished {
+                    name: "heinz".to_string(),
+                    strikes: vec![Strike::default()],
+                },
+            ])
+            .await?;
+
+        assert_eq!(imported, 3);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_should_ask_for_expired_strikes() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/boards/default/strikes"))
+            .and(query_param("include_expired", "true"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"name": "guenther", "strike_count": 4},
+            ])))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = HttpClient {
+            api_key: "abc".to_string(),
+            base_url: mock_server.uri(),
+            board: "default".to_string(),
+        };
+
+        let strikes = client.get_strikes(true).await?;
+
+        assert_eq!(
+            vec![Tarnished {
+                name: "guenther".to_string(),
+                strikes: 4,
+                reasons: vec![],
+            }],
+            strikes
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_should_fetch_the_strikes_of_all_boards() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/boards"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"board": "coffee", "name": "guenther", "strike_count": 2},
+                {"board": "builds", "name": "heinz", "strike_count": 1},
+                {"board": "coffee", "name": "heinz", "strike_count": 3},
+            ])))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = HttpClient {
+            api_key: "abc".to_string(),
+            base_url: mock_server.uri(),
+            board: "default".to_string(),
+        };
+
+        let boards = client.get_all_strikes(false).await?;
+
+        assert_eq!(
+            BTreeMap::from([
+                (
+                    "builds".to_string(),
+                    vec![Tarnished {
+                        name: "heinz".to_string(),
+                        strikes: 1,
+                        reasons: vec![],
+                    }]
+                ),
+                (
+                    "coffee".to_string(),
+                    vec![
+                        Tarnished {
+                            name: "guenther".to_string(),
+                            strikes: 2,
+                            reasons: vec![],
+                        },
+                        Tarnished {
+                            name: "heinz".to_string(),
+                            strikes: 3,
+                            reasons: vec![],
+                        }
+                    ]
+                ),
+            ]),
+            boards
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_should_fetch_the_history_of_a_user() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/boards/default/strikes/guenther/history"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"timestamp": "2024-09-01T08:30:00Z", "reason": "broke the build"},
+                {"timestamp": "2024-09-02T10:00:00Z"},
+            ])))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = HttpClient {
+            api_key: "abc".to_string(),
+            base_url: mock_server.uri(),
+            board: "default".to_string(),
+        };
+
+        let history = client.get_strike_history("guenther").await?;
+
+        assert_eq!(
+            vec![
+                Strike {
+                    reason: Some("broke the build".to_string()),
+                    timestamp: Some("2024-09-01T08:30:00Z".parse()?),
+                },
+                Strike {
+                    reason: None,
+                    timestamp: Some("2024-09-02T10:00:00Z".parse()?),
+                }
+            ],
+            history
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_should_delete_all_strikes() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(any())
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&mock_server)
+            .await;
+
+        let client = HttpClient {
+            api_key: "abc".to_string(),
+            base_url: mock_server.uri(),
+            board: "default".to_string(),
+        };
+
+        assert!(client.delete_strikes().await.is_ok());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_should_report_a_partially_cleared_board() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("DELETE"))
+            .and(path("/boards/default/strikes"))
+            .respond_with(
+                ResponseTemplate::new(500)
+                    .set_body_json(serde_json::json!({"removed": 20, "failed": 5})),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let file = assert_fs::NamedTempFile::new("outbox.json")?;
+        let client = RemoteClient {
+            api_key: "abc".to_string(),
+            base_url: mock_server.uri(),
+            board: "default".to_string(),
+            include_expired: false,
+            outbox: Outbox {
+                path: file.to_path_buf(),
+            },
+        };
+
+        let err = client.clear_strikes().await.unwrap_err();
+
+        assert_eq!(
+            err,
+            StrikesError::Storage(
+                "Removed 20 entries, but 5 could not be removed. Clear again to remove the rest"
+                    .to_string()
+            )
+        );
+        assert!(client.outbox.read()?.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_should_delete_the_strikes_of_one_user() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("DELETE"))
+            .and(path("/boards/default/strikes/guenther"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = HttpClient {
+            api_key: "abc".to_string(),
+            base_url: mock_server.uri(),
+            board: "default".to_string(),
+        };
+
+        assert!(client.delete_user_strikes("guenther").await.is_ok());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_should_queue_strikes_while_the_server_is_unavailable(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let file = assert_fs::NamedTempFile::new("outbox.json")?;
+        let client = RemoteClient {
+            api_key: "abc".to_string(),
+            base_url: "http://127.0.0.1:1".to_string(),
+            board: "default".to_string(),
+            include_expired: false,
+            outbox: Outbox {
+                path: file.to_path_buf(),
+            },
+        };
+
+        let err = client
+            .add_strike("guenther", Some("broke the build"))
+            .await
+            .unwrap_err();
+
+        assert!(
+            matches!(err, StrikesError::Network(err) if err.contains("queued the strike for guenther on default"))
+        );
+        assert!(matches!(
+            client.outbox.read()?.as_slice(),
+            [QueuedOperation::AddStrike { name, reason: Some(reason), .. }]
+                if name == "guenther" && reason == "broke the build"
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_should_not_queue_a_strike_the_server_failed_on(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let file = assert_fs::NamedTempFile::new("outbox.json")?;
+        let client = RemoteClient {
+            api_key: "abc".to_string(),
+            base_url: mock_server.uri(),
+            board: "default".to_string(),
+            include_expired: false,
+            outbox: Outbox {
+                path: file.to_path_buf(),
+            },
+        };
+
+        let err = client.add_strike("guenther", None).await.unwrap_err();
+
+        assert_eq!(
+            err,
+            StrikesError::Storage("500 Internal Server Error".to_string())
+        );
+        assert!(client.outbox.read()?.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_should_not_queue_a_strike_behind_a_failing_gateway(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .respond_with(ResponseTemplate::new(504))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let file = assert_fs::NamedTempFile::new("outbox.json")?;
+        let client = RemoteClient {
+            api_key: "abc".to_string(),
+            base_url: mock_server.uri(),
+            board: "default".to_string(),
+            include_expired: false,
+            outbox: Outbox {
+                path: file.to_path_buf(),
+            },
+        };
+
+        let err = client.add_strike("guenther", None).await.unwrap_err();
+
+        assert_eq!(
+            err,
+            StrikesError::Storage("504 Gateway Timeout".to_string())
+        );
+        assert!(client.outbox.read()?.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_should_not_queue_clearing_a_board() -> Result<(), Box<dyn std::error::Error>> {
+        let file = assert_fs::NamedTempFile::new("outbox.json")?;
+        let client = RemoteClient {
+            api_key: "abc".to_string(),
+            base_url: "http://127.0.0.1:1".to_string(),
+            board: "default".to_string(),
+            include_expired: false,
+            outbox: Outbox {
+                path: file.to_path_buf(),
+            },
+        };
+
+        let err = client.clear_strikes().await.unwrap_err();
+
+        assert!(matches!(err, StrikesError::Network(_)));
+        assert!(client.outbox.read()?.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_should_replay_queued_operations() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/boards/default/strikes/guenther"))
+            .and(body_json(serde_json::json!({"reason": "broke the build"})))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"name": "guenther", "strike_count": 2})),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/boards/default/strikes/heinz/latest"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/boards/default/strikes"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"name": "guenther", "strike_count": 2},
+            ])))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let file = assert_fs::NamedTempFile::new("outbox.json")?;
+        let client = RemoteClient {
+            api_key: "abc".to_string(),
+            base_url: mock_server.uri(),
+            board: "default".to_string(),
+            include_expired: false,
+            outbox: Outbox {
+                path: file.to_path_buf(),
+            },
+        };
+        client.outbox.write(&[
+            QueuedOperation::AddStrike {
+                board: "default".to_string(),
+                name: "guenther".to_string(),
+                reason: Some("broke the build".to_string()),
+            },
+            QueuedOperation::RemoveStrike {
+                board: "default".to_string(),
+                name: "heinz".to_string(),
+            },
+        ])?;
+
+        let report = client.sync().await?;
+
+        assert_eq!(
+            report,
+            SyncReport {
+                replayed: 1,
+                pending: 0,
+                conflicts: vec![
+                    "The unstrike of heinz on default was rejected: 404 Not Found".to_string()
+                ],
+                counts: BTreeMap::from([(
+                    "default".to_string(),
+                    BTreeMap::from([("guenther".to_string(), 2), ("heinz".to_string(), 0)])
+                )]),
+            }
+        );
+        assert!(client.outbox.read()?.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_should_manage_aliases() -> Result<(), Box<dyn std::error::Error>> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/boards/default/aliases/gue"))
+            .and(body_json(serde_json::json!({"name": "guenther"})))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/boards/default/aliases"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!([{"alias": "gue", "name": "guenther"}])),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/boards/default/aliases/gue/merge"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"name": "guenther", "merged": 2})),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = HttpClient {
+            api_key: "abc".to_string(),
+            base_url: mock_server.uri(),
+            board: "default".to_string(),
+        };
+
+        client.put_alias("guenther", "gue").await?;
+
+        assert_eq!(
+            client.get_aliases().await?,
+            BTreeMap::from([("gue".to_string(), "guenther".to_string())])
+        );
+        assert_eq!(client.merge_alias("gue").await?, 2);
 
         Ok(())
     }
@@ -0,0 +1,611 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension, TransactionBehavior};
+use std::collections::{BTreeMap, HashMap};
+
+use super::aliases::{check_alias, fold_aliases, resolve, Aliases};
+use super::client::StrikeClient;
+use super::local_client::{read_boards, Board};
+use super::outbox::SyncReport;
+use crate::{error::StrikesError, export::ExportedTarnished, strike::Strike, tarnished::Tarnished};
+
+pub struct SqliteClient {
+    pub db_path: std::path::PathBuf,
+    pub board: String,
+    pub expire_after_days: Option<u32>,
+}
+
+// Applied in order, the schema version is tracked in `PRAGMA user_version`.
+const MIGRATIONS: &[&str] = &[
+    "CREATE TABLE strikes (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        board TEXT NOT NULL,
+        name TEXT NOT NULL,
+        reason TEXT,
+        timestamp TEXT
+    );
+    CREATE INDEX strikes_by_board_and_name ON strikes (board, name);",
+    "CREATE TABLE aliases (
+        board TEXT NOT NULL,
+        alias TEXT NOT NULL,
+        name TEXT NOT NULL,
+        PRIMARY KEY (board, alias)
+    );",
+];
+
+// Pending migrations run in one immediate transaction, which makes a second process wait and
+// then see the new version. A db.json next to a new database is imported in that same
+// transaction, so a failed import leaves no database behind and is retried on the next run.
+fn migrate(conn: &mut Connection, json_path: Option<&std::path::Path>) -> Result<(), StrikesError> {
+    let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+    let version: usize = tx.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    if version > MIGRATIONS.len() {
+        return Err(StrikesError::Storage(format!(
+            "Database version {} is newer than the supported version {}",
+            version,
+            MIGRATIONS.len()
+        )));
+    }
+    if version == MIGRATIONS.len() {
+        return Ok(());
+    }
+
+    for migration in &MIGRATIONS[version..] {
+        tx.execute_batch(migration)?;
+    }
+    if let Some(json_path) = json_path.filter(|_| version == 0) {
+        import_json_db(&tx, json_path)?;
+    }
+    tx.pragma_update(None, "user_version", MIGRATIONS.len())?;
+
+    tx.commit().map_err(StrikesError::from)
+}
+
+fn import_json_db(conn: &Connection, json_path: &std::path::Path) -> Result<(), StrikesError> {
+    let (boards, aliases) = read_boards(json_path)?;
+
+    for (board, tarnished) in boards {
+        for (name, strikes) in tarnished {
+            for strike in strikes {
+                insert_strike(conn, &board, &name, &strike)?;
+            }
+        }
+    }
+
+    for (board, aliases) in aliases {
+        for (alias, name) in aliases {
+            insert_alias(conn, &board, &alias, &name)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn insert_strike(
+    conn: &Connection,
+    board: &str,
+    name: &str,
+    strike: &Strike,
+) -> rusqlite::Result<usize> {
+    conn.execute(
+        "INSERT INTO strikes (board, name, reason, timestamp) VALUES (?1, ?2, ?3, ?4)",
+        params![board, name, strike.reason, strike.timestamp],
+    )
+}
+
+fn insert_alias(
+    conn: &Connection,
+    board: &str,
+    alias: &str,
+    name: &str,
+) -> rusqlite::Result<usize> {
+    conn.execute(
+        "INSERT OR REPLACE INTO aliases (board, alias, name) VALUES (?1, ?2, ?3)",
+        params![board, alias, name],
+    )
+}
+
+fn query_aliases(conn: &Connection, board: &str) -> rusqlite::Result<Aliases> {
+    let mut statement = conn.prepare("SELECT alias, name FROM aliases WHERE board = ?1")?;
+    let rows = statement.query_map(params![board], |row| Ok((row.get(0)?, row.get(1)?)))?;
+
+    rows.collect()
+}
+
+fn query_strikes(
+    conn: &Connection,
+    board: Option<&str>,
+) -> rusqlite::Result<HashMap<String, Board>> {
+    let mut statement = conn.prepare(
+        "SELECT board, name, reason, timestamp FROM strikes
+         WHERE ?1 IS NULL OR board = ?1
+         ORDER BY timestamp, id",
+    )?;
+    let rows = statement.query_map(params![board], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            Strike {
+                reason: row.get(2)?,
+                timestamp: row.get::<_, Option<DateTime<Utc>>>(3)?,
+            },
+        ))
+    })?;
+
+    let mut boards: HashMap<String, Board> = HashMap::new();
+    for row in rows {
+        let (board, name, strike) = row?;
+        boards
+            .entry(board)
+            .or_default()
+            .entry(name)
+            .or_default()
+            .push(strike);
+    }
+
+    Ok(boards)
+}
+
+// Matches the strikes of board ?1 recorded under the name ?2 or one of its aliases.
+const SPELLINGS: &str = "board = ?1 AND (name = ?2
+    OR name IN (SELECT alias FROM aliases WHERE board = ?1 AND name = ?2))";
+
+impl SqliteClient {
+    // The default path is the JSON database, the SQLite database then lives next to it and
+    // starts out with its strikes.
+    fn sqlite_path(&self) -> std::path::PathBuf {
+        match self.db_path.extension().is_some_and(|ext| ext == "json") {
+            true => self.db_path.with_extension("sqlite"),
+            false => self.db_path.clone(),
+        }
+    }
+
+    fn connect(&self) -> Result<Connection, StrikesError> {
+        let sqlite_path = self.sqlite_path();
+        if let Some(parent) = sqlite_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut conn = Connection::open(&sqlite_path)?;
+        conn.busy_timeout(std::time::Duration::from_secs(5))?;
+        let json_path = self.db_path.with_extension("json");
+        migrate(
+            &mut conn,
+            Some(json_path.as_path()).filter(|path| path.exists()),
+        )?;
+
+        Ok(conn)
+    }
+
+    fn board(&self, conn: &Connection) -> Result<Board, StrikesError> {
+        query_strikes(conn, Some(&self.board))
+            .map(|mut boards| boards.remove(&self.board).unwrap_or_default())
+            .map_err(StrikesError::from)
+    }
+
+    fn folded_board(&self, conn: &Connection) -> Result<Board, StrikesError> {
+        let aliases = query_aliases(conn, &self.board)?;
+
+        Ok(fold_aliases(self.board(conn)?, &aliases))
+    }
+
+    fn active(&self, strikes: &[Strike]) -> Vec<Strike> {
+        strikes
+            .iter()
+            .filter(|strike| {
+                self.expire_after_days
+                    .is_none_or(|days| !strike.is_expired(days))
+            })
+            .cloned()
+            .collect()
+    }
+
+    fn active_board(&self, board: Board) -> Board {
+        board
+            .into_iter()
+            .map(|(name, strikes)| (name, self.active(&strikes)))
+            .filter(|(_, strikes)| !strikes.is_empty())
+            .collect()
+    }
+
+    fn active_count(&self, conn: &Connection, name: &str) -> Result<u32, StrikesError> {
+        let strikes = self.folded_board(conn)?.remove(name).unwrap_or_default();
+
+        Ok(self.active(&strikes).len() as u32)
+    }
+}
+
+#[async_trait]
+impl StrikeClient for SqliteClient {
+    async fn add_strike(&self, name: &str, reason: Option<&str>) -> Result<u32, StrikesError> {
+        let conn = self.connect()?;
+        let aliases = query_aliases(&conn, &self.board)?;
+        let name = resolve(&aliases, name);
+        insert_strike(&conn, &self.board, name, &Strike::now(reason))?;
+
+        self.active_count(&conn, name)
+    }
+
+    async fn remove_strike(&self, name: &str) -> Result<u32, StrikesError> {
+        let conn = self.connect()?;
+        let name = resolve(&query_aliases(&conn, &self.board)?, name).to_string();
+        // The latest strike may still be recorded under an alias.
+        let latest: Option<i64> = conn
+            .query_row(
+                &format!(
+                    "SELECT id FROM strikes WHERE {} ORDER BY timestamp DESC, id DESC LIMIT 1",
+                    SPELLINGS
+                ),
+                params![self.board, name],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        match latest {
+            Some(id) => {
+                conn.execute("DELETE FROM strikes WHERE id = ?1", params![id])?;
+                self.active_count(&conn, &name)
+            }
+            None => Ok(0),
+        }
+    }
+
+    async fn get_tarnished(&self) -> Result<Vec<Tarnished>, StrikesError> {
+        let conn = self.connect()?;
+        let board = self.folded_board(&conn)?;
+
+        Ok(Tarnished::sort_desc_by_strike(Tarnished::from_db(
+            self.active_board(board),
+        )))
+    }
+
+    async fn get_boards(&self) -> Result<BTreeMap<String, Vec<Tarnished>>, StrikesError> {
+        let conn = self.connect()?;
+        let boards = query_strikes(&conn, None)?;
+
+        boards
+            .into_iter()
+            .map(|(board, strikes)| {
+                let strikes = fold_aliases(strikes, &query_aliases(&conn, &board)?);
+                Ok((
+                    board,
+                    Tarnished::sort_desc_by_strike(Tarnished::from_db(self.active_board(strikes))),
+                ))
+            })
+            .collect()
+    }
+
+    async fn get_history(&self, name: &str) -> Result<Vec<Strike>, StrikesError> {
+        let conn = self.connect()?;
+        let name = resolve(&query_aliases(&conn, &self.board)?, name).to_string();
+
+        Ok(self.folded_board(&conn)?.remove(&name).unwrap_or_default())
+    }
+
+    async fn clear_strikes(&self) -> Result<(), StrikesError> {
+        let conn = self.connect()?;
+        conn.execute("DELETE FROM strikes WHERE board = ?1", params![self.board])?;
+
+        Ok(())
+    }
+
+    async fn clear_tarnished(&self, name: &str) -> Result<(), StrikesError> {
+        let conn = self.connect()?;
+        let name = resolve(&query_aliases(&conn, &self.board)?, name).to_string();
+        conn.execute(
+            &format!("DELETE FROM strikes WHERE {}", SPELLINGS),
+            params![self.board, name],
+        )?;
+
+        Ok(())
+    }
+
+    async fn import_strikes(&self, tarnished: &[ExportedTarnished]) -> Result<usize, StrikesError> {
+        let mut conn = self.connect()?;
+        let tx = conn.transaction()?;
+        let aliases = query_aliases(&tx, &self.board)?;
+        let mut imported = 0;
+
+        for entry in tarnished {
+            let name = resolve(&aliases, &entry.name);
+            // Strikes without a timestamp can't be told apart, so their count is authoritative:
+            // only as many are added as the name has fewer of them than the export.
+            let mut untimed: usize = tx.query_row(
+                "SELECT COUNT(*) FROM strikes WHERE board = ?1 AND name = ?2 AND timestamp IS NULL",
+                params![self.board, name],
+                |row| row.get(0),
+            )?;
+            for strike in &entry.strikes {
+                let exists = match strike.timestamp {
+                    Some(timestamp) => tx.query_row(
+                        "SELECT EXISTS (SELECT 1 FROM strikes WHERE board = ?1 AND name = ?2
+                             AND reason IS ?3 AND timestamp = ?4)",
+                        params![self.board, name, strike.reason, timestamp],
+                        |row| row.get(0),
+                    )?,
+                    None if untimed > 0 => {
+                        untimed -= 1;
+                        true
+                    }
+                    None => false,
+                };
+
+                if !exists {
+                    insert_strike(&tx, &self.board, name, strike)?;
+                    imported += 1;
+                }
+            }
+        }
+
+        tx.commit()?;
+
+        Ok(imported)
+    }
+
+    async fn add_alias(&self, name: &str, alias: &str) -> Result<(), StrikesError> {
+        let mut conn = self.connect()?;
+        let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+        let name = check_alias(&query_aliases(&tx, &self.board)?, name, alias)?;
+        insert_alias(&tx, &self.board, alias, &name)?;
+
+        tx.commit().map_err(StrikesError::from)
+    }
+
+    async fn remove_alias(&self, alias: &str) -> Result<(), StrikesError> {
+        let conn = self.connect()?;
+        let removed = conn.execute(
+            "DELETE FROM aliases WHERE board = ?1 AND alias = ?2",
+            params![self.board, alias],
+        )?;
+
+        match removed {
+            0 => Err(StrikesError::NotFound(format!("No alias {}", alias))),
+            _ => Ok(()),
+        }
+    }
+
+    async fn get_aliases(&self) -> Result<Aliases, StrikesError> {
+        let conn = self.connect()?;
+
+        query_aliases(&conn, &self.board).map_err(StrikesError::from)
+    }
+
+    async fn merge_alias(&self, alias: &str) -> Result<u32, StrikesError> {
+        let conn = self.connect()?;
+        let moved = conn.execute(
+            "UPDATE strikes SET name = aliases.name FROM aliases
+             WHERE aliases.board = strikes.board AND aliases.alias = strikes.name
+             AND strikes.board = ?1 AND strikes.name = ?2",
+            params![self.board, alias],
+        )?;
+        if moved == 0 && !query_aliases(&conn, &self.board)?.contains_key(alias) {
+            return Err(StrikesError::NotFound(format!("No alias {}", alias)));
+        }
+
+        Ok(moved as u32)
+    }
+
+    async fn sync(&self) -> Result<SyncReport, StrikesError> {
+        Ok(SyncReport::default())
+    }
+
+    async fn check_health(&self) -> Result<(), StrikesError> {
+        println!("Checking health for sqlite client");
+        self.connect().map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use assert_fs::fixture::{FileWriteStr, PathChild};
+
+    use super::*;
+    use crate::clients::client::DEFAULT_BOARD;
+
+    #[tokio::test]
+    async fn it_should_keep_history_per_board() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = assert_fs::TempDir::new()?;
+        let client = SqliteClient {
+            db_path: dir.child("db.sqlite").to_path_buf(),
+            board: DEFAULT_BOARD.to_string(),
+            expire_after_days: None,
+        };
+        let coffee = SqliteClient {
+            db_path: client.db_path.clone(),
+            board: "coffee".to_string(),
+            expire_after_days: None,
+        };
+
+        client
+            .add_strike("guenther", Some("broke the build"))
+            .await?;
+        assert_eq!(client.add_strike("guenther", None).await?, 2);
+        assert_eq!(coffee.add_strike("guenther", Some("no refill")).await?, 1);
+        assert_eq!(client.remove_strike("guenther").await?, 1);
+
+        let history = client.get_history("guenther").await?;
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].reason.as_deref(), Some("broke the build"));
+
+        let boards = client.get_boards().await?;
+        assert_eq!(
+            boards.keys().collect::<Vec<_>>(),
+            vec!["coffee", DEFAULT_BOARD]
+        );
+
+        coffee.clear_strikes().await?;
+        assert!(coffee.get_tarnished().await?.is_empty());
+        assert_eq!(client.get_tarnished().await?[0].strikes, 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_should_import_an_existing_json_db_once() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = assert_fs::TempDir::new()?;
+        dir.child("db.json").write_str(
+            r#"{"version": 1, "boards": {"default": {"guenther": [
+                {"reason": "typo", "timestamp": "2024-09-01T08:30:00Z"}, {}
+            ]}}}"#,
+        )?;
+        let client = SqliteClient {
+            db_path: dir.child("db.sqlite").to_path_buf(),
+            board: DEFAULT_BOARD.to_string(),
+            expire_after_days: None,
+        };
+
+        assert_eq!(
+            client.get_tarnished().await?,
+            vec![Tarnished {
+                name: "guenther".to_string(),
+                strikes: 2,
+                reasons: vec!["typo".to_string()],
+            }]
+        );
+
+        client.clear_strikes().await?;
+
+        assert!(client.get_tarnished().await?.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_should_keep_the_sqlite_db_next_to_a_json_db_path(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let dir = assert_fs::TempDir::new()?;
+        dir.child("db.json").write_str(
+            r#"{"version": 1, "boards": {"default": {"guenther": [{"reason": "typo"}]}}}"#,
+        )?;
+        let client = SqliteClient {
+            db_path: dir.child("db.json").to_path_buf(),
+            board: DEFAULT_BOARD.to_string(),
+            expire_after_days: None,
+        };
+
+        client.add_strike("heinz", None).await?;
+        let mut tarnished = client.get_tarnished().await?;
+        tarnished.sort_by(|a, b| a.name.cmp(&b.name));
+
+        assert_eq!(
+            tarnished,
+            vec![
+                Tarnished {
+                    name: "guenther".to_string(),
+                    strikes: 1,
+                    reasons: vec!["typo".to_string()],
+                },
+                Tarnished {
+                    name: "heinz".to_string(),
+                    strikes: 1,
+                    reasons: vec![],
+                },
+            ]
+        );
+        assert!(dir.child("db.sqlite").path().exists());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_should_retry_a_failed_import_of_a_json_db() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let dir = assert_fs::TempDir::new()?;
+        dir.child("db.json")
+            .write_str("{\"version\": 1, \"boards\": ")?;
+        let client = SqliteClient {
+            db_path: dir.child("db.sqlite").to_path_buf(),
+            board: DEFAULT_BOARD.to_string(),
+            expire_after_days: None,
+        };
+
+        assert!(matches!(
+            client.get_tarnished().await,
+            Err(StrikesError::Storage(err)) if err.contains("Invalid database")
+        ));
+
+        dir.child("db.json")
+            .write_str(r#"{"version": 1, "boards": {"default": {"guenther": [{}]}}}"#)?;
+
+        assert_eq!(client.get_tarnished().await?[0].strikes, 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_should_import_an_export_without_duplicates(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let dir = assert_fs::TempDir::new()?;
+        let client = SqliteClient {
+            db_path: dir.child("db.sqlite").to_path_buf(),
+            board: DEFAULT_BOARD.to_string(),
+            expire_after_days: None,
+        };
+        let export = vec![ExportedTarnished {
+            name: "guenther".to_string(),
+            strikes: vec![
+                Strike {
+                    reason: Some("typo".to_string()),
+                    timestamp: Some("2024-09-01T08:30:00Z".parse()?),
+                },
+                Strike::default(),
+            ],
+        }];
+
+        assert_eq!(client.import_strikes(&export).await?, 2);
+        assert_eq!(client.import_strikes(&export).await?, 0);
+        assert_eq!(client.get_tarnished().await?[0].strikes, 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_should_resolve_aliases() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = assert_fs::TempDir::new()?;
+        let client = SqliteClient {
+            db_path: dir.child("db.sqlite").to_path_buf(),
+            board: DEFAULT_BOARD.to_string(),
+            expire_after_days: None,
+        };
+
+        client.add_strike("gue", None).await?;
+        client.add_alias("guenther", "gue").await?;
+
+        assert_eq!(client.add_strike("gue", None).await?, 2);
+        assert_eq!(client.get_tarnished().await?[0].name, "guenther");
+        assert_eq!(client.merge_alias("gue").await?, 1);
+        assert_eq!(client.get_history("guenther").await?.len(), 2);
+        assert_eq!(
+            client.get_aliases().await?,
+            Aliases::from([("gue".to_string(), "guenther".to_string())])
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_should_take_back_and_clear_strikes_given_under_an_alias(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let dir = assert_fs::TempDir::new()?;
+        let client = SqliteClient {
+            db_path: dir.child("db.sqlite").to_path_buf(),
+            board: DEFAULT_BOARD.to_string(),
+            expire_after_days: None,
+        };
+
+        client.add_strike("gue", Some("typo")).await?;
+        client.add_alias("guenther", "gue").await?;
+        client.add_strike("gue", None).await?;
+        client.add_strike("guenther", None).await?;
+
+        assert_eq!(client.remove_strike("gue").await?, 2);
+        assert_eq!(client.get_history("gue").await?.len(), 2);
+
+        client.clear_tarnished("gue").await?;
+
+        assert!(client.get_tarnished().await?.is_empty());
+        assert!(client.get_history("guenther").await?.is_empty());
+
+        Ok(())
+    }
+}
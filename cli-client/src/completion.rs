@@ -0,0 +1,208 @@
+use chrono::{DateTime, Duration, Utc};
+use clap::CommandFactory;
+use clap_complete::env::Shells;
+use clap_complete::{CompletionCandidate, Shell};
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+use crate::cli::Cli;
+use crate::clients::{create_client, remote_client::peek_tarnished};
+use crate::configuration::{get_board, get_configuration};
+use crate::error::StrikesError;
+
+pub const COMPLETE_VAR: &str = "COMPLETE";
+
+const CACHE_TTL_SECONDS: i64 = 60;
+
+const REMOTE_TIMEOUT_MILLIS: u64 = 1000;
+
+pub fn command() -> clap::Command {
+    Cli::command().name("strikes")
+}
+
+// The script asks the binary for candidates on every tab, see `CompleteEnv` in main.
+pub fn write_completions(shell: Shell, buf: &mut dyn std::io::Write) -> Result<(), StrikesError> {
+    let shells = Shells::builtins();
+    let completer = shells
+        .completer(&shell.to_string())
+        .ok_or_else(|| StrikesError::Validation(format!("Unsupported shell {}", shell)))?;
+    let bin = std::env::current_exe()?;
+
+    Ok(completer.write_registration(
+        COMPLETE_VAR,
+        "strikes",
+        "strikes",
+        &bin.to_string_lossy(),
+        buf,
+    )?)
+}
+
+pub fn write_man_pages(dir: Option<&Path>) -> Result<(), StrikesError> {
+    match dir {
+        Some(dir) => {
+            std::fs::create_dir_all(dir)?;
+            clap_mangen::generate_to(command(), dir)?;
+        }
+        None => clap_mangen::Man::new(command()).render(&mut std::io::stdout())?,
+    }
+
+    Ok(())
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct NameCache {
+    key: String,
+    created_at: DateTime<Utc>,
+    names: Vec<String>,
+}
+
+fn cache_path() -> Option<PathBuf> {
+    std::env::var_os("XDG_CACHE_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .map(|dir| dir.join("strikes/names.json"))
+}
+
+fn read_cache(path: &Path, key: &str) -> Option<Vec<String>> {
+    let raw = std::fs::read_to_string(path).ok()?;
+    let cache = serde_json::from_str::<NameCache>(&raw).ok()?;
+
+    (cache.key == key && Utc::now() - cache.created_at < Duration::seconds(CACHE_TTL_SECONDS))
+        .then_some(cache.names)
+}
+
+// A cache that can't be written only makes the next completion slower.
+fn write_cache(path: &Path, key: String, names: &[String]) {
+    let cache = NameCache {
+        key,
+        created_at: Utc::now(),
+        names: names.to_vec(),
+    };
+
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    let _ = std::fs::write(path, serde_json::to_string(&cache).unwrap());
+}
+
+// The shell passes the words typed so far after `--`, they may choose another profile or board.
+fn typed_args() -> Cli {
+    let words = std::env::args_os().skip_while(|arg| arg != "--").skip(1);
+    let matches = command()
+        .ignore_errors(true)
+        .try_get_matches_from(words)
+        .unwrap_or_default();
+    let value = |id: &str| {
+        std::iter::once(&matches)
+            .chain(matches.subcommand().map(|(_, matches)| matches))
+            .find_map(|matches| matches.try_get_one::<String>(id).ok().flatten().cloned())
+    };
+
+    Cli {
+        config_path: matches
+            .try_get_one::<PathBuf>("config_path")
+            .ok()
+            .flatten()
+            .cloned(),
+        board: value("board"),
+        profile: value("profile"),
+        command: None,
+    }
+}
+
+fn tarnished_names(args: &Cli) -> Vec<String> {
+    let Ok(settings) = get_configuration(args) else {
+        return vec![];
+    };
+    let board = get_board(args, &settings);
+
+    let Ok(runtime) = tokio::runtime::Runtime::new() else {
+        return vec![];
+    };
+
+    // Reading a local database is fast, only the remote is worth caching.
+    let Some(remote) = settings.remote.as_ref() else {
+        let client = create_client(&settings, &board, false);
+        return runtime
+            .block_on(client.get_tarnished())
+            .map(|tarnished| tarnished.into_iter().map(|entry| entry.name).collect())
+            .unwrap_or_default();
+    };
+
+    let cache_key = format!("{}/boards/{}", remote.base_url, board);
+    let cache_path = cache_path();
+    if let Some(names) = cache_path
+        .as_ref()
+        .and_then(|path| read_cache(path, &cache_key))
+    {
+        return names;
+    }
+
+    // An unreachable server is cached as having no names, so the next tab doesn't wait again.
+    let names: Vec<String> = runtime
+        .block_on(peek_tarnished(
+            remote,
+            &board,
+            std::time::Duration::from_millis(REMOTE_TIMEOUT_MILLIS),
+        ))
+        .map(|tarnished| tarnished.into_iter().map(|entry| entry.name).collect())
+        .unwrap_or_default();
+
+    if let Some(path) = cache_path {
+        write_cache(&path, cache_key, &names);
+    }
+
+    names
+}
+
+pub fn complete_names(current: &OsStr) -> Vec<CompletionCandidate> {
+    let Some(current) = current.to_str() else {
+        return vec![];
+    };
+
+    tarnished_names(&typed_args())
+        .into_iter()
+        .filter(|name| name.starts_with(current))
+        .map(CompletionCandidate::new)
+        .collect()
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    #[test]
+    fn it_should_only_use_a_recent_cache_for_the_same_board(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let dir = assert_fs::TempDir::new()?;
+        let path = dir.path().join("strikes/names.json");
+        let names = vec!["guenther".to_string(), "heinz".to_string()];
+
+        write_cache(
+            &path,
+            "https://example.com/boards/default".to_string(),
+            &names,
+        );
+
+        assert_eq!(
+            read_cache(&path, "https://example.com/boards/default"),
+            Some(names.clone())
+        );
+        assert_eq!(read_cache(&path, "https://example.com/boards/coffee"), None);
+
+        let stale = NameCache {
+            key: "https://example.com/boards/default".to_string(),
+            created_at: Utc::now() - Duration::seconds(CACHE_TTL_SECONDS + 1),
+            names,
+        };
+        std::fs::write(&path, serde_json::to_string(&stale)?)?;
+
+        assert_eq!(
+            read_cache(&path, "https://example.com/boards/default"),
+            None
+        );
+
+        Ok(())
+    }
+}
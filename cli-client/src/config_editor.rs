@@ -0,0 +1,283 @@
+use serde_yaml::{Mapping, Value};
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+
+use crate::cli::Cli;
+use crate::clients::client::DEFAULT_BOARD;
+use crate::configuration::{config_files, get_layers, user_config_path, Settings};
+use crate::error::StrikesError;
+
+pub fn mask_secret(key: &str, value: &str) -> String {
+    if !key.ends_with("api_key") && !key.starts_with("server.api_keys") {
+        return value.to_string();
+    }
+
+    // Keeping the end visible is enough to tell keys apart.
+    let visible = match value.chars().count() {
+        count if count > 8 => 4,
+        _ => 0,
+    };
+    let hidden = value.chars().count() - visible;
+    let tail: String = value.chars().skip(hidden).collect();
+
+    format!("{}{}", "*".repeat(hidden), tail)
+}
+
+pub fn read_config(path: &Path) -> Result<Value, StrikesError> {
+    if !path.exists() {
+        return Ok(Value::Mapping(Mapping::new()));
+    }
+
+    let raw = std::fs::read_to_string(path)?;
+    match serde_yaml::from_str(&raw) {
+        Ok(Value::Null) => Ok(Value::Mapping(Mapping::new())),
+        Ok(config) => Ok(config),
+        Err(err) => Err(StrikesError::Config(format!("{}: {}", path.display(), err))),
+    }
+}
+
+pub fn write_config(path: &Path, config: &Value) -> Result<(), StrikesError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let raw = serde_yaml::to_string(config)
+        .map_err(|err| StrikesError::Config(format!("Failed to render configuration: {}", err)))?;
+
+    Ok(std::fs::write(path, raw)?)
+}
+
+fn set_key(config: &mut Value, key: &str, value: Value) {
+    let mut target = config;
+    for part in key.split('.') {
+        if !target.is_mapping() {
+            *target = Value::Mapping(Mapping::new());
+        }
+        target = target
+            .as_mapping_mut()
+            .unwrap()
+            .entry(Value::String(part.to_string()))
+            .or_insert(Value::Null);
+    }
+
+    *target = value;
+}
+
+// Values are read as YAML so numbers like `decay.expire_after_days` keep their type, unless only
+// the plain string fits the settings, e.g. a numeric API key.
+pub fn set_value(config: &mut Value, key: &str, raw: &str) {
+    let parsed = serde_yaml::from_str::<Value>(raw).unwrap_or(Value::Null);
+    let mut with_string = config.clone();
+    set_key(&mut with_string, key, Value::String(raw.to_string()));
+
+    if parsed.is_string() || parsed.is_null() {
+        *config = with_string;
+        return;
+    }
+
+    set_key(config, key, parsed);
+    if is_valid(config).is_err() && is_valid(&with_string).is_ok() {
+        *config = with_string;
+    }
+}
+
+pub fn is_valid(config: &Value) -> Result<Settings, serde_yaml::Error> {
+    serde_yaml::from_value(config.clone())
+}
+
+fn ask(
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+    question: &str,
+    default: Option<&str>,
+) -> Result<String, StrikesError> {
+    match default {
+        Some(default) => write!(output, "{} [{}]: ", question, default)?,
+        None => write!(output, "{}: ", question)?,
+    }
+    output.flush()?;
+
+    let mut answer = String::new();
+    input.read_line(&mut answer)?;
+
+    Ok(match answer.trim() {
+        "" => default.unwrap_or_default().to_string(),
+        answer => answer.to_string(),
+    })
+}
+
+pub fn prompt_config(
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+    default_db_path: &Path,
+) -> Result<Value, StrikesError> {
+    let mut config = Value::Mapping(Mapping::new());
+
+    let remote = ask(input, output, "Use a remote server? (y/n)", Some("n"))?;
+    if remote.eq_ignore_ascii_case("y") {
+        for (key, question) in [
+            ("remote.base_url", "Base URL of the server"),
+            ("remote.api_key", "API key"),
+        ] {
+            let answer = ask(input, output, question, None)?;
+            if answer.is_empty() {
+                return Err(StrikesError::Validation(format!(
+                    "{} is required",
+                    question
+                )));
+            }
+            set_key(&mut config, key, Value::String(answer));
+        }
+    } else {
+        let db_path = ask(
+            input,
+            output,
+            "Path of the database",
+            Some(&default_db_path.display().to_string()),
+        )?;
+        let backend = ask(input, output, "Backend (json/sqlite)", Some("json"))?;
+        if !["json", "sqlite"].contains(&backend.as_str()) {
+            return Err(StrikesError::Validation(format!(
+                "Unknown backend '{}'",
+                backend
+            )));
+        }
+        set_key(&mut config, "local.db_path", Value::String(db_path));
+        set_key(&mut config, "local.backend", Value::String(backend));
+    }
+
+    let board = ask(input, output, "Default board", Some(DEFAULT_BOARD))?;
+    if board != DEFAULT_BOARD {
+        set_key(&mut config, "default_board", Value::String(board));
+    }
+
+    Ok(config)
+}
+
+pub fn init_config(args: &Cli, force: bool) -> Result<PathBuf, StrikesError> {
+    let path = user_config_path(args).ok_or_else(|| {
+        StrikesError::Config("Set XDG_CONFIG_HOME or HOME to create a configuration".to_string())
+    })?;
+    if path.exists() && !force {
+        return Err(StrikesError::Config(format!(
+            "{} already exists, use --force to overwrite it",
+            path.display()
+        )));
+    }
+
+    let default_db_path = path.with_file_name("db.json");
+    let config = prompt_config(
+        &mut std::io::stdin().lock(),
+        &mut std::io::stdout(),
+        &default_db_path,
+    )?;
+    write_config(&path, &config)?;
+
+    Ok(path)
+}
+
+pub fn set_config(args: &Cli, key: &str, value: &str) -> Result<PathBuf, StrikesError> {
+    let path = user_config_path(args).ok_or_else(|| {
+        StrikesError::Config("Set XDG_CONFIG_HOME or HOME to create a configuration".to_string())
+    })?;
+    let mut config = read_config(&path)?;
+    set_value(&mut config, key, value);
+    write_config(&path, &config)?;
+
+    // Settings are often set one at a time, so an incomplete file is only pointed out.
+    if let Err(err) = is_valid(&config) {
+        eprintln!("The configuration is not complete yet: {}", err);
+    }
+
+    Ok(path)
+}
+
+// Reports the first problem, pinned to a file and line where one file is invalid on its own.
+pub fn validate_config(args: &Cli) -> Result<(), StrikesError> {
+    let mut files = vec![];
+    for file in config_files(args).into_iter().filter(|file| file.exists()) {
+        read_config(&file)?;
+        files.push((std::fs::read_to_string(&file)?, file));
+    }
+
+    let Err(err) = get_layers(args)?.settings() else {
+        return Ok(());
+    };
+
+    for (raw, file) in files {
+        if let Err(file_err) = serde_yaml::from_str::<Option<Settings>>(&raw) {
+            return Err(StrikesError::Config(format!(
+                "{}: {}",
+                file.display(),
+                file_err
+            )));
+        }
+    }
+
+    Err(StrikesError::Config(err.to_string()))
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    #[test]
+    fn it_should_mask_api_keys() {
+        assert_eq!(mask_secret("remote.api_key", "abc"), "***");
+        assert_eq!(
+            mask_secret("remote.api_key", "0123456789abcdef"),
+            "************cdef"
+        );
+        assert_eq!(mask_secret("server.api_keys.0", "abc"), "***");
+        assert_eq!(
+            mask_secret("remote.base_url", "https://example.com"),
+            "https://example.com"
+        );
+    }
+
+    #[test]
+    fn it_should_keep_the_type_of_a_value() {
+        let mut config = Value::Mapping(Mapping::new());
+
+        set_value(&mut config, "decay.expire_after_days", "30");
+        set_value(&mut config, "remote.base_url", "https://example.com");
+        set_value(&mut config, "remote.api_key", "12345");
+
+        let settings = is_valid(&config).unwrap();
+        assert_eq!(settings.decay.unwrap().expire_after_days, 30);
+        assert_eq!(settings.remote.unwrap().api_key, "12345");
+    }
+
+    #[test]
+    fn it_should_prompt_for_a_local_configuration() -> Result<(), Box<dyn std::error::Error>> {
+        let mut output = vec![];
+
+        let config = prompt_config(
+            &mut "n\n\nsqlite\ncoffee\n".as_bytes(),
+            &mut output,
+            Path::new("/home/user/.config/strikes/db.json"),
+        )?;
+
+        assert_eq!(
+            serde_yaml::to_string(&config)?,
+            "local:\n  db_path: /home/user/.config/strikes/db.json\n  backend: sqlite\ndefault_board: coffee\n"
+        );
+        assert!(String::from_utf8(output)?.contains("Backend (json/sqlite) [json]: "));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_should_require_the_remote_settings() {
+        let config = prompt_config(
+            &mut "y\nhttps://example.com\n\n".as_bytes(),
+            &mut vec![],
+            Path::new("db.json"),
+        );
+
+        assert_eq!(
+            config.unwrap_err(),
+            StrikesError::Validation("API key is required".to_string())
+        );
+    }
+}
@@ -1,11 +1,31 @@
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 
 use crate::cli::Cli;
+use crate::clients::client::DEFAULT_BOARD;
+use crate::error::StrikesError;
 
 #[derive(serde::Deserialize, Debug)]
 pub struct Settings {
     pub remote: Option<RemoteSettings>,
     pub local: Option<LocalSettings>,
+    pub default_board: Option<String>,
+    pub decay: Option<DecaySettings>,
+    #[serde(default)]
+    pub thresholds: Vec<Threshold>,
+    pub server: Option<ServerSettings>,
+    pub default_profile: Option<String>,
+    #[serde(default)]
+    pub profiles: HashMap<String, Profile>,
+}
+
+// A profile replaces the top-level remote and local settings, so a local profile is not
+// shadowed by a remote server configured outside of it.
+#[derive(serde::Deserialize, Debug)]
+pub struct Profile {
+    pub remote: Option<RemoteSettings>,
+    pub local: Option<LocalSettings>,
+    pub default_board: Option<String>,
 }
 
 #[derive(serde::Deserialize, Debug)]
@@ -14,48 +34,332 @@ pub struct RemoteSettings {
     pub base_url: String,
 }
 
-#[derive(serde::Deserialize, Debug)]
+#[derive(serde::Deserialize, Debug, Clone)]
 pub struct LocalSettings {
     pub db_path: std::path::PathBuf,
+    #[serde(default)]
+    pub backend: LocalBackend,
+}
+
+#[derive(serde::Deserialize, Debug, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum LocalBackend {
+    #[default]
+    Json,
+    Sqlite,
+}
+
+// Used by `strikes serve`, clients only need the base_url and one of the api_keys.
+#[derive(serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct ServerSettings {
+    #[serde(default = "default_host")]
+    pub host: String,
+    #[serde(default = "default_port")]
+    pub port: u16,
+    #[serde(default)]
+    pub api_keys: Vec<String>,
+}
+
+impl Default for ServerSettings {
+    fn default() -> Self {
+        Self {
+            host: default_host(),
+            port: default_port(),
+            api_keys: vec![],
+        }
+    }
+}
+
+fn default_host() -> String {
+    "127.0.0.1".to_string()
+}
+
+fn default_port() -> u16 {
+    8080
+}
+
+#[derive(serde::Deserialize, Debug)]
+pub struct DecaySettings {
+    pub expire_after_days: u32,
+}
+
+#[derive(serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct Threshold {
+    pub strikes: u32,
+    pub warning: Option<String>,
+    pub command: Option<String>,
 }
 
 impl Default for Settings {
     fn default() -> Self {
         Self {
             remote: None,
+            default_board: None,
+            decay: None,
+            thresholds: vec![],
+            server: None,
+            default_profile: None,
+            profiles: HashMap::new(),
             local: {
                 Some(LocalSettings {
-                    db_path: std::env::var("HOME")
-                        .map(|home| PathBuf::from(home).join(".config/strikes/db.json"))
-                        .unwrap(),
+                    db_path: default_db_path(),
+                    backend: LocalBackend::default(),
                 })
             },
         }
     }
 }
 
-pub fn get_configuration(args: &Cli) -> Settings {
-    let home = &std::env::var("HOME").unwrap();
-    let config_path = args
-        .config_path
+const ENV_OVERRIDES: &[(&str, &str)] = &[
+    ("STRIKES_REMOTE_BASE_URL", "remote.base_url"),
+    ("STRIKES_REMOTE_API_KEY", "remote.api_key"),
+    ("STRIKES_LOCAL_DB_PATH", "local.db_path"),
+    ("STRIKES_LOCAL_BACKEND", "local.backend"),
+    ("STRIKES_DEFAULT_BOARD", "default_board"),
+];
+
+const PROJECT_FILE: &str = ".strikes.yaml";
+
+pub fn config_dir() -> Option<PathBuf> {
+    std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .map(|dir| dir.join("strikes"))
+}
+
+fn default_db_path() -> PathBuf {
+    config_dir().unwrap_or_default().join("db.json")
+}
+
+fn find_project_file() -> Option<PathBuf> {
+    std::env::current_dir()
+        .ok()?
+        .ancestors()
+        .map(|dir| dir.join(PROJECT_FILE))
+        .find(|file| file.is_file())
+}
+
+// The configuration merged from all layers, remembering which layer each value came from.
+#[derive(Debug, Default)]
+pub struct Layers {
+    value: serde_json::Value,
+    pub origins: BTreeMap<String, String>,
+}
+
+impl Layers {
+    pub fn merge(&mut self, origin: &str, layer: serde_json::Value) {
+        if !layer.is_null() {
+            merge_value(&mut self.value, layer, "", origin, &mut self.origins);
+        }
+    }
+
+    // Unlike a merge this drops everything below the key that the lower layers set.
+    fn replace(&mut self, key: &str, origin: &str, value: Option<serde_json::Value>) {
+        self.origins.retain(|path, _| !is_under(path, key));
+        let serde_json::Value::Object(object) = &mut self.value else {
+            return;
+        };
+
+        match value {
+            Some(value) => {
+                for (path, _) in leaves(&value, key) {
+                    self.origins.insert(path, origin.to_string());
+                }
+                object.insert(key.to_string(), value);
+            }
+            None => {
+                object.remove(key);
+            }
+        }
+    }
+
+    pub fn settings(&self) -> Result<Settings, serde_json::Error> {
+        serde_json::from_value(self.value.clone())
+    }
+
+    pub fn get(&self, key: &str) -> Option<&serde_json::Value> {
+        key.split('.')
+            .try_fold(&self.value, |value, key| value.get(key))
+    }
+
+    // Each set value as (key, value, origin), leaving out the profiles that were not applied.
+    pub fn entries(&self) -> Vec<(String, String, String)> {
+        leaves(&self.value, "")
+            .into_iter()
+            .filter(|(path, _)| !is_under(path, "profiles"))
+            .map(|(path, value)| {
+                let value = match value {
+                    serde_json::Value::String(value) => value.clone(),
+                    value => value.to_string(),
+                };
+                let origin = self.origins.get(&path).cloned().unwrap_or_default();
+                (path, value, origin)
+            })
+            .collect()
+    }
+}
+
+fn merge_value(
+    target: &mut serde_json::Value,
+    layer: serde_json::Value,
+    path: &str,
+    origin: &str,
+    origins: &mut BTreeMap<String, String>,
+) {
+    match (target, layer) {
+        (serde_json::Value::Object(target), serde_json::Value::Object(layer)) => {
+            for (key, value) in layer {
+                let path = join(path, &key);
+                let target = target.entry(key).or_insert(serde_json::Value::Null);
+                merge_value(target, value, &path, origin, origins);
+            }
+        }
+        (target, layer) => {
+            origins.retain(|key, _| !is_under(key, path));
+            for (key, _) in leaves(&layer, path) {
+                origins.insert(key, origin.to_string());
+            }
+            *target = layer;
+        }
+    }
+}
+
+fn leaves<'a>(value: &'a serde_json::Value, path: &str) -> Vec<(String, &'a serde_json::Value)> {
+    match value {
+        serde_json::Value::Object(object) => object
+            .iter()
+            .flat_map(|(key, value)| leaves(value, &join(path, key)))
+            .collect(),
+        serde_json::Value::Array(array) => array
+            .iter()
+            .enumerate()
+            .flat_map(|(index, value)| leaves(value, &join(path, &index.to_string())))
+            .collect(),
+        value => vec![(path.to_string(), value)],
+    }
+}
+
+fn join(path: &str, key: &str) -> String {
+    match path {
+        "" => key.to_string(),
+        path => format!("{}.{}", path, key),
+    }
+}
+
+fn is_under(path: &str, key: &str) -> bool {
+    key.is_empty() || path == key || path.starts_with(&format!("{}.", key))
+}
+
+fn nested(key: &str, value: serde_json::Value) -> serde_json::Value {
+    key.rsplit('.')
+        .fold(value, |value, key| serde_json::json!({ key: value }))
+}
+
+// A missing file adds nothing, a file that can't be read or parsed is reported with its path.
+fn read_layer(path: &std::path::Path) -> Result<serde_json::Value, StrikesError> {
+    let raw = match std::fs::read_to_string(path) {
+        Ok(raw) => raw,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(serde_json::Value::Null)
+        }
+        Err(err) => {
+            return Err(StrikesError::Config(format!("{}: {}", path.display(), err)));
+        }
+    };
+
+    serde_yaml::from_str(&raw)
+        .map_err(|err| StrikesError::Config(format!("{}: {}", path.display(), err)))
+}
+
+pub fn user_config_path(args: &Cli) -> Option<PathBuf> {
+    args.config_path
         .clone()
-        .unwrap_or_else(|| PathBuf::from(home).join(".config/strikes/configuration.yaml"));
-
-    let settings = config::Config::builder().add_source(config::File::new(
-        config_path.to_str().unwrap(),
-        config::FileFormat::Yaml,
-    ));
-
-    match settings.build() {
-        Ok(settings) => settings.try_deserialize().map_or_else(
-            |_| Settings::default(),
-            |settings: Settings| match (&settings.remote, &settings.local) {
-                (None, None) => Settings::default(),
-                _ => settings,
-            },
-        ),
-        Err(_) => Settings::default(),
+        .or_else(|| config_dir().map(|dir| dir.join("configuration.yaml")))
+}
+
+// The configuration files that are read, in the order they are layered.
+pub fn config_files(args: &Cli) -> Vec<PathBuf> {
+    user_config_path(args)
+        .into_iter()
+        .chain(find_project_file())
+        .collect()
+}
+
+// Later layers win: defaults, the user configuration, a project's .strikes.yaml, the selected
+// profile, STRIKES_* environment variables and finally the arguments.
+pub fn get_layers(args: &Cli) -> Result<Layers, StrikesError> {
+    let mut layers = Layers::default();
+    layers.merge(
+        "default",
+        serde_json::json!({ "local": { "db_path": default_db_path() } }),
+    );
+
+    for file in config_files(args) {
+        layers.merge(&file.display().to_string(), read_layer(&file)?);
     }
+
+    let default_profile = layers
+        .get("default_profile")
+        .and_then(serde_json::Value::as_str);
+    if let Some(name) = get_profile(args, default_profile) {
+        let profile = layers
+            .get("profiles")
+            .and_then(|profiles| profiles.get(&name))
+            .cloned()
+            .ok_or_else(|| StrikesError::Config(format!("Unknown profile '{}'", name)))?;
+        let origin = format!("profile {}", name);
+
+        layers.replace("remote", &origin, profile.get("remote").cloned());
+        layers.replace("local", &origin, profile.get("local").cloned());
+        if let Some(board) = profile.get("default_board") {
+            layers.replace("default_board", &origin, Some(board.clone()));
+        }
+    }
+
+    for (var, key) in ENV_OVERRIDES {
+        if let Ok(value) = std::env::var(var) {
+            layers.merge(var, nested(key, value.into()));
+        }
+    }
+
+    if let Some(board) = &args.board {
+        layers.merge("--board", nested("default_board", board.as_str().into()));
+    }
+
+    Ok(layers)
+}
+
+pub fn get_configuration(args: &Cli) -> Result<Settings, StrikesError> {
+    let settings = get_layers(args)?.settings().unwrap_or_else(|err| {
+        eprintln!(
+            "Ignoring the invalid configuration ({}), run 'strikes config validate' for details",
+            err
+        );
+        Settings::default()
+    });
+
+    Ok(match (&settings.remote, &settings.local) {
+        (None, None) => Settings {
+            local: Settings::default().local,
+            ..settings
+        },
+        _ => settings,
+    })
+}
+
+pub fn get_profile(args: &Cli, default_profile: Option<&str>) -> Option<String> {
+    args.profile
+        .clone()
+        .or_else(|| std::env::var("STRIKES_PROFILE").ok())
+        .or_else(|| default_profile.map(str::to_string))
+}
+
+pub fn get_board(args: &Cli, settings: &Settings) -> String {
+    args.board
+        .clone()
+        .or_else(|| settings.default_board.clone())
+        .unwrap_or_else(|| DEFAULT_BOARD.to_string())
 }
 
 #[cfg(test)]
@@ -67,18 +371,39 @@ mod tests {
     fn parse_valid_config() {
         let args = Cli {
             config_path: Some(PathBuf::from("tests/fixtures/valid_config.yaml")),
+            board: None,
+            profile: None,
             command: None,
         };
-        let configuration = get_configuration(&args);
+        let configuration = get_configuration(&args).unwrap();
         assert_eq!(configuration.remote.as_ref().unwrap().api_key, "abc");
         assert_eq!(
             configuration.remote.as_ref().unwrap().base_url,
             "https://example.com"
         );
         assert_eq!(
-            configuration.local.unwrap().db_path,
+            configuration.local.as_ref().unwrap().db_path,
             PathBuf::from("/home/user/.config/strikes/db.json")
         );
+        assert_eq!(configuration.local.unwrap().backend, LocalBackend::Json);
+    }
+
+    #[test]
+    fn report_a_config_file_that_is_no_yaml() {
+        let file = assert_fs::NamedTempFile::new("configuration.yaml").unwrap();
+        std::fs::write(file.path(), "remote: [api_key: abc").unwrap();
+        let args = Cli {
+            config_path: Some(file.to_path_buf()),
+            board: None,
+            profile: None,
+            command: None,
+        };
+
+        let err = get_configuration(&args).unwrap_err();
+
+        assert!(
+            matches!(err, StrikesError::Config(err) if err.starts_with(&file.path().display().to_string()))
+        );
     }
 
     #[test]
@@ -86,10 +411,12 @@ mod tests {
         std::env::set_var("HOME", "/home/user");
         let args = Cli {
             config_path: Some(PathBuf::from("tests/fixtures/empty_config.yaml")),
+            board: None,
+            profile: None,
             command: None,
         };
 
-        let configuration = get_configuration(&args);
+        let configuration = get_configuration(&args).unwrap();
 
         assert_eq!(
             configuration.local.unwrap().db_path,
@@ -102,14 +429,156 @@ mod tests {
         std::env::set_var("HOME", "/home/user");
         let args = Cli {
             config_path: Some(PathBuf::from("tests/fixtures/invalid_config.yaml")),
+            board: None,
+            profile: None,
             command: None,
         };
 
-        let configuration = get_configuration(&args);
+        let configuration = get_configuration(&args).unwrap();
 
         assert_eq!(
             configuration.local.unwrap().db_path,
             PathBuf::from("/home/user/.config/strikes/db.json")
         )
     }
+
+    #[test]
+    fn prefer_the_board_argument_over_the_default_board() {
+        let args = Cli {
+            config_path: Some(PathBuf::from("tests/fixtures/valid_config.yaml")),
+            board: None,
+            profile: None,
+            command: None,
+        };
+        let configuration = get_configuration(&args).unwrap();
+
+        assert_eq!(get_board(&args, &configuration), "builds");
+
+        let args = Cli {
+            board: Some("coffee".to_string()),
+            ..args
+        };
+
+        assert_eq!(get_board(&args, &configuration), "coffee");
+    }
+
+    #[test]
+    fn parse_decay_config() {
+        let args = Cli {
+            config_path: Some(PathBuf::from("tests/fixtures/valid_config.yaml")),
+            board: None,
+            profile: None,
+            command: None,
+        };
+        let configuration = get_configuration(&args).unwrap();
+
+        assert_eq!(configuration.decay.unwrap().expire_after_days, 30);
+    }
+
+    #[test]
+    fn parse_thresholds() {
+        let args = Cli {
+            config_path: Some(PathBuf::from("tests/fixtures/valid_config.yaml")),
+            board: None,
+            profile: None,
+            command: None,
+        };
+        let configuration = get_configuration(&args).unwrap();
+
+        assert_eq!(
+            configuration.thresholds,
+            vec![
+                Threshold {
+                    strikes: 3,
+                    warning: Some("Three strikes, you're out!".to_string()),
+                    command: None,
+                },
+                Threshold {
+                    strikes: 5,
+                    warning: None,
+                    command: Some("echo $STRIKES_NAME".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn select_the_default_profile() {
+        let args = Cli {
+            config_path: Some(PathBuf::from("tests/fixtures/profiles_config.yaml")),
+            board: None,
+            profile: None,
+            command: None,
+        };
+        let configuration = get_configuration(&args).unwrap();
+
+        assert_eq!(configuration.remote.as_ref().unwrap().api_key, "work-key");
+        assert_eq!(get_board(&args, &configuration), "builds");
+    }
+
+    #[test]
+    fn prefer_the_profile_argument_over_the_default_profile() {
+        let args = Cli {
+            config_path: Some(PathBuf::from("tests/fixtures/profiles_config.yaml")),
+            board: None,
+            profile: Some("private".to_string()),
+            command: None,
+        };
+        let configuration = get_configuration(&args).unwrap();
+
+        assert!(configuration.remote.is_none());
+        assert_eq!(
+            configuration.local.as_ref().unwrap().db_path,
+            PathBuf::from("/home/user/.config/strikes/private.json")
+        );
+        assert_eq!(get_board(&args, &configuration), "chores");
+    }
+
+    #[test]
+    fn reject_an_unknown_profile() {
+        let args = Cli {
+            config_path: Some(PathBuf::from("tests/fixtures/profiles_config.yaml")),
+            board: None,
+            profile: Some("test".to_string()),
+            command: None,
+        };
+
+        assert_eq!(
+            get_configuration(&args).unwrap_err(),
+            StrikesError::Config("Unknown profile 'test'".to_string())
+        );
+    }
+
+    #[test]
+    fn remember_the_layer_of_each_value() {
+        let mut layers = Layers::default();
+        layers.merge(
+            "configuration.yaml",
+            serde_json::json!({"remote": {"base_url": "https://example.com", "api_key": "abc"}}),
+        );
+        layers.merge(
+            "STRIKES_REMOTE_API_KEY",
+            nested("remote.api_key", "secret".into()),
+        );
+
+        assert_eq!(
+            layers.entries(),
+            vec![
+                (
+                    "remote.api_key".to_string(),
+                    "secret".to_string(),
+                    "STRIKES_REMOTE_API_KEY".to_string()
+                ),
+                (
+                    "remote.base_url".to_string(),
+                    "https://example.com".to_string(),
+                    "configuration.yaml".to_string()
+                ),
+            ]
+        );
+
+        layers.replace("remote", "profile private", None);
+
+        assert!(layers.entries().is_empty());
+    }
 }
@@ -0,0 +1,110 @@
+#[derive(Debug, Clone, PartialEq)]
+pub enum StrikesError {
+    // The request never reached the server or timed out in front of it, so retrying later is
+    // safe and may succeed.
+    Network(String),
+    Auth(String),
+    NotFound(String),
+    Validation(String),
+    Storage(String),
+    Config(String),
+    Usage(String),
+}
+
+impl StrikesError {
+    // Follows sysexits.h so scripts can tell failures apart without parsing the message.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            StrikesError::Usage(_) => 64,
+            StrikesError::Validation(_) => 65,
+            StrikesError::NotFound(_) => 66,
+            StrikesError::Network(_) => 69,
+            StrikesError::Storage(_) => 74,
+            StrikesError::Auth(_) => 77,
+            StrikesError::Config(_) => 78,
+        }
+    }
+}
+
+impl std::fmt::Display for StrikesError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StrikesError::Network(err)
+            | StrikesError::Auth(err)
+            | StrikesError::NotFound(err)
+            | StrikesError::Validation(err)
+            | StrikesError::Storage(err)
+            | StrikesError::Config(err)
+            | StrikesError::Usage(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for StrikesError {}
+
+impl From<std::io::Error> for StrikesError {
+    fn from(err: std::io::Error) -> Self {
+        StrikesError::Storage(err.to_string())
+    }
+}
+
+impl From<rusqlite::Error> for StrikesError {
+    fn from(err: rusqlite::Error) -> Self {
+        StrikesError::Storage(err.to_string())
+    }
+}
+
+// Only failures to connect and timeouts count as an unavailable server. Anything else may have
+// happened after the server applied the request, and retrying it would apply it twice.
+impl From<reqwest::Error> for StrikesError {
+    fn from(err: reqwest::Error) -> Self {
+        match err.is_connect() || err.is_timeout() {
+            true => StrikesError::Network(err.to_string()),
+            false => StrikesError::Storage(err.to_string()),
+        }
+    }
+}
+
+impl From<reqwest::StatusCode> for StrikesError {
+    fn from(status: reqwest::StatusCode) -> Self {
+        match status {
+            reqwest::StatusCode::UNAUTHORIZED | reqwest::StatusCode::FORBIDDEN => {
+                StrikesError::Auth(status.to_string())
+            }
+            reqwest::StatusCode::NOT_FOUND => StrikesError::NotFound(status.to_string()),
+            // A gateway answers 502 or 504 when the lambda failed or timed out, possibly after
+            // the change was stored, so no server error counts as an unavailable server.
+            status if status.is_server_error() => StrikesError::Storage(status.to_string()),
+            status => StrikesError::Validation(status.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    #[test]
+    fn it_should_map_http_statuses_to_errors() {
+        assert_eq!(
+            StrikesError::from(reqwest::StatusCode::FORBIDDEN),
+            StrikesError::Auth("403 Forbidden".to_string())
+        );
+        assert_eq!(
+            StrikesError::from(reqwest::StatusCode::NOT_FOUND),
+            StrikesError::NotFound("404 Not Found".to_string())
+        );
+        assert_eq!(
+            StrikesError::from(reqwest::StatusCode::BAD_GATEWAY),
+            StrikesError::Storage("502 Bad Gateway".to_string())
+        );
+        assert_eq!(
+            StrikesError::from(reqwest::StatusCode::INTERNAL_SERVER_ERROR),
+            StrikesError::Storage("500 Internal Server Error".to_string())
+        );
+        assert_eq!(
+            StrikesError::from(reqwest::StatusCode::BAD_REQUEST),
+            StrikesError::Validation("400 Bad Request".to_string())
+        );
+    }
+}
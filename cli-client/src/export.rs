@@ -0,0 +1,124 @@
+use chrono::{DateTime, Utc};
+
+use crate::{clients::client::StrikeClient, error::StrikesError, strike::Strike};
+
+pub const EXPORT_VERSION: u32 = 1;
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+pub struct Export {
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub board: String,
+    pub tarnished: Vec<ExportedTarnished>,
+}
+
+pub use strikes_core::wire::ExportedTarnished;
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+pub enum ImportMode {
+    #[default]
+    Merge,
+    Replace,
+}
+
+pub async fn export_board(client: &dyn StrikeClient, board: &str) -> Result<Export, StrikesError> {
+    let mut tarnished = vec![];
+
+    for entry in client.get_tarnished().await? {
+        let mut strikes = Strike::sort_asc_by_timestamp(client.get_history(&entry.name).await?);
+        // Strikes counted before any history was kept only survive as a count.
+        let missing = (entry.strikes as usize).saturating_sub(strikes.len());
+        strikes.splice(0..0, vec![Strike::default(); missing]);
+
+        tarnished.push(ExportedTarnished {
+            name: entry.name,
+            strikes,
+        });
+    }
+
+    Ok(Export {
+        version: EXPORT_VERSION,
+        exported_at: Utc::now(),
+        board: board.to_string(),
+        tarnished,
+    })
+}
+
+pub async fn import_board(
+    client: &dyn StrikeClient,
+    export: &Export,
+    mode: ImportMode,
+) -> Result<usize, StrikesError> {
+    if mode == ImportMode::Replace {
+        client.clear_strikes().await?;
+    }
+
+    client.import_strikes(&export.tarnished).await
+}
+
+pub fn read_export(raw: &str) -> Result<Export, StrikesError> {
+    let version = serde_json::from_str::<serde_json::Value>(raw)
+        .map_err(|err| StrikesError::Validation(err.to_string()))?
+        .get("version")
+        .and_then(serde_json::Value::as_u64)
+        .ok_or(StrikesError::Validation(
+            "Missing export version".to_string(),
+        ))?;
+
+    if version > EXPORT_VERSION as u64 {
+        return Err(StrikesError::Validation(format!(
+            "Export version {} is newer than the supported version {}",
+            version, EXPORT_VERSION
+        )));
+    }
+
+    serde_json::from_str(raw).map_err(|err| StrikesError::Validation(err.to_string()))
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    #[test]
+    fn it_should_reject_newer_export_versions() {
+        let raw = serde_json::json!({
+            "version": EXPORT_VERSION + 1,
+            "exported_at": "2024-09-01T08:30:00Z",
+            "board": "default",
+            "tarnished": [],
+        })
+        .to_string();
+
+        assert!(matches!(
+            read_export(&raw),
+            Err(StrikesError::Validation(err)) if err.contains("newer")
+        ));
+    }
+
+    #[test]
+    fn it_should_read_an_export() {
+        let raw = r#"{
+            "version": 1,
+            "exported_at": "2024-09-01T08:30:00Z",
+            "board": "coffee",
+            "tarnished": [{"name": "guenther", "strikes": [{"reason": "no refill"}, {}]}]
+        }"#;
+
+        let export = read_export(raw).unwrap();
+
+        assert_eq!(export.board, "coffee");
+        assert_eq!(
+            export.tarnished,
+            vec![ExportedTarnished {
+                name: "guenther".to_string(),
+                strikes: vec![
+                    Strike {
+                        reason: Some("no refill".to_string()),
+                        timestamp: None,
+                    },
+                    Strike::default(),
+                ],
+            }]
+        );
+    }
+}
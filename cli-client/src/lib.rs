@@ -1,5 +1,12 @@
 pub mod cli;
 pub mod clients;
+pub mod completion;
+pub mod config_editor;
 pub mod configuration;
+pub mod error;
+pub mod export;
 pub mod output;
-pub mod tarnished;
+pub mod server;
+pub mod thresholds;
+
+pub use strikes_core::{strike, tarnished};
@@ -1,53 +1,246 @@
 use clap::Parser;
-use strikes::cli::{Cli, Command};
-use strikes::clients::client::StrikeClient;
-use strikes::clients::local_client::LocalClient;
-use strikes::clients::remote_client::RemoteClient;
-use strikes::configuration::{get_configuration, Settings};
-use strikes::output::{print_as_table, print_strikes};
+use clap_complete::CompleteEnv;
+use strikes::cli::{AliasCommand, Cli, Command, ConfigCommand};
+use strikes::clients::create_client;
+use strikes::completion::{command, write_completions, write_man_pages, COMPLETE_VAR};
+use strikes::config_editor::{init_config, mask_secret, set_config, validate_config};
+use strikes::configuration::{get_board, get_configuration, get_layers, Settings};
+use strikes::error::StrikesError;
+use strikes::export::{export_board, import_board, read_export};
+use strikes::output::{
+    print_aliases, print_config, print_history, print_strikes, print_sync_report,
+};
+use strikes::server::{serve, Server};
+use strikes::strike::Strike;
 use strikes::tarnished::Tarnished;
+use strikes::thresholds::apply_thresholds;
+
+fn main() {
+    // Answers the completion script before anything else is written to stdout.
+    CompleteEnv::with_factory(command)
+        .var(COMPLETE_VAR)
+        .complete();
+
+    run();
+}
 
 #[tokio::main]
-async fn main() {
+async fn run() {
     let args = &Cli::parse();
-    let settings = &get_configuration(args);
-    let client = create_client(settings);
 
-    match &args.clone().command {
-        Some(Command::Strike { name }) => match client.add_strike(name).await {
-            Ok(strikes) => print_strikes(name, strikes),
-            Err(err) => eprintln!("Failed to add strike: {}", err),
+    match &args.command {
+        Some(Command::Config {
+            command: ConfigCommand::Init { force },
+        }) => match init_config(args, *force) {
+            Ok(path) => {
+                println!("Wrote the configuration to {}", path.display());
+                let settings = load_settings(args);
+                let client = create_client(&settings, &get_board(args, &settings), false);
+                match client.check_health().await {
+                    Ok(_) => println!("Everything is fine!"),
+                    Err(err) => fail("Failed to check health", err),
+                }
+            }
+            Err(err) => fail("Failed to create configuration", err),
         },
-        Some(Command::Ls) => match client.get_tarnished().await {
-            Ok(tarnished) => print_as_table(Tarnished::sort_desc_by_strike(tarnished)),
-            Err(err) => eprintln!("Failed to get strikes: {}", err),
+        Some(Command::Config {
+            command: ConfigCommand::Show { origin },
+        }) => match get_layers(args) {
+            Ok(layers) => print_config(
+                layers
+                    .entries()
+                    .into_iter()
+                    .map(|(key, value, origin)| {
+                        let value = mask_secret(&key, &value);
+                        (key, value, origin)
+                    })
+                    .collect(),
+                *origin,
+            ),
+            Err(err) => fail("Failed to show configuration", err),
         },
-        Some(Command::Clear) => match client.clear_strikes().await {
-            Ok(()) => println!("All strikes have been cleared!"),
-            Err(err) => eprintln!("Faild to clear all strikes: {}", err),
+        Some(Command::Config {
+            command: ConfigCommand::Set { key, value },
+        }) => match set_config(args, key, value) {
+            Ok(path) => println!("Set {} in {}", key, path.display()),
+            Err(err) => fail("Failed to set configuration", err),
         },
-        Some(Command::CheckHealth) => match client.check_health().await {
-            Ok(_) => println!("Everything is fine!"),
-            Err(err) => eprintln!("Failed to check health: {}", err),
+        Some(Command::Config {
+            command: ConfigCommand::Validate,
+        }) => match validate_config(args) {
+            Ok(()) => println!("The configuration is valid"),
+            Err(err) => fail("Invalid configuration", err),
         },
-        None => {
-            eprintln!("No supported command was provided");
+        Some(Command::Completions { shell }) => {
+            if let Err(err) = write_completions(*shell, &mut std::io::stdout()) {
+                fail("Failed to write completions", err);
+            }
+        }
+        Some(Command::Man { dir }) => {
+            if let Err(err) = write_man_pages(dir.as_deref()) {
+                fail("Failed to write man pages", err);
+            }
+        }
+        Some(Command::Serve { host, port }) => {
+            let settings = &load_settings(args);
+            let config = settings.server.clone().unwrap_or_default();
+            let served = match Server::from_settings(settings) {
+                Ok(server) => {
+                    serve(
+                        server,
+                        host.as_deref().unwrap_or(&config.host),
+                        port.unwrap_or(config.port),
+                    )
+                    .await
+                }
+                Err(err) => Err(err),
+            };
+            if let Err(err) = served {
+                fail("Failed to serve", err);
+            }
         }
+        Some(command) => run_with_client(args, command).await,
+        None => fail(
+            "No command was provided",
+            StrikesError::Usage("Run 'strikes help' to list the commands".to_string()),
+        ),
     }
 }
 
-fn create_client(settings: &Settings) -> Box<dyn StrikeClient> {
-    settings.remote.as_ref().map_or_else(
-        || {
-            Box::new(LocalClient {
-                db_path: settings.local.as_ref().unwrap().db_path.clone(),
-            }) as Box<dyn StrikeClient>
+fn load_settings(args: &Cli) -> Settings {
+    get_configuration(args).unwrap_or_else(|err| fail("Failed to load configuration", err))
+}
+
+// Only the commands that read or write strikes create a client, so a broken configuration
+// doesn't keep completions or the config commands from running.
+async fn run_with_client(args: &Cli, command: &Command) {
+    let settings = &load_settings(args);
+    let board = &get_board(args, settings);
+    let include_expired = matches!(
+        command,
+        Command::Ls {
+            include_expired: true,
+            ..
+        } | Command::Export { .. }
+    );
+    let client = create_client(settings, board, include_expired);
+
+    match command {
+        Command::Strike {
+            name,
+            reason,
+            output,
+        } => match client.add_strike(name, reason.as_deref()).await {
+            Ok(strikes) => {
+                println!("{}", output.renderer().render_strikes(name, strikes));
+                if let Err(err) = apply_thresholds(name, strikes, &settings.thresholds) {
+                    fail("Failed to apply threshold", err);
+                }
+            }
+            Err(err) => fail("Failed to add strike", err),
+        },
+        Command::Unstrike { name } => match client.remove_strike(name).await {
+            Ok(strikes) => print_strikes(name, strikes),
+            Err(err) => fail("Failed to remove strike", err),
+        },
+        Command::Ls {
+            all_boards: true,
+            output,
+            ..
+        } => match client.get_boards().await {
+            Ok(boards) => println!("{}", output.renderer().render_boards(&boards)),
+            Err(err) => fail("Failed to get strikes", err),
+        },
+        Command::Ls {
+            verbose, output, ..
+        } => match client.get_tarnished().await {
+            Ok(tarnished) => println!(
+                "{}",
+                output
+                    .renderer()
+                    .render_tarnished(&Tarnished::sort_desc_by_strike(tarnished), *verbose)
+            ),
+            Err(err) => fail("Failed to get strikes", err),
         },
-        |remote| {
-            Box::new(RemoteClient {
-                api_key: remote.api_key.clone(),
-                base_url: remote.base_url.clone(),
-            }) as Box<dyn StrikeClient>
+        Command::History { name } => match client.get_history(name).await {
+            Ok(strikes) => print_history(name, Strike::sort_asc_by_timestamp(strikes)),
+            Err(err) => fail("Failed to get history", err),
         },
-    )
+        Command::Clear { name: None, .. } => match client.clear_strikes().await {
+            Ok(()) => println!("All strikes have been cleared!"),
+            Err(err) => fail("Failed to clear all strikes", err),
+        },
+        Command::Clear {
+            name: Some(name), ..
+        } => match client.clear_tarnished(name).await {
+            Ok(()) => println!("All strikes of {} have been cleared!", name),
+            Err(err) => fail(&format!("Failed to clear strikes of {}", name), err),
+        },
+        Command::Export { file } => match export_board(client.as_ref(), board).await {
+            Ok(export) => {
+                let json = serde_json::to_string_pretty(&export).unwrap();
+                match file {
+                    Some(file) => match std::fs::write(file, json) {
+                        Ok(()) => println!("Exported {} to {}", board, file.display()),
+                        Err(err) => fail("Failed to write export", err.into()),
+                    },
+                    None => println!("{}", json),
+                }
+            }
+            Err(err) => fail("Failed to export strikes", err),
+        },
+        Command::Import { file, mode } => {
+            let export = std::fs::read_to_string(file)
+                .map_err(StrikesError::from)
+                .and_then(|raw| read_export(&raw));
+            match export {
+                Ok(export) => match import_board(client.as_ref(), &export, *mode).await {
+                    Ok(imported) => println!("Imported {} strikes into {}", imported, board),
+                    Err(err) => fail("Failed to import strikes", err),
+                },
+                Err(err) => fail("Failed to read export", err),
+            }
+        }
+        Command::Sync => match client.sync().await {
+            Ok(report) => print_sync_report(report),
+            Err(err) => fail("Failed to sync", err),
+        },
+        Command::Alias {
+            command: AliasCommand::Add { name, alias },
+        } => match client.add_alias(name, alias).await {
+            Ok(()) => println!("Strikes for {} now go to {}", alias, name),
+            Err(err) => fail("Failed to add alias", err),
+        },
+        Command::Alias {
+            command: AliasCommand::Ls,
+        } => match client.get_aliases().await {
+            Ok(aliases) => print_aliases(aliases),
+            Err(err) => fail("Failed to get aliases", err),
+        },
+        Command::Alias {
+            command: AliasCommand::Rm { alias },
+        } => match client.remove_alias(alias).await {
+            Ok(()) => println!("Removed the alias {}", alias),
+            Err(err) => fail("Failed to remove alias", err),
+        },
+        Command::Alias {
+            command: AliasCommand::Merge { alias },
+        } => match client.merge_alias(alias).await {
+            Ok(merged) => println!("Merged {} strikes of {}", merged, alias),
+            Err(err) => fail("Failed to merge alias", err),
+        },
+        Command::CheckHealth => match client.check_health().await {
+            Ok(_) => println!("Everything is fine!"),
+            Err(err) => fail("Failed to check health", err),
+        },
+        Command::Config { .. }
+        | Command::Completions { .. }
+        | Command::Man { .. }
+        | Command::Serve { .. } => unreachable!("{:?} is run without a client", command),
+    }
+}
+
+fn fail(context: &str, err: StrikesError) -> ! {
+    eprintln!("{}: {}", context, err);
+    std::process::exit(err.exit_code())
 }
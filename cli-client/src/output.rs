@@ -1,22 +1,447 @@
-use crate::tarnished::Tarnished;
+use crate::{clients::outbox::SyncReport, strike::Strike, tarnished::Tarnished};
 use comfy_table::Table;
+use serde_json::json;
+use std::collections::BTreeMap;
 
-pub fn print_as_table(tarnished: Vec<Tarnished>) {
-    if tarnished.is_empty() {
-        println!("No one has been tarnished yet!");
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+pub enum OutputFormat {
+    #[default]
+    Table,
+    Json,
+    Csv,
+    Yaml,
+    Markdown,
+    Tsv,
+}
+
+impl OutputFormat {
+    pub fn renderer(&self) -> Box<dyn Renderer> {
+        match self {
+            OutputFormat::Table => Box::new(TableRenderer),
+            OutputFormat::Json => Box::new(JsonRenderer),
+            OutputFormat::Csv => Box::new(CsvRenderer),
+            OutputFormat::Yaml => Box::new(YamlRenderer),
+            OutputFormat::Markdown => Box::new(MarkdownRenderer),
+            OutputFormat::Tsv => Box::new(TsvRenderer),
+        }
+    }
+}
+
+pub trait Renderer {
+    fn render_tarnished(&self, tarnished: &[Tarnished], verbose: bool) -> String;
+    fn render_boards(&self, boards: &BTreeMap<String, Vec<Tarnished>>) -> String;
+    fn render_strikes(&self, name: &str, strikes: u32) -> String;
+}
+
+pub struct TableRenderer;
+pub struct JsonRenderer;
+pub struct YamlRenderer;
+pub struct CsvRenderer;
+pub struct TsvRenderer;
+pub struct MarkdownRenderer;
+
+struct Rows {
+    header: Vec<&'static str>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Rows {
+    fn from_tarnished(tarnished: &[Tarnished], verbose: bool) -> Self {
+        let header = match verbose {
+            true => vec!["Tarnished", "Strikes", "Reasons"],
+            false => vec!["Tarnished", "Strikes"],
+        };
+        let rows = tarnished
+            .iter()
+            .map(|tarnished| {
+                let mut row = vec![tarnished.name.clone(), tarnished.strikes.to_string()];
+                if verbose {
+                    row.push(tarnished.reasons.join("\n"));
+                }
+                row
+            })
+            .collect();
+
+        Rows { header, rows }
+    }
+
+    fn from_boards(boards: &BTreeMap<String, Vec<Tarnished>>) -> Self {
+        let rows = boards
+            .iter()
+            .flat_map(|(board, tarnished)| {
+                tarnished.iter().map(|tarnished| {
+                    vec![
+                        board.clone(),
+                        tarnished.name.clone(),
+                        tarnished.strikes.to_string(),
+                    ]
+                })
+            })
+            .collect();
+
+        Rows {
+            header: vec!["Board", "Tarnished", "Strikes"],
+            rows,
+        }
+    }
+
+    fn from_strikes(name: &str, strikes: u32) -> Self {
+        Rows {
+            header: vec!["Tarnished", "Strikes"],
+            rows: vec![vec![name.to_string(), strikes.to_string()]],
+        }
+    }
+
+    fn delimited(&self, delimiter: &str, escape: fn(&str) -> String) -> String {
+        std::iter::once(self.header.join(delimiter))
+            .chain(self.rows.iter().map(|row| {
+                row.iter()
+                    .map(|cell| escape(cell))
+                    .collect::<Vec<_>>()
+                    .join(delimiter)
+            }))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl TableRenderer {
+    fn table(rows: Rows) -> String {
+        if rows.rows.is_empty() {
+            return "No one has been tarnished yet!".to_string();
+        }
+
+        let mut table = Table::new();
+        table.set_header(rows.header);
+        for row in rows.rows {
+            table.add_row(row);
+        }
+
+        table.to_string()
+    }
+}
+
+impl Renderer for TableRenderer {
+    fn render_tarnished(&self, tarnished: &[Tarnished], verbose: bool) -> String {
+        TableRenderer::table(Rows::from_tarnished(tarnished, verbose))
+    }
+
+    fn render_boards(&self, boards: &BTreeMap<String, Vec<Tarnished>>) -> String {
+        TableRenderer::table(Rows::from_boards(boards))
+    }
+
+    fn render_strikes(&self, name: &str, strikes: u32) -> String {
+        format!("{} has now {} strikes!", name, strikes)
+    }
+}
+
+fn tarnished_as_json(tarnished: &[Tarnished]) -> serde_json::Value {
+    tarnished
+        .iter()
+        .map(|tarnished| {
+            json!({
+                "name": tarnished.name,
+                "strikes": tarnished.strikes,
+                "reasons": tarnished.reasons,
+            })
+        })
+        .collect()
+}
+
+fn boards_as_json(boards: &BTreeMap<String, Vec<Tarnished>>) -> serde_json::Value {
+    boards
+        .iter()
+        .map(|(board, tarnished)| (board.clone(), tarnished_as_json(tarnished)))
+        .collect::<serde_json::Map<_, _>>()
+        .into()
+}
+
+fn strikes_as_json(name: &str, strikes: u32) -> serde_json::Value {
+    json!({"name": name, "strikes": strikes})
+}
+
+impl Renderer for JsonRenderer {
+    fn render_tarnished(&self, tarnished: &[Tarnished], _verbose: bool) -> String {
+        serde_json::to_string_pretty(&tarnished_as_json(tarnished)).unwrap()
+    }
+
+    fn render_boards(&self, boards: &BTreeMap<String, Vec<Tarnished>>) -> String {
+        serde_json::to_string_pretty(&boards_as_json(boards)).unwrap()
+    }
+
+    fn render_strikes(&self, name: &str, strikes: u32) -> String {
+        serde_json::to_string_pretty(&strikes_as_json(name, strikes)).unwrap()
+    }
+}
+
+impl Renderer for YamlRenderer {
+    fn render_tarnished(&self, tarnished: &[Tarnished], _verbose: bool) -> String {
+        yaml(&tarnished_as_json(tarnished))
+    }
+
+    fn render_boards(&self, boards: &BTreeMap<String, Vec<Tarnished>>) -> String {
+        yaml(&boards_as_json(boards))
+    }
+
+    fn render_strikes(&self, name: &str, strikes: u32) -> String {
+        yaml(&strikes_as_json(name, strikes))
+    }
+}
+
+fn yaml(value: &serde_json::Value) -> String {
+    serde_yaml::to_string(value).unwrap().trim_end().to_string()
+}
+
+// Quotes a field only when it would otherwise break the row, as described in RFC 4180.
+fn escape_csv(cell: &str) -> String {
+    if cell.contains([',', '"', '\n']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+fn escape_tsv(cell: &str) -> String {
+    cell.replace(['\t', '\n'], " ")
+}
+
+fn escape_markdown(cell: &str) -> String {
+    cell.replace('|', "\\|").replace('\n', "<br>")
+}
+
+impl Renderer for CsvRenderer {
+    fn render_tarnished(&self, tarnished: &[Tarnished], verbose: bool) -> String {
+        Rows::from_tarnished(tarnished, verbose).delimited(",", escape_csv)
+    }
+
+    fn render_boards(&self, boards: &BTreeMap<String, Vec<Tarnished>>) -> String {
+        Rows::from_boards(boards).delimited(",", escape_csv)
+    }
+
+    fn render_strikes(&self, name: &str, strikes: u32) -> String {
+        Rows::from_strikes(name, strikes).delimited(",", escape_csv)
+    }
+}
+
+impl Renderer for TsvRenderer {
+    fn render_tarnished(&self, tarnished: &[Tarnished], verbose: bool) -> String {
+        Rows::from_tarnished(tarnished, verbose).delimited("\t", escape_tsv)
+    }
+
+    fn render_boards(&self, boards: &BTreeMap<String, Vec<Tarnished>>) -> String {
+        Rows::from_boards(boards).delimited("\t", escape_tsv)
+    }
+
+    fn render_strikes(&self, name: &str, strikes: u32) -> String {
+        Rows::from_strikes(name, strikes).delimited("\t", escape_tsv)
+    }
+}
+
+impl MarkdownRenderer {
+    fn markdown(rows: Rows) -> String {
+        let separator = rows.header.iter().map(|_| "---").collect::<Vec<_>>();
+
+        std::iter::once(&rows.header)
+            .chain(std::iter::once(&separator))
+            .map(|row| format!("| {} |", row.join(" | ")))
+            .chain(rows.rows.iter().map(|row| {
+                format!(
+                    "| {} |",
+                    row.iter()
+                        .map(|cell| escape_markdown(cell))
+                        .collect::<Vec<_>>()
+                        .join(" | ")
+                )
+            }))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl Renderer for MarkdownRenderer {
+    fn render_tarnished(&self, tarnished: &[Tarnished], verbose: bool) -> String {
+        MarkdownRenderer::markdown(Rows::from_tarnished(tarnished, verbose))
+    }
+
+    fn render_boards(&self, boards: &BTreeMap<String, Vec<Tarnished>>) -> String {
+        MarkdownRenderer::markdown(Rows::from_boards(boards))
+    }
+
+    fn render_strikes(&self, name: &str, strikes: u32) -> String {
+        MarkdownRenderer::markdown(Rows::from_strikes(name, strikes))
+    }
+}
+
+pub fn print_strikes(name: &str, strikes: u32) {
+    println!("{}", TableRenderer.render_strikes(name, strikes));
+}
+
+// Goes to stderr, so the output of a strike stays parseable in every format.
+pub fn print_warning(warning: &str) {
+    eprintln!("{}", warning);
+}
+
+pub fn print_sync_report(report: SyncReport) {
+    for conflict in &report.conflicts {
+        eprintln!("{}", conflict);
+    }
+
+    println!("Replayed {} queued operations", report.replayed);
+    if report.pending > 0 {
+        println!(
+            "{} operations are still queued, the server is unavailable",
+            report.pending
+        );
+    }
+
+    let boards = report
+        .counts
+        .into_iter()
+        .map(|(board, counts)| {
+            let tarnished = counts
+                .into_iter()
+                .map(|(name, strikes)| Tarnished {
+                    name,
+                    strikes,
+                    reasons: vec![],
+                })
+                .collect();
+            (board, tarnished)
+        })
+        .collect::<BTreeMap<_, _>>();
+    if boards
+        .values()
+        .any(|tarnished: &Vec<Tarnished>| !tarnished.is_empty())
+    {
+        println!("{}", TableRenderer.render_boards(&boards));
+    }
+}
+
+pub fn print_config(entries: Vec<(String, String, String)>, with_origin: bool) {
+    let mut table = Table::new();
+    match with_origin {
+        true => table.set_header(vec!["Setting", "Value", "Origin"]),
+        false => table.set_header(vec!["Setting", "Value"]),
+    };
+
+    for (key, value, origin) in entries {
+        match with_origin {
+            true => table.add_row(vec![key, value, origin]),
+            false => table.add_row(vec![key, value]),
+        };
+    }
+
+    println!("{table}");
+}
+
+pub fn print_aliases(aliases: BTreeMap<String, String>) {
+    if aliases.is_empty() {
+        println!("No aliases yet!");
+        return;
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec!["Alias", "Tarnished"]);
+
+    for (alias, name) in aliases {
+        table.add_row(vec![alias, name]);
+    }
+
+    println!("{table}");
+}
+
+pub fn print_history(name: &str, strikes: Vec<Strike>) {
+    if strikes.is_empty() {
+        println!("{} has not been tarnished yet!", name);
         return;
     }
 
     let mut table = Table::new();
-    table.set_header(vec!["Tarnished", "Strikes"]);
+    table.set_header(vec!["Date", "Reason"]);
 
-    for tarnished in tarnished {
-        table.add_row(vec![tarnished.name, tarnished.strikes.to_string()]);
+    for strike in strikes {
+        table.add_row(vec![
+            strike.timestamp.map_or("unknown".to_string(), |timestamp| {
+                timestamp.format("%Y-%m-%d %H:%M:%S").to_string()
+            }),
+            strike.reason.unwrap_or_default(),
+        ]);
     }
 
     println!("{table}");
 }
 
-pub fn print_strikes(name: &str, strikes: u8) {
-    println!("{} has now {} strikes!", name, strikes);
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    fn tarnished() -> Vec<Tarnished> {
+        vec![
+            Tarnished {
+                name: "heinz".to_string(),
+                strikes: 2,
+                reasons: vec![
+                    "broke the build".to_string(),
+                    "said \"oops\", twice".to_string(),
+                ],
+            },
+            Tarnished {
+                name: "guenther".to_string(),
+                strikes: 1,
+                reasons: vec![],
+            },
+        ]
+    }
+
+    #[test]
+    fn it_should_render_json() {
+        let rendered = JsonRenderer.render_tarnished(&tarnished(), false);
+
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&rendered).unwrap(),
+            json!([
+                {"name": "heinz", "strikes": 2, "reasons": ["broke the build", "said \"oops\", twice"]},
+                {"name": "guenther", "strikes": 1, "reasons": []},
+            ])
+        );
+    }
+
+    #[test]
+    fn it_should_render_yaml() {
+        assert_eq!(
+            YamlRenderer.render_strikes("guenther", 3),
+            "name: guenther\nstrikes: 3"
+        );
+    }
+
+    #[test]
+    fn it_should_render_csv() {
+        assert_eq!(
+            CsvRenderer.render_tarnished(&tarnished(), true),
+            "Tarnished,Strikes,Reasons\n\
+             heinz,2,\"broke the build\nsaid \"\"oops\"\", twice\"\n\
+             guenther,1,"
+        );
+    }
+
+    #[test]
+    fn it_should_render_tsv() {
+        assert_eq!(
+            TsvRenderer.render_tarnished(&tarnished(), false),
+            "Tarnished\tStrikes\nheinz\t2\nguenther\t1"
+        );
+    }
+
+    #[test]
+    fn it_should_render_markdown() {
+        let boards = BTreeMap::from([("coffee".to_string(), tarnished())]);
+
+        assert_eq!(
+            MarkdownRenderer.render_boards(&boards),
+            "| Board | Tarnished | Strikes |\n\
+             | --- | --- | --- |\n\
+             | coffee | heinz | 2 |\n\
+             | coffee | guenther | 1 |"
+        );
+    }
 }
@@ -0,0 +1,618 @@
+use axum::{
+    body::Bytes,
+    extract::{Path, Query, Request, State},
+    http::StatusCode,
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{delete, get, post, put},
+    Json, Router,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::clients::{
+    aliases::resolve,
+    client::{StrikeClient, DEFAULT_BOARD},
+    create_local_client,
+};
+use crate::configuration::{LocalSettings, Settings};
+use crate::{error::StrikesError, strike::Strike};
+use strikes_core::{
+    name::Name,
+    wire::{
+        AliasRequest, AliasResponse, BoardStrikesResponse, ExportedTarnished, ImportResponse,
+        MergeResponse, StrikeCountResponse, StrikeRequest, StrikesPage, StrikesResponse,
+        DEFAULT_PAGE_SIZE,
+    },
+};
+
+// Answers like the lambdas do, so a RemoteClient can't tell it apart from the AWS stack.
+pub struct Server {
+    pub local: LocalSettings,
+    pub api_keys: Vec<String>,
+    pub expire_after_days: Option<u32>,
+    pub thresholds: Vec<u32>,
+}
+
+type Params = Path<HashMap<String, String>>;
+
+#[derive(serde::Deserialize, Default)]
+struct ListQuery {
+    #[serde(default)]
+    include_expired: bool,
+    limit: Option<usize>,
+    cursor: Option<String>,
+}
+
+impl Server {
+    pub fn from_settings(settings: &Settings) -> Result<Self, StrikesError> {
+        let api_keys = settings
+            .server
+            .as_ref()
+            .map(|server| server.api_keys.clone())
+            .unwrap_or_default();
+        if api_keys.is_empty() {
+            return Err(StrikesError::Config(
+                "Add at least one key to server.api_keys before serving".to_string(),
+            ));
+        }
+
+        Ok(Server {
+            local: settings
+                .local
+                .clone()
+                .or(Settings::default().local)
+                .unwrap(),
+            api_keys,
+            expire_after_days: settings.decay.as_ref().map(|decay| decay.expire_after_days),
+            thresholds: settings
+                .thresholds
+                .iter()
+                .map(|threshold| threshold.strikes)
+                .collect(),
+        })
+    }
+
+    fn client(&self, board: &str, include_expired: bool) -> Box<dyn StrikeClient> {
+        create_local_client(
+            &self.local,
+            board,
+            self.expire_after_days.filter(|_| !include_expired),
+        )
+    }
+}
+
+// The local clients read, write and lock files synchronously, so they run on the blocking pool
+// where a held lock can't stall the workers serving other requests.
+async fn blocking<T, F, Fut>(client: Box<dyn StrikeClient>, operation: F) -> Result<T, StrikesError>
+where
+    T: Send + 'static,
+    F: FnOnce(Box<dyn StrikeClient>) -> Fut + Send + 'static,
+    Fut: std::future::Future<Output = Result<T, StrikesError>>,
+{
+    let runtime = tokio::runtime::Handle::current();
+    tokio::task::spawn_blocking(move || runtime.block_on(operation(client)))
+        .await
+        .map_err(|err| StrikesError::Storage(err.to_string()))?
+}
+
+impl IntoResponse for StrikesError {
+    fn into_response(self) -> Response {
+        let status = match self {
+            StrikesError::Validation(_) | StrikesError::Usage(_) => StatusCode::BAD_REQUEST,
+            StrikesError::Auth(_) => StatusCode::FORBIDDEN,
+            StrikesError::NotFound(_) => StatusCode::NOT_FOUND,
+            StrikesError::Network(_) => StatusCode::BAD_GATEWAY,
+            StrikesError::Storage(_) | StrikesError::Config(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        (status, self.to_string()).into_response()
+    }
+}
+
+fn board(params: &HashMap<String, String>) -> Result<Name, StrikesError> {
+    Name::parse(params.get("board").map_or(DEFAULT_BOARD, String::as_str))
+        .map_err(|_| StrikesError::Validation("Invalid board".to_string()))
+}
+
+fn name(params: &HashMap<String, String>, key: &str) -> Result<Name, StrikesError> {
+    Name::parse(&params[key]).map_err(|_| StrikesError::Validation(format!("Invalid {}", key)))
+}
+
+fn body<T: serde::de::DeserializeOwned>(raw: &[u8]) -> Result<T, StrikesError> {
+    serde_json::from_slice(raw)
+        .map_err(|_| StrikesError::Validation("Invalid request body".to_string()))
+}
+
+async fn check_api_key(
+    State(server): State<Arc<Server>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let api_key = request
+        .headers()
+        .get("x-api-key")
+        .and_then(|key| key.to_str().ok());
+
+    match api_key {
+        Some(key) if server.api_keys.iter().any(|api_key| api_key == key) => {
+            next.run(request).await
+        }
+        _ => StrikesError::Auth("Forbidden".to_string()).into_response(),
+    }
+}
+
+async fn health() -> &'static str {
+    "OK"
+}
+
+async fn get_boards(
+    State(server): State<Arc<Server>>,
+    Query(query): Query<ListQuery>,
+) -> Result<Json<Vec<BoardStrikesResponse>>, StrikesError> {
+    let boards = blocking(
+        server.client(DEFAULT_BOARD, query.include_expired),
+        |client| async move { client.get_boards().await },
+    )
+    .await?;
+
+    Ok(Json(
+        boards
+            .into_iter()
+            .flat_map(|(board, tarnished)| {
+                tarnished
+                    .into_iter()
+                    .map(move |tarnished| BoardStrikesResponse {
+                        board: board.clone(),
+                        strikes: tarnished.into(),
+                    })
+            })
+            .collect(),
+    ))
+}
+
+async fn get_strikes(
+    State(server): State<Arc<Server>>,
+    Path(params): Params,
+    Query(query): Query<ListQuery>,
+) -> Result<Response, StrikesError> {
+    if query.limit == Some(0) {
+        return Err(StrikesError::Validation("Invalid limit".to_string()));
+    }
+
+    let tarnished = blocking(
+        server.client(&board(&params)?, query.include_expired),
+        |client| async move { client.get_tarnished().await },
+    )
+    .await?;
+    let strikes = tarnished
+        .into_iter()
+        .map(StrikesResponse::from)
+        .collect::<Vec<_>>();
+
+    match (query.limit, query.cursor.as_deref()) {
+        (None, None) => Ok(Json(strikes).into_response()),
+        (limit, cursor) => Ok(Json(StrikesPage::from_list(
+            strikes,
+            cursor,
+            limit.unwrap_or(DEFAULT_PAGE_SIZE),
+        ))
+        .into_response()),
+    }
+}
+
+async fn import_strikes(
+    State(server): State<Arc<Server>>,
+    Path(params): Params,
+    raw: Bytes,
+) -> Result<Json<ImportResponse>, StrikesError> {
+    let board = board(&params)?;
+    let tarnished = body::<Vec<ExportedTarnished>>(&raw)?
+        .into_iter()
+        .map(|tarnished| {
+            Ok(ExportedTarnished {
+                name: Name::parse(&tarnished.name)
+                    .map_err(|_| StrikesError::Validation("Invalid username".to_string()))?
+                    .into(),
+                ..tarnished
+            })
+        })
+        .collect::<Result<Vec<_>, StrikesError>>()?;
+
+    let imported = blocking(server.client(&board, false), |client| async move {
+        client.import_strikes(&tarnished).await
+    })
+    .await?;
+
+    Ok(Json(ImportResponse { imported }))
+}
+
+async fn delete_strikes(
+    State(server): State<Arc<Server>>,
+    Path(params): Params,
+) -> Result<String, StrikesError> {
+    blocking(
+        server.client(&board(&params)?, false),
+        |client| async move { client.clear_strikes().await },
+    )
+    .await?;
+
+    Ok("All strikes deleted".to_string())
+}
+
+async fn put_strike(
+    State(server): State<Arc<Server>>,
+    Path(params): Params,
+    raw: Bytes,
+) -> Result<Json<StrikeCountResponse>, StrikesError> {
+    let (board, user) = (board(&params)?, name(&params, "user")?);
+    let request = match raw.as_ref() {
+        [] => StrikeRequest::default(),
+        raw => body::<StrikeRequest>(raw)?,
+    };
+
+    // Answers with the name the strike was counted for, like the lambda does.
+    let (name, strike_count) = blocking(server.client(&board, false), |client| async move {
+        let name = resolve(&client.get_aliases().await?, &user).to_string();
+        let strike_count = client.add_strike(&name, request.reason.as_deref()).await?;
+        Ok((name, strike_count))
+    })
+    .await?;
+
+    Ok(Json(StrikeCountResponse {
+        name,
+        strike_count,
+        threshold_reached: Some(server.thresholds.contains(&strike_count)),
+    }))
+}
+
+async fn delete_user_strikes(
+    State(server): State<Arc<Server>>,
+    Path(params): Params,
+) -> Result<String, StrikesError> {
+    let (board, user) = (board(&params)?, name(&params, "user")?);
+    let name = blocking(server.client(&board, false), |client| async move {
+        let name = resolve(&client.get_aliases().await?, &user).to_string();
+        client.clear_tarnished(&name).await?;
+        Ok(name)
+    })
+    .await?;
+
+    Ok(format!("All strikes of {} deleted", name))
+}
+
+async fn delete_latest_strike(
+    State(server): State<Arc<Server>>,
+    Path(params): Params,
+) -> Result<Json<StrikeCountResponse>, StrikesError> {
+    let (board, user) = (board(&params)?, name(&params, "user")?);
+    let (name, strike_count) = blocking(server.client(&board, false), |client| async move {
+        let name = resolve(&client.get_aliases().await?, &user).to_string();
+        let strike_count = client.remove_strike(&name).await?;
+        Ok((name, strike_count))
+    })
+    .await?;
+
+    Ok(Json(StrikeCountResponse {
+        name,
+        strike_count,
+        threshold_reached: None,
+    }))
+}
+
+async fn get_strike_history(
+    State(server): State<Arc<Server>>,
+    Path(params): Params,
+) -> Result<Json<Vec<Strike>>, StrikesError> {
+    let (board, user) = (board(&params)?, name(&params, "user")?);
+    let history = blocking(server.client(&board, true), |client| async move {
+        client.get_history(&user).await
+    })
+    .await?;
+
+    Ok(Json(Strike::sort_asc_by_timestamp(history)))
+}
+
+async fn get_aliases(
+    State(server): State<Arc<Server>>,
+    Path(params): Params,
+) -> Result<Json<Vec<AliasResponse>>, StrikesError> {
+    let aliases = blocking(
+        server.client(&board(&params)?, false),
+        |client| async move { client.get_aliases().await },
+    )
+    .await?;
+
+    Ok(Json(
+        aliases
+            .into_iter()
+            .map(|(alias, name)| AliasResponse { alias, name })
+            .collect(),
+    ))
+}
+
+async fn put_alias(
+    State(server): State<Arc<Server>>,
+    Path(params): Params,
+    raw: Bytes,
+) -> Result<String, StrikesError> {
+    let (board, alias) = (board(&params)?, name(&params, "alias")?);
+    let name = Name::parse(&body::<AliasRequest>(&raw)?.name)
+        .map_err(|_| StrikesError::Validation("Invalid request body".to_string()))?;
+    let message = format!("Strikes for {} now go to {}", alias, name);
+    blocking(server.client(&board, false), |client| async move {
+        client.add_alias(&name, &alias).await
+    })
+    .await?;
+
+    Ok(message)
+}
+
+async fn delete_alias(
+    State(server): State<Arc<Server>>,
+    Path(params): Params,
+) -> Result<String, StrikesError> {
+    let (board, alias) = (board(&params)?, name(&params, "alias")?);
+    let message = format!("Removed the alias {}", alias);
+    blocking(server.client(&board, false), |client| async move {
+        client.remove_alias(&alias).await
+    })
+    .await?;
+
+    Ok(message)
+}
+
+async fn merge_alias(
+    State(server): State<Arc<Server>>,
+    Path(params): Params,
+) -> Result<Json<MergeResponse>, StrikesError> {
+    let (board, alias) = (board(&params)?, name(&params, "alias")?);
+    let response = blocking(server.client(&board, false), |client| async move {
+        let name = client
+            .get_aliases()
+            .await?
+            .remove(alias.as_str())
+            .ok_or_else(|| StrikesError::NotFound(format!("No alias {}", alias)))?;
+        let merged = client.merge_alias(&alias).await?;
+        Ok(MergeResponse { name, merged })
+    })
+    .await?;
+
+    Ok(Json(response))
+}
+
+fn strikes_routes() -> Router<Arc<Server>> {
+    Router::new()
+        .route(
+            "/strikes",
+            get(get_strikes).post(import_strikes).delete(delete_strikes),
+        )
+        .route(
+            "/strikes/:user",
+            put(put_strike).delete(delete_user_strikes),
+        )
+        .route("/strikes/:user/latest", delete(delete_latest_strike))
+        .route("/strikes/:user/history", get(get_strike_history))
+}
+
+// The routes without a board are the ones from before boards existed and use the default board.
+pub fn router(server: Server) -> Router {
+    let server = Arc::new(server);
+    let board_routes = strikes_routes()
+        .route("/aliases", get(get_aliases))
+        .route("/aliases/:alias", put(put_alias).delete(delete_alias))
+        .route("/aliases/:alias/merge", post(merge_alias));
+
+    Router::new()
+        .route("/health", get(health))
+        .route("/boards", get(get_boards))
+        .nest("/boards/:board", board_routes)
+        .merge(strikes_routes())
+        .layer(middleware::from_fn_with_state(
+            server.clone(),
+            check_api_key,
+        ))
+        .with_state(server)
+}
+
+pub async fn serve(server: Server, host: &str, port: u16) -> Result<(), StrikesError> {
+    let listener = tokio::net::TcpListener::bind((host, port)).await?;
+    println!(
+        "Serving {} on http://{}",
+        server.local.db_path.display(),
+        listener.local_addr()?
+    );
+
+    axum::serve(listener, router(server))
+        .with_graceful_shutdown(async {
+            tokio::signal::ctrl_c().await.ok();
+        })
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use crate::clients::{outbox::Outbox, remote_client::RemoteClient};
+    use crate::configuration::LocalBackend;
+
+    async fn start(db_path: &std::path::Path) -> Result<String, Box<dyn std::error::Error>> {
+        let listener = tokio::net::TcpListener::bind(("127.0.0.1", 0)).await?;
+        let base_url = format!("http://{}", listener.local_addr()?);
+        let app = router(Server {
+            local: LocalSettings {
+                db_path: db_path.to_path_buf(),
+                backend: LocalBackend::Json,
+            },
+            api_keys: vec!["abc".to_string()],
+            expire_after_days: None,
+            thresholds: vec![3],
+        });
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        Ok(base_url)
+    }
+
+    fn remote(base_url: &str, api_key: &str, dir: &std::path::Path) -> RemoteClient {
+        RemoteClient {
+            api_key: api_key.to_string(),
+            base_url: base_url.to_string(),
+            board: "builds".to_string(),
+            include_expired: false,
+            outbox: Outbox {
+                path: dir.join("outbox.json"),
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn it_should_serve_a_remote_client() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = assert_fs::TempDir::new()?;
+        let base_url = start(&dir.path().join("db.json")).await?;
+        let client = remote(&base_url, "abc", dir.path());
+
+        client
+            .add_strike("guenther", Some("broke the build"))
+            .await?;
+        assert_eq!(client.add_strike("guenther", None).await?, 2);
+        client
+            .import_strikes(&[ExportedTarnished {
+                name: "Heinz".to_string(),
+                strikes: vec![Strike::default()],
+            }])
+            .await?;
+
+        let tarnished = client.get_tarnished().await?;
+        assert_eq!(tarnished[0].name, "guenther");
+        assert_eq!(tarnished[0].reasons, vec!["broke the build".to_string()]);
+        assert_eq!(tarnished[1].name, "heinz");
+        assert_eq!(client.get_boards().await?["builds"].len(), 2);
+        assert_eq!(client.remove_strike("guenther").await?, 1);
+        assert_eq!(client.get_history("guenther").await?.len(), 1);
+
+        client.clear_strikes().await?;
+        assert!(client.get_tarnished().await?.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_should_count_strikes_of_an_alias_for_its_name(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let dir = assert_fs::TempDir::new()?;
+        let base_url = start(&dir.path().join("db.json")).await?;
+        let client = remote(&base_url, "abc", dir.path());
+        client.add_alias("guenther", "gue").await?;
+
+        let response = reqwest::Client::new()
+            .put(format!("{}/boards/builds/strikes/gue", base_url))
+            .header("x-api-key", "abc")
+            .send()
+            .await?
+            .text()
+            .await?;
+        let response = serde_json::from_str::<StrikeCountResponse>(&response)?;
+        client
+            .import_strikes(&[ExportedTarnished {
+                name: "gue".to_string(),
+                strikes: vec![Strike::default()],
+            }])
+            .await?;
+
+        assert_eq!(response.name, "guenther");
+        assert_eq!(
+            client
+                .get_tarnished()
+                .await?
+                .into_iter()
+                .map(|tarnished| (tarnished.name, tarnished.strikes))
+                .collect::<Vec<_>>(),
+            vec![("guenther".to_string(), 2)]
+        );
+        assert_eq!(client.get_history("guenther").await?.len(), 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_should_take_back_strikes_of_an_alias_for_its_name(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let dir = assert_fs::TempDir::new()?;
+        let base_url = start(&dir.path().join("db.json")).await?;
+        let client = remote(&base_url, "abc", dir.path());
+        client.add_alias("guenther", "gue").await?;
+        client.add_strike("gue", Some("late")).await?;
+        client.add_strike("gue", None).await?;
+
+        let response = reqwest::Client::new()
+            .delete(format!("{}/boards/builds/strikes/gue/latest", base_url))
+            .header("x-api-key", "abc")
+            .send()
+            .await?
+            .text()
+            .await?;
+        let response = serde_json::from_str::<StrikeCountResponse>(&response)?;
+
+        assert_eq!(
+            (response.name.as_str(), response.strike_count),
+            ("guenther", 1)
+        );
+        assert_eq!(client.get_history("gue").await?.len(), 1);
+
+        client.clear_tarnished("gue").await?;
+
+        assert!(client.get_tarnished().await?.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_should_answer_while_the_database_is_locked(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let dir = assert_fs::TempDir::new()?;
+        let base_url = start(&dir.path().join("db.json")).await?;
+        let lock = std::fs::File::create(dir.path().join("db.json.lock"))?;
+        lock.lock()?;
+
+        let client = remote(&base_url, "abc", dir.path());
+        let listing = tokio::spawn(async move { client.get_tarnished().await });
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        let health = reqwest::Client::new()
+            .get(format!("{}/health", base_url))
+            .header("x-api-key", "abc")
+            .timeout(std::time::Duration::from_secs(1))
+            .send()
+            .await?;
+
+        assert_eq!(health.status(), reqwest::StatusCode::OK);
+        assert!(!listing.is_finished());
+
+        drop(lock);
+
+        assert!(listing.await?.is_ok());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_should_reject_unknown_api_keys() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = assert_fs::TempDir::new()?;
+        let base_url = start(&dir.path().join("db.json")).await?;
+
+        let result = remote(&base_url, "xyz", dir.path()).get_tarnished().await;
+
+        assert!(matches!(result, Err(StrikesError::Auth(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_should_require_an_api_key() {
+        assert!(matches!(
+            Server::from_settings(&Settings::default()),
+            Err(StrikesError::Config(_))
+        ));
+    }
+}
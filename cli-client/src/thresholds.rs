@@ -0,0 +1,77 @@
+use crate::configuration::Threshold;
+use crate::error::StrikesError;
+use crate::output::print_warning;
+
+pub fn reached(thresholds: &[Threshold], strikes: u32) -> Vec<&Threshold> {
+    thresholds
+        .iter()
+        .filter(|threshold| threshold.strikes == strikes)
+        .collect()
+}
+
+pub fn apply_thresholds(
+    name: &str,
+    strikes: u32,
+    thresholds: &[Threshold],
+) -> Result<(), StrikesError> {
+    for threshold in reached(thresholds, strikes) {
+        if let Some(warning) = &threshold.warning {
+            print_warning(warning);
+        }
+
+        if let Some(command) = &threshold.command {
+            let status = std::process::Command::new("sh")
+                .arg("-c")
+                .arg(command)
+                .env("STRIKES_NAME", name)
+                .env("STRIKES_COUNT", strikes.to_string())
+                .status()
+                .map_err(|err| StrikesError::Config(format!("'{}' failed: {}", command, err)))?;
+
+            if !status.success() {
+                return Err(StrikesError::Config(format!(
+                    "'{}' exited with {}",
+                    command, status
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    #[test]
+    fn it_should_only_reach_thresholds_with_the_exact_strike_count() {
+        let thresholds = vec![
+            Threshold {
+                strikes: 3,
+                warning: Some("Three strikes, you're out!".to_string()),
+                command: None,
+            },
+            Threshold {
+                strikes: 5,
+                warning: None,
+                command: Some("true".to_string()),
+            },
+        ];
+
+        assert_eq!(reached(&thresholds, 3), vec![&thresholds[0]]);
+        assert!(reached(&thresholds, 4).is_empty());
+        assert!(reached(&thresholds, 6).is_empty());
+    }
+
+    #[test]
+    fn it_should_fail_if_the_command_fails() {
+        let thresholds = vec![Threshold {
+            strikes: 5,
+            warning: None,
+            command: Some("test \"$STRIKES_COUNT\" = 4".to_string()),
+        }];
+
+        assert!(apply_thresholds("guenther", 5, &thresholds).is_err());
+    }
+}
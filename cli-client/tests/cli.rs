@@ -1,5 +1,5 @@
 use assert_cmd::prelude::*;
-use assert_fs::fixture::FileWriteStr;
+use assert_fs::fixture::{FileWriteStr, PathChild, PathCreateDir};
 use predicates::prelude::*;
 use std::process::Command;
 
@@ -38,6 +38,295 @@ fn it_should_add_strike() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn it_should_not_lose_strikes_from_parallel_invocations() -> Result<(), Box<dyn std::error::Error>>
+{
+    let db_file = assert_fs::NamedTempFile::new("db.json")?;
+    let config_file = assert_fs::NamedTempFile::new("./tests/fixtures/configuration.yaml")?;
+    config_file.write_str(
+        format!(
+            "{{\"local\": {{\"db_path\": \"{}\"}}}}",
+            db_file.path().to_str().unwrap()
+        )
+        .as_str(),
+    )?;
+
+    let children = (0..20)
+        .map(|_| {
+            Command::cargo_bin("strikes")
+                .unwrap()
+                .arg("--config-path")
+                .arg(config_file.path())
+                .arg("strike")
+                .arg("guenther")
+                .stdout(std::process::Stdio::null())
+                .spawn()
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    for mut child in children {
+        assert!(child.wait()?.success());
+    }
+
+    let mut cmd = Command::cargo_bin("strikes")?;
+    cmd.arg("--config-path")
+        .arg(config_file.path())
+        .arg("ls")
+        .arg("--output")
+        .arg("csv");
+    cmd.assert()
+        .success()
+        .stdout("Tarnished,Strikes\nguenther,20\n");
+
+    Ok(())
+}
+
+#[test]
+fn it_should_store_strikes_in_sqlite() -> Result<(), Box<dyn std::error::Error>> {
+    let db_dir = assert_fs::TempDir::new()?;
+    let config_file = assert_fs::NamedTempFile::new("./tests/fixtures/configuration.yaml")?;
+    config_file.write_str(
+        format!(
+            "local:\n  db_path: {}\n  backend: sqlite\n",
+            db_dir.child("db.sqlite").path().to_str().unwrap()
+        )
+        .as_str(),
+    )?;
+
+    for reason in ["typo", "flaky test"] {
+        let mut cmd = Command::cargo_bin("strikes")?;
+        cmd.arg("--config-path")
+            .arg(config_file.path())
+            .arg("strike")
+            .arg("guenther")
+            .arg("--reason")
+            .arg(reason);
+        cmd.assert().success();
+    }
+
+    let mut cmd = Command::cargo_bin("strikes")?;
+    cmd.arg("--config-path")
+        .arg(config_file.path())
+        .arg("ls")
+        .arg("--verbose")
+        .arg("--output")
+        .arg("csv");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("guenther,2,\"typo\nflaky test\""));
+
+    assert!(db_dir.child("db.sqlite").path().exists());
+
+    Ok(())
+}
+
+#[test]
+fn it_should_use_the_profile_from_the_environment() -> Result<(), Box<dyn std::error::Error>> {
+    let work_db = assert_fs::NamedTempFile::new("work.json")?;
+    let private_db = assert_fs::NamedTempFile::new("private.json")?;
+    let config_file = assert_fs::NamedTempFile::new("./tests/fixtures/configuration.yaml")?;
+    config_file.write_str(
+        format!(
+            "default_profile: work\nprofiles:\n  work:\n    local:\n      db_path: {}\n  private:\n    local:\n      db_path: {}\n",
+            work_db.path().to_str().unwrap(),
+            private_db.path().to_str().unwrap()
+        )
+        .as_str(),
+    )?;
+
+    let mut cmd = Command::cargo_bin("strikes")?;
+    cmd.env("STRIKES_PROFILE", "private")
+        .arg("--config-path")
+        .arg(config_file.path())
+        .arg("strike")
+        .arg("guenther");
+    cmd.assert().success();
+
+    assert!(private_db.path().exists());
+    assert!(!work_db.path().exists());
+
+    let mut cmd = Command::cargo_bin("strikes")?;
+    cmd.env("STRIKES_PROFILE", "private")
+        .arg("--config-path")
+        .arg(config_file.path())
+        .arg("--profile")
+        .arg("test")
+        .arg("ls");
+    cmd.assert()
+        .code(78)
+        .stderr(predicate::str::contains("Unknown profile 'test'"));
+
+    Ok(())
+}
+
+#[test]
+fn it_should_layer_the_configuration() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = assert_fs::TempDir::new()?;
+    dir.child("xdg/strikes/configuration.yaml").write_str(
+        "remote:\n  base_url: https://example.com\n  api_key: abc\ndefault_board: builds\n",
+    )?;
+    dir.child("project/.strikes.yaml")
+        .write_str("default_board: coffee\n")?;
+    dir.child("project/src").create_dir_all()?;
+
+    let mut cmd = Command::cargo_bin("strikes")?;
+    cmd.current_dir(dir.child("project/src").path())
+        .env("XDG_CONFIG_HOME", dir.child("xdg").path())
+        .env("STRIKES_REMOTE_API_KEY", "secret")
+        .arg("config")
+        .arg("show")
+        .arg("--origin");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::is_match(
+            r"default_board +\| coffee +\| .*project/\.strikes\.yaml",
+        )?)
+        .stdout(predicate::str::is_match(
+            r"remote\.api_key +\| \*{6} +\| STRIKES_REMOTE_API_KEY",
+        )?)
+        .stdout(predicate::str::is_match(
+            r"remote\.base_url +\| https://example\.com +\| .*xdg/strikes/configuration\.yaml",
+        )?);
+
+    let mut cmd = Command::cargo_bin("strikes")?;
+    cmd.current_dir(dir.child("project/src").path())
+        .env("XDG_CONFIG_HOME", dir.child("xdg").path())
+        .arg("--board")
+        .arg("tea")
+        .arg("config")
+        .arg("show")
+        .arg("--origin");
+    cmd.assert().success().stdout(predicate::str::is_match(
+        r"default_board +\| tea +\| --board",
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn it_should_use_sqlite_with_the_default_db_path() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = assert_fs::TempDir::new()?;
+    dir.child("xdg/strikes/configuration.yaml")
+        .write_str("local:\n  backend: sqlite\n")?;
+
+    let mut cmd = Command::cargo_bin("strikes")?;
+    cmd.env("XDG_CONFIG_HOME", dir.child("xdg").path())
+        .args(["strike", "guenther"]);
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("strikes")?;
+    cmd.env("XDG_CONFIG_HOME", dir.child("xdg").path())
+        .args(["ls", "-o", "csv"]);
+    cmd.assert()
+        .success()
+        .stdout("Tarnished,Strikes\nguenther,1\n");
+
+    assert!(dir.child("xdg/strikes/db.sqlite").path().exists());
+
+    Ok(())
+}
+
+#[test]
+fn it_should_create_and_change_a_configuration() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = assert_fs::TempDir::new()?;
+    let config_file = dir.child("configuration.yaml");
+
+    let mut cmd = assert_cmd::Command::cargo_bin("strikes")?;
+    cmd.arg("--config-path")
+        .arg(config_file.path())
+        .arg("config")
+        .arg("init")
+        .write_stdin("n\n\nsqlite\n\n");
+    cmd.assert().success().stdout(predicate::str::contains(
+        "Checking health for sqlite client",
+    ));
+
+    let mut cmd = Command::cargo_bin("strikes")?;
+    cmd.arg("--config-path")
+        .arg(config_file.path())
+        .arg("config")
+        .arg("set")
+        .arg("decay.expire_after_days")
+        .arg("30");
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("strikes")?;
+    cmd.arg("--config-path")
+        .arg(config_file.path())
+        .arg("config")
+        .arg("validate");
+    cmd.assert()
+        .success()
+        .stdout("The configuration is valid\n");
+
+    assert_eq!(
+        std::fs::read_to_string(config_file.path())?,
+        format!(
+            "local:\n  db_path: {}\n  backend: sqlite\ndecay:\n  expire_after_days: 30\n",
+            dir.child("db.json").path().display()
+        )
+    );
+
+    let mut cmd = Command::cargo_bin("strikes")?;
+    cmd.arg("--config-path")
+        .arg(config_file.path())
+        .arg("config")
+        .arg("init");
+    cmd.assert()
+        .code(78)
+        .stderr(predicate::str::contains("use --force to overwrite it"));
+
+    Ok(())
+}
+
+#[test]
+fn it_should_report_where_the_configuration_is_invalid() -> Result<(), Box<dyn std::error::Error>> {
+    let config_file = assert_fs::NamedTempFile::new("./tests/fixtures/configuration.yaml")?;
+    config_file.write_str("default_board: builds\nremote:\n  api_key: abc\n")?;
+
+    let mut cmd = Command::cargo_bin("strikes")?;
+    cmd.arg("--config-path")
+        .arg(config_file.path())
+        .arg("config")
+        .arg("validate");
+    cmd.assert().code(78).stderr(predicate::str::contains(
+        "remote: missing field `base_url` at line 3 column 3",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn it_should_apply_thresholds_after_a_strike() -> Result<(), Box<dyn std::error::Error>> {
+    let db_file = assert_fs::NamedTempFile::new("./tests/fixtures/db.json")?;
+    let out_file = assert_fs::NamedTempFile::new("threshold.txt")?;
+    let config_file = assert_fs::NamedTempFile::new("./tests/fixtures/configuration.yaml")?;
+    config_file.write_str(
+        format!(
+            "local:\n  db_path: {}\nthresholds:\n  - strikes: 2\n    warning: Two strikes!\n    command: echo \"$STRIKES_NAME $STRIKES_COUNT\" > {}\n",
+            db_file.path().to_str().unwrap(),
+            out_file.path().to_str().unwrap()
+        )
+        .as_str(),
+    )?;
+
+    db_file.write_str("{\"guenther\": 1}")?;
+
+    let mut cmd = Command::cargo_bin("strikes")?;
+    cmd.arg("--config-path")
+        .arg(config_file.path())
+        .arg("strike")
+        .arg("guenther");
+    cmd.arg("--output").arg("json");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Two strikes!").not())
+        .stderr(predicate::str::contains("Two strikes!"));
+
+    assert_eq!(std::fs::read_to_string(out_file.path())?, "guenther 2\n");
+
+    Ok(())
+}
+
 #[test]
 fn it_should_list_strikes_in_descending_order() -> Result<(), Box<dyn std::error::Error>> {
     let db_file = assert_fs::NamedTempFile::new("./tests/fixtures/db.json")?;
@@ -69,6 +358,301 @@ fn it_should_list_strikes_in_descending_order() -> Result<(), Box<dyn std::error
     Ok(())
 }
 
+#[test]
+fn it_should_list_strikes_as_csv() -> Result<(), Box<dyn std::error::Error>> {
+    let db_file = assert_fs::NamedTempFile::new("./tests/fixtures/db.json")?;
+    let config_file = assert_fs::NamedTempFile::new("./tests/fixtures/configuration.yaml")?;
+    config_file.write_str(
+        format!(
+            "{{\"local\": {{\"db_path\": \"{}\"}}}}",
+            db_file.path().to_str().unwrap()
+        )
+        .as_str(),
+    )?;
+
+    db_file.write_str("{\"guenther\": 1, \"heinz\": 2}")?;
+
+    let mut cmd = Command::cargo_bin("strikes")?;
+    cmd.arg("--config-path")
+        .arg(config_file.path())
+        .arg("ls")
+        .arg("--output")
+        .arg("csv");
+
+    cmd.assert()
+        .success()
+        .stdout("Tarnished,Strikes\nheinz,2\nguenther,1\n");
+
+    let mut cmd = Command::cargo_bin("strikes")?;
+    cmd.arg("--config-path")
+        .arg(config_file.path())
+        .arg("strike")
+        .arg("guenther")
+        .arg("-o")
+        .arg("json");
+
+    cmd.assert()
+        .success()
+        .stdout("{\n  \"name\": \"guenther\",\n  \"strikes\": 2\n}\n");
+
+    Ok(())
+}
+
+#[test]
+fn it_should_list_reasons_when_verbose() -> Result<(), Box<dyn std::error::Error>> {
+    let db_file = assert_fs::NamedTempFile::new("./tests/fixtures/db.json")?;
+    let config_file = assert_fs::NamedTempFile::new("./tests/fixtures/configuration.yaml")?;
+    config_file.write_str(
+        format!(
+            "{{\"local\": {{\"db_path\": \"{}\"}}}}",
+            db_file.path().to_str().unwrap()
+        )
+        .as_str(),
+    )?;
+
+    let mut cmd = Command::cargo_bin("strikes")?;
+    cmd.arg("--config-path")
+        .arg(config_file.path())
+        .arg("strike")
+        .arg("guenther")
+        .arg("--reason")
+        .arg("broke the build");
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("strikes")?;
+    cmd.arg("--config-path")
+        .arg(config_file.path())
+        .arg("ls")
+        .arg("--verbose");
+    let expected_output = "+-----------+---------+-----------------+\n\
+                           | Tarnished | Strikes | Reasons         |\n\
+                           +=======================================+\n\
+                           | guenther  | 1       | broke the build |\n\
+                           +-----------+---------+-----------------+\n";
+
+    cmd.assert().success().stdout(expected_output);
+
+    Ok(())
+}
+
+#[test]
+fn it_should_show_the_history_of_a_tarnished() -> Result<(), Box<dyn std::error::Error>> {
+    let db_file = assert_fs::NamedTempFile::new("./tests/fixtures/db.json")?;
+    let config_file = assert_fs::NamedTempFile::new("./tests/fixtures/configuration.yaml")?;
+    config_file.write_str(
+        format!(
+            "{{\"local\": {{\"db_path\": \"{}\"}}}}",
+            db_file.path().to_str().unwrap()
+        )
+        .as_str(),
+    )?;
+
+    db_file.write_str(
+        "{\"guenther\": [{\"reason\": \"broke the build\", \"timestamp\": \"2024-09-01T08:30:00Z\"}]}",
+    )?;
+
+    let mut cmd = Command::cargo_bin("strikes")?;
+    cmd.arg("--config-path")
+        .arg(config_file.path())
+        .arg("history")
+        .arg("guenther");
+    let expected_output = "+---------------------+-----------------+\n\
+                           | Date                | Reason          |\n\
+                           +=======================================+\n\
+                           | 2024-09-01 08:30:00 | broke the build |\n\
+                           +---------------------+-----------------+\n";
+
+    cmd.assert().success().stdout(expected_output);
+
+    Ok(())
+}
+
+#[test]
+fn it_should_hide_expired_strikes_unless_asked() -> Result<(), Box<dyn std::error::Error>> {
+    let db_file = assert_fs::NamedTempFile::new("./tests/fixtures/db.json")?;
+    let config_file = assert_fs::NamedTempFile::new("./tests/fixtures/configuration.yaml")?;
+    config_file.write_str(
+        format!(
+            "{{\"local\": {{\"db_path\": \"{}\"}}, \"decay\": {{\"expire_after_days\": 30}}}}",
+            db_file.path().to_str().unwrap()
+        )
+        .as_str(),
+    )?;
+
+    db_file.write_str(
+        "{\"guenther\": [{\"reason\": \"broke the build\", \"timestamp\": \"2024-09-01T08:30:00Z\"}]}",
+    )?;
+
+    let mut cmd = Command::cargo_bin("strikes")?;
+    cmd.arg("--config-path").arg(config_file.path()).arg("ls");
+    cmd.assert()
+        .success()
+        .stdout("No one has been tarnished yet!\n");
+
+    let mut cmd = Command::cargo_bin("strikes")?;
+    cmd.arg("--config-path")
+        .arg(config_file.path())
+        .arg("ls")
+        .arg("--include-expired");
+    let expected_output = "+-----------+---------+\n\
+                           | Tarnished | Strikes |\n\
+                           +=====================+\n\
+                           | guenther  | 1       |\n\
+                           +-----------+---------+\n";
+
+    cmd.assert().success().stdout(expected_output);
+
+    Ok(())
+}
+
+#[test]
+fn it_should_list_the_strikes_of_all_boards() -> Result<(), Box<dyn std::error::Error>> {
+    let db_file = assert_fs::NamedTempFile::new("./tests/fixtures/db.json")?;
+    let config_file = assert_fs::NamedTempFile::new("./tests/fixtures/configuration.yaml")?;
+    config_file.write_str(
+        format!(
+            "{{\"local\": {{\"db_path\": \"{}\"}}}}",
+            db_file.path().to_str().unwrap()
+        )
+        .as_str(),
+    )?;
+
+    let mut cmd = Command::cargo_bin("strikes")?;
+    cmd.arg("--config-path")
+        .arg(config_file.path())
+        .arg("--board")
+        .arg("coffee")
+        .arg("strike")
+        .arg("guenther");
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("strikes")?;
+    cmd.arg("--config-path")
+        .arg(config_file.path())
+        .arg("strike")
+        .arg("heinz")
+        .arg("--board")
+        .arg("builds");
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("strikes")?;
+    cmd.arg("--config-path").arg(config_file.path()).arg("ls");
+    cmd.assert()
+        .success()
+        .stdout("No one has been tarnished yet!\n");
+
+    let mut cmd = Command::cargo_bin("strikes")?;
+    cmd.arg("--config-path")
+        .arg(config_file.path())
+        .arg("ls")
+        .arg("--all-boards");
+    let expected_output = "+--------+-----------+---------+\n\
+                           | Board  | Tarnished | Strikes |\n\
+                           +==============================+\n\
+                           | builds | heinz     | 1       |\n\
+                           |--------+-----------+---------|\n\
+                           | coffee | guenther  | 1       |\n\
+                           +--------+-----------+---------+\n";
+
+    cmd.assert().success().stdout(expected_output);
+
+    Ok(())
+}
+
+#[test]
+fn it_should_move_a_board_with_export_and_import() -> Result<(), Box<dyn std::error::Error>> {
+    let source_db = assert_fs::NamedTempFile::new("./tests/fixtures/db.json")?;
+    let target_db = assert_fs::NamedTempFile::new("./tests/fixtures/db.json")?;
+    let export_file = assert_fs::NamedTempFile::new("export.json")?;
+    let source_config = assert_fs::NamedTempFile::new("./tests/fixtures/configuration.yaml")?;
+    let target_config = assert_fs::NamedTempFile::new("./tests/fixtures/configuration.yaml")?;
+    source_config.write_str(
+        format!(
+            "{{\"local\": {{\"db_path\": \"{}\"}}}}",
+            source_db.path().to_str().unwrap()
+        )
+        .as_str(),
+    )?;
+    target_config.write_str(
+        format!(
+            "{{\"local\": {{\"db_path\": \"{}\"}}}}",
+            target_db.path().to_str().unwrap()
+        )
+        .as_str(),
+    )?;
+
+    source_db.write_str("{\"guenther\": 1, \"heinz\": 2}")?;
+
+    let mut cmd = Command::cargo_bin("strikes")?;
+    cmd.arg("--config-path")
+        .arg(source_config.path())
+        .arg("export")
+        .arg("--file")
+        .arg(export_file.path());
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("strikes")?;
+    cmd.arg("--config-path")
+        .arg(target_config.path())
+        .arg("import")
+        .arg(export_file.path())
+        .arg("--mode")
+        .arg("replace");
+    cmd.assert()
+        .success()
+        .stdout("Imported 3 strikes into default\n");
+
+    let mut cmd = Command::cargo_bin("strikes")?;
+    cmd.arg("--config-path").arg(target_config.path()).arg("ls");
+    let expected_output = "+-----------+---------+\n\
+                           | Tarnished | Strikes |\n\
+                           +=====================+\n\
+                           | heinz     | 2       |\n\
+                           |-----------+---------|\n\
+                           | guenther  | 1       |\n\
+                           +-----------+---------+\n";
+
+    cmd.assert().success().stdout(expected_output);
+
+    Ok(())
+}
+
+#[test]
+fn it_should_exit_with_a_distinct_code_on_failure() -> Result<(), Box<dyn std::error::Error>> {
+    let db_file = assert_fs::NamedTempFile::new("./tests/fixtures/db.json")?;
+    let export_file = assert_fs::NamedTempFile::new("export.json")?;
+    let config_file = assert_fs::NamedTempFile::new("./tests/fixtures/configuration.yaml")?;
+    config_file.write_str(
+        format!(
+            "{{\"local\": {{\"db_path\": \"{}\"}}}}",
+            db_file.path().to_str().unwrap()
+        )
+        .as_str(),
+    )?;
+
+    export_file.write_str("not an export")?;
+
+    let mut cmd = Command::cargo_bin("strikes")?;
+    cmd.arg("--config-path")
+        .arg(config_file.path())
+        .arg("import")
+        .arg(export_file.path());
+    cmd.assert()
+        .code(65)
+        .stderr(predicate::str::contains("Failed to read export"));
+
+    db_file.write_str("{\"version\": 99, \"boards\": {}}")?;
+
+    let mut cmd = Command::cargo_bin("strikes")?;
+    cmd.arg("--config-path")
+        .arg(config_file.path())
+        .arg("strike")
+        .arg("guenther");
+    cmd.assert().code(74);
+
+    Ok(())
+}
+
 #[test]
 fn it_should_clear_all_strikes() -> Result<(), Box<dyn std::error::Error>> {
     let db_file = assert_fs::NamedTempFile::new("./tests/fixtures/db.json")?;
@@ -101,7 +685,8 @@ fn it_should_clear_all_strikes() -> Result<(), Box<dyn std::error::Error>> {
     let mut cmd = Command::cargo_bin("strikes")?;
     cmd.arg("--config-path")
         .arg(config_file.path())
-        .arg("clear");
+        .arg("clear")
+        .arg("--all");
     cmd.assert()
         .success()
         .stdout("All strikes have been cleared!\n");
@@ -115,6 +700,54 @@ fn it_should_clear_all_strikes() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn it_should_clear_the_strikes_of_one_tarnished() -> Result<(), Box<dyn std::error::Error>> {
+    let db_file = assert_fs::NamedTempFile::new("./tests/fixtures/db.json")?;
+    let config_file = assert_fs::NamedTempFile::new("./tests/fixtures/configuration.yaml")?;
+    config_file.write_str(
+        format!(
+            "{{\"local\": {{\"db_path\": \"{}\"}}}}",
+            db_file.path().to_str().unwrap()
+        )
+        .as_str(),
+    )?;
+
+    db_file.write_str("{\"guenther\": 1, \"heinz\": 2}")?;
+
+    let mut cmd = Command::cargo_bin("strikes")?;
+    cmd.arg("--config-path")
+        .arg(config_file.path())
+        .arg("clear")
+        .arg("heinz");
+    cmd.assert()
+        .success()
+        .stdout("All strikes of heinz have been cleared!\n");
+
+    let mut cmd = Command::cargo_bin("strikes")?;
+    cmd.arg("--config-path").arg(config_file.path()).arg("ls");
+    let expected_output = "+-----------+---------+\n\
+                           | Tarnished | Strikes |\n\
+                           +=====================+\n\
+                           | guenther  | 1       |\n\
+                           +-----------+---------+\n";
+
+    cmd.assert().success().stdout(expected_output);
+
+    Ok(())
+}
+
+#[test]
+fn it_should_require_a_name_or_all_to_clear() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("strikes")?;
+
+    cmd.arg("clear");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "required arguments were not provided",
+    ));
+
+    Ok(())
+}
+
 #[test]
 fn it_should_reject_usernames_longer_than_20_characters() -> Result<(), Box<dyn std::error::Error>>
 {
@@ -150,3 +783,151 @@ fn it_should_show_help_if_no_subcommand_is_given() -> Result<(), Box<dyn std::er
 
     Ok(())
 }
+
+#[test]
+fn it_should_fail_with_a_usage_error_without_a_command() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("strikes")?;
+
+    cmd.arg("--board").arg("coffee");
+    cmd.assert()
+        .code(64)
+        .stderr(predicate::str::contains("No command was provided"));
+
+    Ok(())
+}
+
+#[test]
+fn it_should_print_completions_with_a_broken_configuration(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("strikes")?;
+
+    cmd.arg("--profile")
+        .arg("unknown")
+        .arg("completions")
+        .arg("bash");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("complete -o nospace"));
+
+    Ok(())
+}
+
+#[test]
+fn it_should_print_completions_and_man_pages() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("strikes")?;
+    cmd.arg("completions").arg("bash");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("complete -o nospace"));
+
+    let mut cmd = Command::cargo_bin("strikes")?;
+    cmd.arg("man");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(".TH strikes 1"));
+
+    Ok(())
+}
+
+#[test]
+fn it_should_complete_names_of_the_tarnished() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = assert_fs::TempDir::new()?;
+    let config_file = dir.child("configuration.yaml");
+    config_file.write_str(&format!(
+        "local:\n  db_path: {}\n",
+        dir.child("db.json").path().display()
+    ))?;
+
+    for name in ["guenther", "heinz"] {
+        let mut cmd = Command::cargo_bin("strikes")?;
+        cmd.arg("-c")
+            .arg(config_file.path())
+            .arg("strike")
+            .arg(name);
+        cmd.assert().success();
+    }
+
+    let mut cmd = Command::cargo_bin("strikes")?;
+    cmd.env("COMPLETE", "bash")
+        .env("_CLAP_COMPLETE_INDEX", "4")
+        .args(["--", "strikes", "-c"])
+        .arg(config_file.path())
+        .args(["unstrike", "g"]);
+    cmd.assert().success().stdout("guenther");
+
+    Ok(())
+}
+
+#[test]
+fn it_should_complete_names_without_replaying_the_outbox() -> Result<(), Box<dyn std::error::Error>>
+{
+    let dir = assert_fs::TempDir::new()?;
+    let config_file = dir.child("configuration.yaml");
+    config_file.write_str(&format!(
+        "remote:\n  api_key: abc\n  base_url: http://127.0.0.1:1\nlocal:\n  db_path: {}\n",
+        dir.child("db.json").path().display()
+    ))?;
+    let outbox = dir.child("outbox-http___127_0_0_1_1.json");
+    outbox.write_str(r#"[{"operation": "add_strike", "board": "default", "name": "guenther"}]"#)?;
+
+    let mut cmd = Command::cargo_bin("strikes")?;
+    cmd.env("COMPLETE", "bash")
+        .env("XDG_CACHE_HOME", dir.child("cache").path())
+        .env("_CLAP_COMPLETE_INDEX", "4")
+        .args(["--", "strikes", "-c"])
+        .arg(config_file.path())
+        .args(["unstrike", "g"]);
+    cmd.assert().success().stdout("").stderr("");
+
+    assert!(std::fs::read_to_string(outbox.path())?.contains("guenther"));
+    assert!(
+        std::fs::read_to_string(dir.child("cache/strikes/names.json").path())?
+            .contains(r#""names":[]"#)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn it_should_strike_a_tarnished_under_an_alias() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = assert_fs::TempDir::new()?;
+    let config_file = dir.child("configuration.yaml");
+    config_file.write_str(&format!(
+        "local:\n  db_path: {}\n",
+        dir.child("db.json").path().display()
+    ))?;
+
+    let mut cmd = Command::cargo_bin("strikes")?;
+    cmd.arg("-c")
+        .arg(config_file.path())
+        .args(["alias", "add", "guenther", "gue"]);
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("strikes")?;
+    cmd.arg("-c")
+        .arg(config_file.path())
+        .args(["strike", "gue"]);
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("strikes")?;
+    cmd.arg("-c").arg(config_file.path()).args(["alias", "ls"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("| gue   | guenther  |"));
+
+    let mut cmd = Command::cargo_bin("strikes")?;
+    cmd.arg("-c")
+        .arg(config_file.path())
+        .args(["ls", "-o", "csv"]);
+    cmd.assert()
+        .success()
+        .stdout("Tarnished,Strikes\nguenther,1\n");
+
+    let mut cmd = Command::cargo_bin("strikes")?;
+    cmd.arg("-c")
+        .arg(config_file.path())
+        .args(["alias", "add", "gue", "guenther"]);
+    cmd.assert().failure().code(65);
+
+    Ok(())
+}
@@ -1,13 +1,31 @@
 locals {
-  health_lambda_name              = "health"
-  put_strike_lambda_name          = "put-strikes"
-  get_strikes_lambda_name         = "get-strikes"
-  delete_strikes_lambda_name      = "delete-strikes"
-  sse_strikes_lambda_name         = "sse-strikes"
-  website_lambda_name             = "website"
-  connect_lambda_name             = "connect"
-  disconnect_lambda_name          = "disconnect"
-  send_strikes_update_lambda_name = "send_strikes_update"
+  health_lambda_name               = "health"
+  put_strike_lambda_name           = "put-strikes"
+  get_strikes_lambda_name          = "get-strikes"
+  delete_strikes_lambda_name       = "delete-strikes"
+  delete_latest_strike_lambda_name = "delete-latest-strike"
+  delete_user_strikes_lambda_name  = "delete-user-strikes"
+  get_strike_history_lambda_name   = "get-strike-history"
+  get_boards_lambda_name           = "get-boards"
+  import_strikes_lambda_name       = "import-strikes"
+  aliases_lambda_name              = "aliases"
+  sse_strikes_lambda_name          = "sse-strikes"
+  website_lambda_name              = "website"
+  connect_lambda_name              = "connect"
+  disconnect_lambda_name           = "disconnect"
+  send_strikes_update_lambda_name  = "send_strikes_update"
+}
+
+variable "strike_expiry_days" {
+  description = "Number of days after which a strike expires, 0 keeps strikes forever"
+  type        = number
+  default     = 0
+}
+
+variable "strike_thresholds" {
+  description = "Comma separated strike counts at which put-strikes reports a reached threshold"
+  type        = string
+  default     = "3"
 }
 
 module "website" {
@@ -65,7 +83,7 @@ resource "aws_iam_role_policy_attachment" "connect_lambda_execution_role_policy_
 
 data "archive_file" "connect_lambda_archive" {
   type        = "zip"
-  source_file = "${path.module}/target/lambda/connect/bootstrap"
+  source_file = "${path.module}/../../target/lambda/connect/bootstrap"
   output_path = "${path.module}/target/archive/connect.zip"
 }
 
@@ -110,7 +128,7 @@ resource "aws_lambda_permission" "apigw_ws_invoke_disconnect_lambda" {
 }
 
 resource "aws_lambda_event_source_mapping" "example" {
-  event_source_arn  = aws_dynamodb_table.strikes-table.stream_arn
+  event_source_arn  = aws_dynamodb_table.board-strikes-table.stream_arn
   function_name     = aws_lambda_function.send_strikes_update.arn
   starting_position = "LATEST"
 }
@@ -131,7 +149,7 @@ resource "aws_iam_role_policy_attachment" "disconnect_lambda_execution_role_poli
 
 data "archive_file" "disconnect_lambda_archive" {
   type        = "zip"
-  source_file = "${path.module}/target/lambda/disconnect/bootstrap"
+  source_file = "${path.module}/../../target/lambda/disconnect/bootstrap"
   output_path = "${path.module}/target/archive/disconnect.zip"
 }
 
@@ -221,6 +239,7 @@ data "aws_iam_policy_document" "strikes_dynamo_read_only" {
 
     actions = [
       "dynamodb:GetItem",
+      "dynamodb:Query",
       "dynamodb:Scan",
     ]
 
@@ -265,7 +284,7 @@ resource "aws_iam_role_policy_attachment" "send_strikes_update_lambda_execution_
 
 data "archive_file" "send_strikes_update_lambda_archive" {
   type        = "zip"
-  source_file = "${path.module}/target/lambda/strikes_update/bootstrap"
+  source_file = "${path.module}/../../target/lambda/strikes_update/bootstrap"
   output_path = "${path.module}/target/archive/strikes_update.zip"
 }
 
@@ -286,148 +305,792 @@ resource "aws_lambda_function" "send_strikes_update" {
 
   environment {
     variables = {
-      WEBSOCKET_API_ID = aws_apigatewayv2_api.ws_strikes.id
+      WEBSOCKET_API_ID   = aws_apigatewayv2_api.ws_strikes.id
+      STRIKE_EXPIRY_DAYS = var.strike_expiry_days
+    }
+  }
+}
+
+# -----------------------------------------------------------------------------
+#
+# REST API
+#
+# -----------------------------------------------------------------------------
+resource "aws_api_gateway_rest_api" "strikes" {
+  name = "strikes"
+}
+
+resource "aws_api_gateway_resource" "strikes" {
+  parent_id   = aws_api_gateway_rest_api.strikes.root_resource_id
+  path_part   = "strikes"
+  rest_api_id = aws_api_gateway_rest_api.strikes.id
+}
+
+# -----------------------------------------------------------------------------
+# GET STRIKES
+# -----------------------------------------------------------------------------
+resource "aws_api_gateway_method" "get_strikes" {
+  authorization    = "NONE"
+  http_method      = "GET"
+  resource_id      = aws_api_gateway_resource.strikes.id
+  rest_api_id      = aws_api_gateway_rest_api.strikes.id
+  api_key_required = false
+}
+
+resource "aws_api_gateway_integration" "get_strikes" {
+  http_method             = aws_api_gateway_method.get_strikes.http_method
+  resource_id             = aws_api_gateway_resource.strikes.id
+  rest_api_id             = aws_api_gateway_rest_api.strikes.id
+  type                    = "AWS_PROXY"
+  integration_http_method = "POST"
+  uri                     = aws_lambda_function.get_strikes.invoke_arn
+}
+
+resource "aws_lambda_permission" "apigw_invoke_get_strikes_lambda" {
+  statement_id  = "AllowAPIGatewayInvoke"
+  action        = "lambda:InvokeFunction"
+  function_name = aws_lambda_function.get_strikes.function_name
+  principal     = "apigateway.amazonaws.com"
+
+  source_arn = "${aws_api_gateway_rest_api.strikes.execution_arn}/*/*"
+}
+
+resource "aws_iam_role" "get_strikes_lambda_role" {
+  name               = "${local.get_strikes_lambda_name}-role"
+  assume_role_policy = data.aws_iam_policy_document.strikes_lambda_assume_role.json
+  inline_policy {
+    name   = "dynamo_write"
+    policy = data.aws_iam_policy_document.dynamo_read_only.json
+  }
+}
+
+resource "aws_iam_role_policy_attachment" "get_strikes_basic_execution_role_policy_attachment" {
+  role       = aws_iam_role.get_strikes_lambda_role.name
+  policy_arn = "arn:aws:iam::aws:policy/service-role/AWSLambdaBasicExecutionRole"
+}
+
+data "archive_file" "get_strikes_lambda_archive" {
+  type        = "zip"
+  source_file = "${path.module}/../../target/lambda/get_strikes/bootstrap"
+  output_path = "${path.module}/target/archive/get_strikes.zip"
+}
+
+resource "aws_lambda_function" "get_strikes" {
+  filename      = data.archive_file.get_strikes_lambda_archive.output_path
+  function_name = local.get_strikes_lambda_name
+  role          = aws_iam_role.get_strikes_lambda_role.arn
+
+  handler = "bootstrap"
+
+  source_code_hash = data.archive_file.get_strikes_lambda_archive.output_base64sha256
+
+  runtime = "provided.al2023"
+
+  architectures = ["x86_64"]
+
+  memory_size = 1024
+
+  environment {
+    variables = {
+      STRIKE_EXPIRY_DAYS = var.strike_expiry_days
+    }
+  }
+}
+
+# -----------------------------------------------------------------------------
+# DELETE STRIKES
+# -----------------------------------------------------------------------------
+resource "aws_api_gateway_method" "delete_strikes" {
+  authorization    = "NONE"
+  http_method      = "DELETE"
+  resource_id      = aws_api_gateway_resource.strikes.id
+  rest_api_id      = aws_api_gateway_rest_api.strikes.id
+  api_key_required = true
+}
+
+resource "aws_api_gateway_integration" "delete_strikes" {
+  http_method             = aws_api_gateway_method.delete_strikes.http_method
+  resource_id             = aws_api_gateway_resource.strikes.id
+  rest_api_id             = aws_api_gateway_rest_api.strikes.id
+  type                    = "AWS_PROXY"
+  integration_http_method = "POST"
+  uri                     = aws_lambda_function.delete_strikes.invoke_arn
+}
+
+resource "aws_lambda_permission" "apigw_invoke_delete_strikes_lambda" {
+  statement_id  = "AllowAPIGatewayInvoke"
+  action        = "lambda:InvokeFunction"
+  function_name = aws_lambda_function.delete_strikes.function_name
+  principal     = "apigateway.amazonaws.com"
+
+  source_arn = "${aws_api_gateway_rest_api.strikes.execution_arn}/*/*"
+}
+
+resource "aws_iam_role" "delete_strikes_lambda_role" {
+  name               = "${local.delete_strikes_lambda_name}-role"
+  assume_role_policy = data.aws_iam_policy_document.strikes_lambda_assume_role.json
+  inline_policy {
+    name   = "dynamo_delete"
+    policy = data.aws_iam_policy_document.dynamo_delete.json
+  }
+}
+
+resource "aws_iam_role_policy_attachment" "delete_strikes_basic_execution_role_policy_attachment" {
+  role       = aws_iam_role.delete_strikes_lambda_role.name
+  policy_arn = "arn:aws:iam::aws:policy/service-role/AWSLambdaBasicExecutionRole"
+}
+
+data "archive_file" "delete_strikes_lambda_archive" {
+  type        = "zip"
+  source_file = "${path.module}/../../target/lambda/delete_strikes/bootstrap"
+  output_path = "${path.module}/target/archive/delete_strikes.zip"
+}
+
+resource "aws_lambda_function" "delete_strikes" {
+  filename      = data.archive_file.delete_strikes_lambda_archive.output_path
+  function_name = local.delete_strikes_lambda_name
+  role          = aws_iam_role.delete_strikes_lambda_role.arn
+
+  handler = "bootstrap"
+
+  source_code_hash = data.archive_file.delete_strikes_lambda_archive.output_base64sha256
+
+  runtime = "provided.al2023"
+
+  architectures = ["x86_64"]
+
+  memory_size = 1024
+}
+
+# -----------------------------------------------------------------------------
+# PUT STRIKE
+# -----------------------------------------------------------------------------
+resource "aws_api_gateway_resource" "put_strike" {
+  parent_id   = aws_api_gateway_resource.strikes.id
+  path_part   = "{user}"
+  rest_api_id = aws_api_gateway_rest_api.strikes.id
+}
+
+resource "aws_api_gateway_method" "put_strike" {
+  authorization    = "NONE"
+  http_method      = "PUT"
+  resource_id      = aws_api_gateway_resource.put_strike.id
+  rest_api_id      = aws_api_gateway_rest_api.strikes.id
+  api_key_required = true
+}
+
+resource "aws_api_gateway_integration" "put_strike" {
+  http_method             = aws_api_gateway_method.put_strike.http_method
+  resource_id             = aws_api_gateway_resource.put_strike.id
+  rest_api_id             = aws_api_gateway_rest_api.strikes.id
+  type                    = "AWS_PROXY"
+  integration_http_method = "POST"
+  uri                     = aws_lambda_function.put_strike.invoke_arn
+}
+
+resource "aws_lambda_permission" "apigw_invoke_put_strike_lambda" {
+  statement_id  = "AllowAPIGatewayInvoke"
+  action        = "lambda:InvokeFunction"
+  function_name = aws_lambda_function.put_strike.function_name
+  principal     = "apigateway.amazonaws.com"
+
+  source_arn = "${aws_api_gateway_rest_api.strikes.execution_arn}/*/*"
+}
+
+resource "aws_iam_role" "put_strike_lambda_role" {
+  name               = "${local.put_strike_lambda_name}-role"
+  assume_role_policy = data.aws_iam_policy_document.strikes_lambda_assume_role.json
+  inline_policy {
+    name   = "dynamo_write"
+    policy = data.aws_iam_policy_document.dynamo_write.json
+  }
+}
+
+resource "aws_iam_role_policy_attachment" "strikes_basic_execution_role_policy_attachment" {
+  role       = aws_iam_role.put_strike_lambda_role.name
+  policy_arn = "arn:aws:iam::aws:policy/service-role/AWSLambdaBasicExecutionRole"
+}
+
+data "archive_file" "put_strike_lambda_archive" {
+  type        = "zip"
+  source_file = "${path.module}/../../target/lambda/put_strike/bootstrap"
+  output_path = "${path.module}/target/archive/put_strike.zip"
+}
+
+resource "aws_lambda_function" "put_strike" {
+  filename      = data.archive_file.put_strike_lambda_archive.output_path
+  function_name = local.put_strike_lambda_name
+  role          = aws_iam_role.put_strike_lambda_role.arn
+
+  handler = "bootstrap"
+
+  source_code_hash = data.archive_file.put_strike_lambda_archive.output_base64sha256
+
+  runtime = "provided.al2023"
+
+  architectures = ["x86_64"]
+
+  memory_size = 1024
+
+  environment {
+    variables = {
+      STRIKE_EXPIRY_DAYS = var.strike_expiry_days
+      STRIKE_THRESHOLDS  = var.strike_thresholds
+    }
+  }
+}
+
+# -----------------------------------------------------------------------------
+# DELETE USER STRIKES
+# -----------------------------------------------------------------------------
+resource "aws_api_gateway_method" "delete_user_strikes" {
+  authorization    = "NONE"
+  http_method      = "DELETE"
+  resource_id      = aws_api_gateway_resource.put_strike.id
+  rest_api_id      = aws_api_gateway_rest_api.strikes.id
+  api_key_required = true
+}
+
+resource "aws_api_gateway_integration" "delete_user_strikes" {
+  http_method             = aws_api_gateway_method.delete_user_strikes.http_method
+  resource_id             = aws_api_gateway_resource.put_strike.id
+  rest_api_id             = aws_api_gateway_rest_api.strikes.id
+  type                    = "AWS_PROXY"
+  integration_http_method = "POST"
+  uri                     = aws_lambda_function.delete_user_strikes.invoke_arn
+}
+
+resource "aws_lambda_permission" "apigw_invoke_delete_user_strikes_lambda" {
+  statement_id  = "AllowAPIGatewayInvoke"
+  action        = "lambda:InvokeFunction"
+  function_name = aws_lambda_function.delete_user_strikes.function_name
+  principal     = "apigateway.amazonaws.com"
+
+  source_arn = "${aws_api_gateway_rest_api.strikes.execution_arn}/*/*"
+}
+
+resource "aws_iam_role" "delete_user_strikes_lambda_role" {
+  name               = "${local.delete_user_strikes_lambda_name}-role"
+  assume_role_policy = data.aws_iam_policy_document.strikes_lambda_assume_role.json
+  inline_policy {
+    name   = "dynamo_delete"
+    policy = data.aws_iam_policy_document.dynamo_delete.json
+  }
+}
+
+resource "aws_iam_role_policy_attachment" "delete_user_strikes_basic_execution_role_policy_attachment" {
+  role       = aws_iam_role.delete_user_strikes_lambda_role.name
+  policy_arn = "arn:aws:iam::aws:policy/service-role/AWSLambdaBasicExecutionRole"
+}
+
+data "archive_file" "delete_user_strikes_lambda_archive" {
+  type        = "zip"
+  source_file = "${path.module}/../../target/lambda/delete_user_strikes/bootstrap"
+  output_path = "${path.module}/target/archive/delete_user_strikes.zip"
+}
+
+resource "aws_lambda_function" "delete_user_strikes" {
+  filename      = data.archive_file.delete_user_strikes_lambda_archive.output_path
+  function_name = local.delete_user_strikes_lambda_name
+  role          = aws_iam_role.delete_user_strikes_lambda_role.arn
+
+  handler = "bootstrap"
+
+  source_code_hash = data.archive_file.delete_user_strikes_lambda_archive.output_base64sha256
+
+  runtime = "provided.al2023"
+
+  architectures = ["x86_64"]
+
+  memory_size = 1024
+}
+
+# -----------------------------------------------------------------------------
+# DELETE LATEST STRIKE
+# -----------------------------------------------------------------------------
+resource "aws_api_gateway_resource" "delete_latest_strike" {
+  parent_id   = aws_api_gateway_resource.put_strike.id
+  path_part   = "latest"
+  rest_api_id = aws_api_gateway_rest_api.strikes.id
+}
+
+resource "aws_api_gateway_method" "delete_latest_strike" {
+  authorization    = "NONE"
+  http_method      = "DELETE"
+  resource_id      = aws_api_gateway_resource.delete_latest_strike.id
+  rest_api_id      = aws_api_gateway_rest_api.strikes.id
+  api_key_required = true
+}
+
+resource "aws_api_gateway_integration" "delete_latest_strike" {
+  http_method             = aws_api_gateway_method.delete_latest_strike.http_method
+  resource_id             = aws_api_gateway_resource.delete_latest_strike.id
+  rest_api_id             = aws_api_gateway_rest_api.strikes.id
+  type                    = "AWS_PROXY"
+  integration_http_method = "POST"
+  uri                     = aws_lambda_function.delete_latest_strike.invoke_arn
+}
+
+resource "aws_lambda_permission" "apigw_invoke_delete_latest_strike_lambda" {
+  statement_id  = "AllowAPIGatewayInvoke"
+  action        = "lambda:InvokeFunction"
+  function_name = aws_lambda_function.delete_latest_strike.function_name
+  principal     = "apigateway.amazonaws.com"
+
+  source_arn = "${aws_api_gateway_rest_api.strikes.execution_arn}/*/*"
+}
+
+resource "aws_iam_role" "delete_latest_strike_lambda_role" {
+  name               = "${local.delete_latest_strike_lambda_name}-role"
+  assume_role_policy = data.aws_iam_policy_document.strikes_lambda_assume_role.json
+  inline_policy {
+    name   = "dynamo_decrement"
+    policy = data.aws_iam_policy_document.dynamo_decrement.json
+  }
+}
+
+resource "aws_iam_role_policy_attachment" "delete_latest_strike_basic_execution_role_policy_attachment" {
+  role       = aws_iam_role.delete_latest_strike_lambda_role.name
+  policy_arn = "arn:aws:iam::aws:policy/service-role/AWSLambdaBasicExecutionRole"
+}
+
+data "archive_file" "delete_latest_strike_lambda_archive" {
+  type        = "zip"
+  source_file = "${path.module}/../../target/lambda/delete_latest_strike/bootstrap"
+  output_path = "${path.module}/target/archive/delete_latest_strike.zip"
+}
+
+resource "aws_lambda_function" "delete_latest_strike" {
+  filename      = data.archive_file.delete_latest_strike_lambda_archive.output_path
+  function_name = local.delete_latest_strike_lambda_name
+  role          = aws_iam_role.delete_latest_strike_lambda_role.arn
+
+  handler = "bootstrap"
+
+  source_code_hash = data.archive_file.delete_latest_strike_lambda_archive.output_base64sha256
+
+  runtime = "provided.al2023"
+
+  architectures = ["x86_64"]
+
+  memory_size = 1024
+
+  environment {
+    variables = {
+      STRIKE_EXPIRY_DAYS = var.strike_expiry_days
     }
   }
 }
 
-# -----------------------------------------------------------------------------
-#
-# REST API
-#
-# -----------------------------------------------------------------------------
-resource "aws_api_gateway_rest_api" "strikes" {
-  name = "strikes"
+# -----------------------------------------------------------------------------
+# GET STRIKE HISTORY
+# -----------------------------------------------------------------------------
+resource "aws_api_gateway_resource" "get_strike_history" {
+  parent_id   = aws_api_gateway_resource.put_strike.id
+  path_part   = "history"
+  rest_api_id = aws_api_gateway_rest_api.strikes.id
+}
+
+resource "aws_api_gateway_method" "get_strike_history" {
+  authorization    = "NONE"
+  http_method      = "GET"
+  resource_id      = aws_api_gateway_resource.get_strike_history.id
+  rest_api_id      = aws_api_gateway_rest_api.strikes.id
+  api_key_required = false
+}
+
+resource "aws_api_gateway_integration" "get_strike_history" {
+  http_method             = aws_api_gateway_method.get_strike_history.http_method
+  resource_id             = aws_api_gateway_resource.get_strike_history.id
+  rest_api_id             = aws_api_gateway_rest_api.strikes.id
+  type                    = "AWS_PROXY"
+  integration_http_method = "POST"
+  uri                     = aws_lambda_function.get_strike_history.invoke_arn
+}
+
+resource "aws_lambda_permission" "apigw_invoke_get_strike_history_lambda" {
+  statement_id  = "AllowAPIGatewayInvoke"
+  action        = "lambda:InvokeFunction"
+  function_name = aws_lambda_function.get_strike_history.function_name
+  principal     = "apigateway.amazonaws.com"
+
+  source_arn = "${aws_api_gateway_rest_api.strikes.execution_arn}/*/*"
+}
+
+resource "aws_iam_role" "get_strike_history_lambda_role" {
+  name               = "${local.get_strike_history_lambda_name}-role"
+  assume_role_policy = data.aws_iam_policy_document.strikes_lambda_assume_role.json
+  inline_policy {
+    name   = "dynamo_events_read"
+    policy = data.aws_iam_policy_document.dynamo_events_read_only.json
+  }
+}
+
+resource "aws_iam_role_policy_attachment" "get_strike_history_basic_execution_role_policy_attachment" {
+  role       = aws_iam_role.get_strike_history_lambda_role.name
+  policy_arn = "arn:aws:iam::aws:policy/service-role/AWSLambdaBasicExecutionRole"
+}
+
+data "archive_file" "get_strike_history_lambda_archive" {
+  type        = "zip"
+  source_file = "${path.module}/../../target/lambda/get_strike_history/bootstrap"
+  output_path = "${path.module}/target/archive/get_strike_history.zip"
+}
+
+resource "aws_lambda_function" "get_strike_history" {
+  filename      = data.archive_file.get_strike_history_lambda_archive.output_path
+  function_name = local.get_strike_history_lambda_name
+  role          = aws_iam_role.get_strike_history_lambda_role.arn
+
+  handler = "bootstrap"
+
+  source_code_hash = data.archive_file.get_strike_history_lambda_archive.output_base64sha256
+
+  runtime = "provided.al2023"
+
+  architectures = ["x86_64"]
+
+  memory_size = 1024
+}
+
+# -----------------------------------------------------------------------------
+# BOARDS
+# -----------------------------------------------------------------------------
+resource "aws_api_gateway_resource" "boards" {
+  parent_id   = aws_api_gateway_rest_api.strikes.root_resource_id
+  path_part   = "boards"
+  rest_api_id = aws_api_gateway_rest_api.strikes.id
+}
+
+resource "aws_api_gateway_resource" "board" {
+  parent_id   = aws_api_gateway_resource.boards.id
+  path_part   = "{board}"
+  rest_api_id = aws_api_gateway_rest_api.strikes.id
+}
+
+resource "aws_api_gateway_resource" "board_strikes" {
+  parent_id   = aws_api_gateway_resource.board.id
+  path_part   = "strikes"
+  rest_api_id = aws_api_gateway_rest_api.strikes.id
+}
+
+resource "aws_api_gateway_resource" "board_strike" {
+  parent_id   = aws_api_gateway_resource.board_strikes.id
+  path_part   = "{user}"
+  rest_api_id = aws_api_gateway_rest_api.strikes.id
+}
+
+resource "aws_api_gateway_resource" "board_latest_strike" {
+  parent_id   = aws_api_gateway_resource.board_strike.id
+  path_part   = "latest"
+  rest_api_id = aws_api_gateway_rest_api.strikes.id
+}
+
+resource "aws_api_gateway_resource" "board_strike_history" {
+  parent_id   = aws_api_gateway_resource.board_strike.id
+  path_part   = "history"
+  rest_api_id = aws_api_gateway_rest_api.strikes.id
+}
+
+resource "aws_api_gateway_method" "get_board_strikes" {
+  authorization    = "NONE"
+  http_method      = "GET"
+  resource_id      = aws_api_gateway_resource.board_strikes.id
+  rest_api_id      = aws_api_gateway_rest_api.strikes.id
+  api_key_required = false
+}
+
+resource "aws_api_gateway_integration" "get_board_strikes" {
+  http_method             = aws_api_gateway_method.get_board_strikes.http_method
+  resource_id             = aws_api_gateway_resource.board_strikes.id
+  rest_api_id             = aws_api_gateway_rest_api.strikes.id
+  type                    = "AWS_PROXY"
+  integration_http_method = "POST"
+  uri                     = aws_lambda_function.get_strikes.invoke_arn
+}
+
+resource "aws_api_gateway_method" "delete_board_strikes" {
+  authorization    = "NONE"
+  http_method      = "DELETE"
+  resource_id      = aws_api_gateway_resource.board_strikes.id
+  rest_api_id      = aws_api_gateway_rest_api.strikes.id
+  api_key_required = true
+}
+
+resource "aws_api_gateway_integration" "delete_board_strikes" {
+  http_method             = aws_api_gateway_method.delete_board_strikes.http_method
+  resource_id             = aws_api_gateway_resource.board_strikes.id
+  rest_api_id             = aws_api_gateway_rest_api.strikes.id
+  type                    = "AWS_PROXY"
+  integration_http_method = "POST"
+  uri                     = aws_lambda_function.delete_strikes.invoke_arn
+}
+
+resource "aws_api_gateway_method" "put_board_strike" {
+  authorization    = "NONE"
+  http_method      = "PUT"
+  resource_id      = aws_api_gateway_resource.board_strike.id
+  rest_api_id      = aws_api_gateway_rest_api.strikes.id
+  api_key_required = true
+}
+
+resource "aws_api_gateway_integration" "put_board_strike" {
+  http_method             = aws_api_gateway_method.put_board_strike.http_method
+  resource_id             = aws_api_gateway_resource.board_strike.id
+  rest_api_id             = aws_api_gateway_rest_api.strikes.id
+  type                    = "AWS_PROXY"
+  integration_http_method = "POST"
+  uri                     = aws_lambda_function.put_strike.invoke_arn
+}
+
+resource "aws_api_gateway_method" "delete_board_user_strikes" {
+  authorization    = "NONE"
+  http_method      = "DELETE"
+  resource_id      = aws_api_gateway_resource.board_strike.id
+  rest_api_id      = aws_api_gateway_rest_api.strikes.id
+  api_key_required = true
 }
 
-resource "aws_api_gateway_resource" "strikes" {
-  parent_id   = aws_api_gateway_rest_api.strikes.root_resource_id
-  path_part   = "strikes"
-  rest_api_id = aws_api_gateway_rest_api.strikes.id
+resource "aws_api_gateway_integration" "delete_board_user_strikes" {
+  http_method             = aws_api_gateway_method.delete_board_user_strikes.http_method
+  resource_id             = aws_api_gateway_resource.board_strike.id
+  rest_api_id             = aws_api_gateway_rest_api.strikes.id
+  type                    = "AWS_PROXY"
+  integration_http_method = "POST"
+  uri                     = aws_lambda_function.delete_user_strikes.invoke_arn
+}
+
+resource "aws_api_gateway_method" "delete_board_latest_strike" {
+  authorization    = "NONE"
+  http_method      = "DELETE"
+  resource_id      = aws_api_gateway_resource.board_latest_strike.id
+  rest_api_id      = aws_api_gateway_rest_api.strikes.id
+  api_key_required = true
+}
+
+resource "aws_api_gateway_integration" "delete_board_latest_strike" {
+  http_method             = aws_api_gateway_method.delete_board_latest_strike.http_method
+  resource_id             = aws_api_gateway_resource.board_latest_strike.id
+  rest_api_id             = aws_api_gateway_rest_api.strikes.id
+  type                    = "AWS_PROXY"
+  integration_http_method = "POST"
+  uri                     = aws_lambda_function.delete_latest_strike.invoke_arn
+}
+
+resource "aws_api_gateway_method" "get_board_strike_history" {
+  authorization    = "NONE"
+  http_method      = "GET"
+  resource_id      = aws_api_gateway_resource.board_strike_history.id
+  rest_api_id      = aws_api_gateway_rest_api.strikes.id
+  api_key_required = false
+}
+
+resource "aws_api_gateway_integration" "get_board_strike_history" {
+  http_method             = aws_api_gateway_method.get_board_strike_history.http_method
+  resource_id             = aws_api_gateway_resource.board_strike_history.id
+  rest_api_id             = aws_api_gateway_rest_api.strikes.id
+  type                    = "AWS_PROXY"
+  integration_http_method = "POST"
+  uri                     = aws_lambda_function.get_strike_history.invoke_arn
 }
 
 # -----------------------------------------------------------------------------
-# GET STRIKES
+# GET BOARDS
 # -----------------------------------------------------------------------------
-resource "aws_api_gateway_method" "get_strikes" {
+resource "aws_api_gateway_method" "get_boards" {
   authorization    = "NONE"
   http_method      = "GET"
-  resource_id      = aws_api_gateway_resource.strikes.id
+  resource_id      = aws_api_gateway_resource.boards.id
   rest_api_id      = aws_api_gateway_rest_api.strikes.id
   api_key_required = false
 }
 
-resource "aws_api_gateway_integration" "get_strikes" {
-  http_method             = aws_api_gateway_method.get_strikes.http_method
-  resource_id             = aws_api_gateway_resource.strikes.id
+resource "aws_api_gateway_integration" "get_boards" {
+  http_method             = aws_api_gateway_method.get_boards.http_method
+  resource_id             = aws_api_gateway_resource.boards.id
   rest_api_id             = aws_api_gateway_rest_api.strikes.id
   type                    = "AWS_PROXY"
   integration_http_method = "POST"
-  uri                     = aws_lambda_function.get_strikes.invoke_arn
+  uri                     = aws_lambda_function.get_boards.invoke_arn
 }
 
-resource "aws_lambda_permission" "apigw_invoke_get_strikes_lambda" {
+resource "aws_lambda_permission" "apigw_invoke_get_boards_lambda" {
   statement_id  = "AllowAPIGatewayInvoke"
   action        = "lambda:InvokeFunction"
-  function_name = aws_lambda_function.get_strikes.function_name
+  function_name = aws_lambda_function.get_boards.function_name
   principal     = "apigateway.amazonaws.com"
 
   source_arn = "${aws_api_gateway_rest_api.strikes.execution_arn}/*/*"
 }
 
-resource "aws_iam_role" "get_strikes_lambda_role" {
-  name               = "${local.get_strikes_lambda_name}-role"
+resource "aws_iam_role" "get_boards_lambda_role" {
+  name               = "${local.get_boards_lambda_name}-role"
   assume_role_policy = data.aws_iam_policy_document.strikes_lambda_assume_role.json
   inline_policy {
-    name   = "dynamo_write"
+    name   = "dynamo_read"
     policy = data.aws_iam_policy_document.dynamo_read_only.json
   }
 }
 
-resource "aws_iam_role_policy_attachment" "get_strikes_basic_execution_role_policy_attachment" {
-  role       = aws_iam_role.get_strikes_lambda_role.name
+resource "aws_iam_role_policy_attachment" "get_boards_basic_execution_role_policy_attachment" {
+  role       = aws_iam_role.get_boards_lambda_role.name
   policy_arn = "arn:aws:iam::aws:policy/service-role/AWSLambdaBasicExecutionRole"
 }
 
-data "archive_file" "get_strikes_lambda_archive" {
+data "archive_file" "get_boards_lambda_archive" {
   type        = "zip"
-  source_file = "${path.module}/target/lambda/get_strikes/bootstrap"
-  output_path = "${path.module}/target/archive/get_strikes.zip"
+  source_file = "${path.module}/../../target/lambda/get_boards/bootstrap"
+  output_path = "${path.module}/target/archive/get_boards.zip"
 }
 
-resource "aws_lambda_function" "get_strikes" {
-  filename      = data.archive_file.get_strikes_lambda_archive.output_path
-  function_name = local.get_strikes_lambda_name
-  role          = aws_iam_role.get_strikes_lambda_role.arn
+resource "aws_lambda_function" "get_boards" {
+  filename      = data.archive_file.get_boards_lambda_archive.output_path
+  function_name = local.get_boards_lambda_name
+  role          = aws_iam_role.get_boards_lambda_role.arn
 
   handler = "bootstrap"
 
-  source_code_hash = data.archive_file.get_strikes_lambda_archive.output_base64sha256
+  source_code_hash = data.archive_file.get_boards_lambda_archive.output_base64sha256
 
   runtime = "provided.al2023"
 
   architectures = ["x86_64"]
 
   memory_size = 1024
+
+  environment {
+    variables = {
+      STRIKE_EXPIRY_DAYS = var.strike_expiry_days
+    }
+  }
 }
 
 # -----------------------------------------------------------------------------
-# DELETE STRIKES
+# ALIASES
 # -----------------------------------------------------------------------------
-resource "aws_api_gateway_method" "delete_strikes" {
+resource "aws_api_gateway_resource" "board_aliases" {
+  parent_id   = aws_api_gateway_resource.board.id
+  path_part   = "aliases"
+  rest_api_id = aws_api_gateway_rest_api.strikes.id
+}
+
+resource "aws_api_gateway_resource" "board_alias" {
+  parent_id   = aws_api_gateway_resource.board_aliases.id
+  path_part   = "{alias}"
+  rest_api_id = aws_api_gateway_rest_api.strikes.id
+}
+
+resource "aws_api_gateway_resource" "board_alias_merge" {
+  parent_id   = aws_api_gateway_resource.board_alias.id
+  path_part   = "merge"
+  rest_api_id = aws_api_gateway_rest_api.strikes.id
+}
+
+resource "aws_api_gateway_method" "get_board_aliases" {
+  authorization    = "NONE"
+  http_method      = "GET"
+  resource_id      = aws_api_gateway_resource.board_aliases.id
+  rest_api_id      = aws_api_gateway_rest_api.strikes.id
+  api_key_required = false
+}
+
+resource "aws_api_gateway_integration" "get_board_aliases" {
+  http_method             = aws_api_gateway_method.get_board_aliases.http_method
+  resource_id             = aws_api_gateway_resource.board_aliases.id
+  rest_api_id             = aws_api_gateway_rest_api.strikes.id
+  type                    = "AWS_PROXY"
+  integration_http_method = "POST"
+  uri                     = aws_lambda_function.aliases.invoke_arn
+}
+
+resource "aws_api_gateway_method" "put_board_alias" {
+  authorization    = "NONE"
+  http_method      = "PUT"
+  resource_id      = aws_api_gateway_resource.board_alias.id
+  rest_api_id      = aws_api_gateway_rest_api.strikes.id
+  api_key_required = true
+}
+
+resource "aws_api_gateway_integration" "put_board_alias" {
+  http_method             = aws_api_gateway_method.put_board_alias.http_method
+  resource_id             = aws_api_gateway_resource.board_alias.id
+  rest_api_id             = aws_api_gateway_rest_api.strikes.id
+  type                    = "AWS_PROXY"
+  integration_http_method = "POST"
+  uri                     = aws_lambda_function.aliases.invoke_arn
+}
+
+resource "aws_api_gateway_method" "delete_board_alias" {
   authorization    = "NONE"
   http_method      = "DELETE"
-  resource_id      = aws_api_gateway_resource.strikes.id
+  resource_id      = aws_api_gateway_resource.board_alias.id
   rest_api_id      = aws_api_gateway_rest_api.strikes.id
   api_key_required = true
 }
 
-resource "aws_api_gateway_integration" "delete_strikes" {
-  http_method             = aws_api_gateway_method.delete_strikes.http_method
-  resource_id             = aws_api_gateway_resource.strikes.id
+resource "aws_api_gateway_integration" "delete_board_alias" {
+  http_method             = aws_api_gateway_method.delete_board_alias.http_method
+  resource_id             = aws_api_gateway_resource.board_alias.id
   rest_api_id             = aws_api_gateway_rest_api.strikes.id
   type                    = "AWS_PROXY"
   integration_http_method = "POST"
-  uri                     = aws_lambda_function.delete_strikes.invoke_arn
+  uri                     = aws_lambda_function.aliases.invoke_arn
 }
 
-resource "aws_lambda_permission" "apigw_invoke_delete_strikes_lambda" {
+resource "aws_api_gateway_method" "merge_board_alias" {
+  authorization    = "NONE"
+  http_method      = "POST"
+  resource_id      = aws_api_gateway_resource.board_alias_merge.id
+  rest_api_id      = aws_api_gateway_rest_api.strikes.id
+  api_key_required = true
+}
+
+resource "aws_api_gateway_integration" "merge_board_alias" {
+  http_method             = aws_api_gateway_method.merge_board_alias.http_method
+  resource_id             = aws_api_gateway_resource.board_alias_merge.id
+  rest_api_id             = aws_api_gateway_rest_api.strikes.id
+  type                    = "AWS_PROXY"
+  integration_http_method = "POST"
+  uri                     = aws_lambda_function.aliases.invoke_arn
+}
+
+resource "aws_lambda_permission" "apigw_invoke_aliases_lambda" {
   statement_id  = "AllowAPIGatewayInvoke"
   action        = "lambda:InvokeFunction"
-  function_name = aws_lambda_function.delete_strikes.function_name
+  function_name = aws_lambda_function.aliases.function_name
   principal     = "apigateway.amazonaws.com"
 
   source_arn = "${aws_api_gateway_rest_api.strikes.execution_arn}/*/*"
 }
 
-resource "aws_iam_role" "delete_strikes_lambda_role" {
-  name               = "${local.delete_strikes_lambda_name}-role"
+resource "aws_iam_role" "aliases_lambda_role" {
+  name               = "${local.aliases_lambda_name}-role"
   assume_role_policy = data.aws_iam_policy_document.strikes_lambda_assume_role.json
   inline_policy {
-    name   = "dynamo_delete"
-    policy = data.aws_iam_policy_document.dynamo_delete.json
+    name   = "dynamo_aliases"
+    policy = data.aws_iam_policy_document.dynamo_aliases.json
   }
 }
 
-resource "aws_iam_role_policy_attachment" "delete_strikes_basic_execution_role_policy_attachment" {
-  role       = aws_iam_role.delete_strikes_lambda_role.name
+resource "aws_iam_role_policy_attachment" "aliases_basic_execution_role_policy_attachment" {
+  role       = aws_iam_role.aliases_lambda_role.name
   policy_arn = "arn:aws:iam::aws:policy/service-role/AWSLambdaBasicExecutionRole"
 }
 
-data "archive_file" "delete_strikes_lambda_archive" {
+data "archive_file" "aliases_lambda_archive" {
   type        = "zip"
-  source_file = "${path.module}/target/lambda/delete_strikes/bootstrap"
-  output_path = "${path.module}/target/archive/delete_strikes.zip"
+  source_file = "${path.module}/../../target/lambda/aliases/bootstrap"
+  output_path = "${path.module}/target/archive/aliases.zip"
 }
 
-resource "aws_lambda_function" "delete_strikes" {
-  filename      = data.archive_file.delete_strikes_lambda_archive.output_path
-  function_name = local.delete_strikes_lambda_name
-  role          = aws_iam_role.delete_strikes_lambda_role.arn
+resource "aws_lambda_function" "aliases" {
+  filename      = data.archive_file.aliases_lambda_archive.output_path
+  function_name = local.aliases_lambda_name
+  role          = aws_iam_role.aliases_lambda_role.arn
 
   handler = "bootstrap"
 
-  source_code_hash = data.archive_file.delete_strikes_lambda_archive.output_base64sha256
+  source_code_hash = data.archive_file.aliases_lambda_archive.output_base64sha256
 
   runtime = "provided.al2023"
 
@@ -437,42 +1100,36 @@ resource "aws_lambda_function" "delete_strikes" {
 }
 
 # -----------------------------------------------------------------------------
-# PUT STRIKE
+# IMPORT STRIKES
 # -----------------------------------------------------------------------------
-resource "aws_api_gateway_resource" "put_strike" {
-  parent_id   = aws_api_gateway_resource.strikes.id
-  path_part   = "{user}"
-  rest_api_id = aws_api_gateway_rest_api.strikes.id
-}
-
-resource "aws_api_gateway_method" "put_strike" {
+resource "aws_api_gateway_method" "import_strikes" {
   authorization    = "NONE"
-  http_method      = "PUT"
-  resource_id      = aws_api_gateway_resource.put_strike.id
+  http_method      = "POST"
+  resource_id      = aws_api_gateway_resource.board_strikes.id
   rest_api_id      = aws_api_gateway_rest_api.strikes.id
   api_key_required = true
 }
 
-resource "aws_api_gateway_integration" "put_strike" {
-  http_method             = aws_api_gateway_method.put_strike.http_method
-  resource_id             = aws_api_gateway_resource.put_strike.id
+resource "aws_api_gateway_integration" "import_strikes" {
+  http_method             = aws_api_gateway_method.import_strikes.http_method
+  resource_id             = aws_api_gateway_resource.board_strikes.id
   rest_api_id             = aws_api_gateway_rest_api.strikes.id
   type                    = "AWS_PROXY"
   integration_http_method = "POST"
-  uri                     = aws_lambda_function.put_strike.invoke_arn
+  uri                     = aws_lambda_function.import_strikes.invoke_arn
 }
 
-resource "aws_lambda_permission" "apigw_invoke_put_strike_lambda" {
+resource "aws_lambda_permission" "apigw_invoke_import_strikes_lambda" {
   statement_id  = "AllowAPIGatewayInvoke"
   action        = "lambda:InvokeFunction"
-  function_name = aws_lambda_function.put_strike.function_name
+  function_name = aws_lambda_function.import_strikes.function_name
   principal     = "apigateway.amazonaws.com"
 
   source_arn = "${aws_api_gateway_rest_api.strikes.execution_arn}/*/*"
 }
 
-resource "aws_iam_role" "put_strike_lambda_role" {
-  name               = "${local.put_strike_lambda_name}-role"
+resource "aws_iam_role" "import_strikes_lambda_role" {
+  name               = "${local.import_strikes_lambda_name}-role"
   assume_role_policy = data.aws_iam_policy_document.strikes_lambda_assume_role.json
   inline_policy {
     name   = "dynamo_write"
@@ -480,31 +1137,37 @@ resource "aws_iam_role" "put_strike_lambda_role" {
   }
 }
 
-resource "aws_iam_role_policy_attachment" "strikes_basic_execution_role_policy_attachment" {
-  role       = aws_iam_role.put_strike_lambda_role.name
+resource "aws_iam_role_policy_attachment" "import_strikes_basic_execution_role_policy_attachment" {
+  role       = aws_iam_role.import_strikes_lambda_role.name
   policy_arn = "arn:aws:iam::aws:policy/service-role/AWSLambdaBasicExecutionRole"
 }
 
-data "archive_file" "put_strike_lambda_archive" {
+data "archive_file" "import_strikes_lambda_archive" {
   type        = "zip"
-  source_file = "${path.module}/target/lambda/put_strike/bootstrap"
-  output_path = "${path.module}/target/archive/put_strike.zip"
+  source_file = "${path.module}/../../target/lambda/import_strikes/bootstrap"
+  output_path = "${path.module}/target/archive/import_strikes.zip"
 }
 
-resource "aws_lambda_function" "put_strike" {
-  filename      = data.archive_file.put_strike_lambda_archive.output_path
-  function_name = local.put_strike_lambda_name
-  role          = aws_iam_role.put_strike_lambda_role.arn
+resource "aws_lambda_function" "import_strikes" {
+  filename      = data.archive_file.import_strikes_lambda_archive.output_path
+  function_name = local.import_strikes_lambda_name
+  role          = aws_iam_role.import_strikes_lambda_role.arn
 
   handler = "bootstrap"
 
-  source_code_hash = data.archive_file.put_strike_lambda_archive.output_base64sha256
+  source_code_hash = data.archive_file.import_strikes_lambda_archive.output_base64sha256
 
   runtime = "provided.al2023"
 
   architectures = ["x86_64"]
 
   memory_size = 1024
+
+  environment {
+    variables = {
+      STRIKE_EXPIRY_DAYS = var.strike_expiry_days
+    }
+  }
 }
 
 # -----------------------------------------------------------------------------
@@ -567,7 +1230,7 @@ resource "aws_iam_role_policy_attachment" "health_lambda_basic_execution_role_po
 
 data "archive_file" "lambda_archive" {
   type        = "zip"
-  source_file = "${path.module}/target/lambda/health/bootstrap"
+  source_file = "${path.module}/../../target/lambda/health/bootstrap"
   output_path = "${path.module}/target/archive/health.zip"
 }
 
@@ -640,7 +1303,7 @@ resource "aws_iam_role_policy_attachment" "website_basic_execution_role_policy_a
 
 data "archive_file" "website_lambda_archive" {
   type        = "zip"
-  source_file = "${path.module}/target/lambda/website/bootstrap"
+  source_file = "${path.module}/../../target/lambda/website/bootstrap"
   output_path = "${path.module}/target/archive/website.zip"
 }
 
@@ -681,6 +1344,14 @@ resource "aws_api_gateway_deployment" "strikes" {
       aws_api_gateway_resource.put_strike.id,
       aws_api_gateway_method.put_strike.id,
       aws_api_gateway_integration.put_strike.id,
+      aws_api_gateway_method.delete_user_strikes.id,
+      aws_api_gateway_integration.delete_user_strikes.id,
+      aws_api_gateway_resource.get_strike_history.id,
+      aws_api_gateway_method.get_strike_history.id,
+      aws_api_gateway_integration.get_strike_history.id,
+      aws_api_gateway_resource.delete_latest_strike.id,
+      aws_api_gateway_method.delete_latest_strike.id,
+      aws_api_gateway_integration.delete_latest_strike.id,
       aws_api_gateway_resource.strikes.id,
       aws_api_gateway_method.get_strikes.id,
       aws_api_gateway_integration.get_strikes.id,
@@ -688,6 +1359,39 @@ resource "aws_api_gateway_deployment" "strikes" {
       aws_api_gateway_integration.delete_strikes.id,
       aws_api_gateway_method.website.id,
       aws_api_gateway_integration.website.id,
+      aws_api_gateway_resource.boards.id,
+      aws_api_gateway_resource.board.id,
+      aws_api_gateway_resource.board_strikes.id,
+      aws_api_gateway_resource.board_strike.id,
+      aws_api_gateway_resource.board_latest_strike.id,
+      aws_api_gateway_resource.board_strike_history.id,
+      aws_api_gateway_method.get_boards.id,
+      aws_api_gateway_integration.get_boards.id,
+      aws_api_gateway_method.get_board_strikes.id,
+      aws_api_gateway_integration.get_board_strikes.id,
+      aws_api_gateway_method.delete_board_strikes.id,
+      aws_api_gateway_integration.delete_board_strikes.id,
+      aws_api_gateway_method.put_board_strike.id,
+      aws_api_gateway_integration.put_board_strike.id,
+      aws_api_gateway_method.delete_board_user_strikes.id,
+      aws_api_gateway_integration.delete_board_user_strikes.id,
+      aws_api_gateway_method.delete_board_latest_strike.id,
+      aws_api_gateway_integration.delete_board_latest_strike.id,
+      aws_api_gateway_method.get_board_strike_history.id,
+      aws_api_gateway_integration.get_board_strike_history.id,
+      aws_api_gateway_method.import_strikes.id,
+      aws_api_gateway_integration.import_strikes.id,
+      aws_api_gateway_resource.board_aliases.id,
+      aws_api_gateway_resource.board_alias.id,
+      aws_api_gateway_resource.board_alias_merge.id,
+      aws_api_gateway_method.get_board_aliases.id,
+      aws_api_gateway_integration.get_board_aliases.id,
+      aws_api_gateway_method.put_board_alias.id,
+      aws_api_gateway_integration.put_board_alias.id,
+      aws_api_gateway_method.delete_board_alias.id,
+      aws_api_gateway_integration.delete_board_alias.id,
+      aws_api_gateway_method.merge_board_alias.id,
+      aws_api_gateway_integration.merge_board_alias.id,
     ]))
   }
 
@@ -782,6 +1486,8 @@ resource "aws_api_gateway_usage_plan_key" "dev" {
 # -----------------------------------------------------------------------------
 # STRIKES TABLE
 # -----------------------------------------------------------------------------
+# The table from before boards existed. It's kept until its strikes were copied into the
+# BoardStrikes table with `cargo run --bin migrate_strikes`, see the README.
 resource "aws_dynamodb_table" "strikes-table" {
   name             = "Strikes"
   billing_mode     = "PROVISIONED"
@@ -795,6 +1501,118 @@ resource "aws_dynamodb_table" "strikes-table" {
     name = "UserId"
     type = "S"
   }
+
+  lifecycle {
+    prevent_destroy = true
+  }
+}
+
+resource "aws_dynamodb_table" "board-strikes-table" {
+  name             = "BoardStrikes"
+  billing_mode     = "PROVISIONED"
+  stream_enabled   = true
+  stream_view_type = "KEYS_ONLY"
+  read_capacity    = 8
+  write_capacity   = 8
+  hash_key         = "Board"
+  range_key        = "UserId"
+
+  attribute {
+    name = "Board"
+    type = "S"
+  }
+
+  attribute {
+    name = "UserId"
+    type = "S"
+  }
+
+  lifecycle {
+    prevent_destroy = true
+  }
+}
+
+# -----------------------------------------------------------------------------
+# STRIKE EVENTS TABLE
+# -----------------------------------------------------------------------------
+resource "aws_dynamodb_table" "strike-events-table" {
+  name           = "StrikeEvents"
+  billing_mode   = "PROVISIONED"
+  read_capacity  = 8
+  write_capacity = 8
+  hash_key       = "BoardUserId"
+  range_key      = "Timestamp"
+
+  attribute {
+    name = "BoardUserId"
+    type = "S"
+  }
+
+  attribute {
+    name = "Timestamp"
+    type = "S"
+  }
+
+  ttl {
+    attribute_name = "ExpiresAt"
+    enabled        = true
+  }
+}
+
+# -----------------------------------------------------------------------------
+# ALIASES TABLE
+# -----------------------------------------------------------------------------
+resource "aws_dynamodb_table" "aliases-table" {
+  name           = "Aliases"
+  billing_mode   = "PROVISIONED"
+  read_capacity  = 2
+  write_capacity = 2
+  hash_key       = "Board"
+  range_key      = "Alias"
+
+  attribute {
+    name = "Board"
+    type = "S"
+  }
+
+  attribute {
+    name = "Alias"
+    type = "S"
+  }
+}
+
+data "aws_iam_policy_document" "dynamo_aliases" {
+  statement {
+    effect = "Allow"
+
+    actions = [
+      "dynamodb:PutItem",
+      "dynamodb:GetItem",
+      "dynamodb:UpdateItem",
+      "dynamodb:DeleteItem",
+      "dynamodb:Query",
+    ]
+
+    resources = [
+      aws_dynamodb_table.board-strikes-table.arn,
+      aws_dynamodb_table.strike-events-table.arn,
+      aws_dynamodb_table.aliases-table.arn
+    ]
+  }
+}
+
+data "aws_iam_policy_document" "dynamo_events_read_only" {
+  statement {
+    effect = "Allow"
+
+    actions = [
+      "dynamodb:Query",
+    ]
+
+    resources = [
+      aws_dynamodb_table.strike-events-table.arn
+    ]
+  }
 }
 
 data "aws_iam_policy_document" "strikes_lambda_assume_role" {
@@ -818,11 +1636,14 @@ data "aws_iam_policy_document" "dynamo_write" {
       "dynamodb:PutItem",
       "dynamodb:GetItem",
       "dynamodb:UpdateItem",
+      "dynamodb:Query",
       "dynamodb:Scan"
     ]
 
     resources = [
-      aws_dynamodb_table.strikes-table.arn
+      aws_dynamodb_table.board-strikes-table.arn,
+      aws_dynamodb_table.strike-events-table.arn,
+      aws_dynamodb_table.aliases-table.arn
     ]
   }
 }
@@ -833,11 +1654,31 @@ data "aws_iam_policy_document" "dynamo_read_only" {
 
     actions = [
       "dynamodb:GetItem",
+      "dynamodb:Query",
       "dynamodb:Scan",
     ]
 
     resources = [
-      aws_dynamodb_table.strikes-table.arn
+      aws_dynamodb_table.board-strikes-table.arn,
+      aws_dynamodb_table.strike-events-table.arn,
+      aws_dynamodb_table.aliases-table.arn
+    ]
+  }
+}
+
+data "aws_iam_policy_document" "dynamo_decrement" {
+  statement {
+    effect = "Allow"
+
+    actions = [
+      "dynamodb:UpdateItem",
+      "dynamodb:DeleteItem",
+      "dynamodb:Query",
+    ]
+
+    resources = [
+      aws_dynamodb_table.board-strikes-table.arn,
+      aws_dynamodb_table.strike-events-table.arn
     ]
   }
 }
@@ -848,11 +1689,14 @@ data "aws_iam_policy_document" "dynamo_delete" {
 
     actions = [
       "dynamodb:DeleteItem",
+      "dynamodb:BatchWriteItem",
       "dynamodb:Scan",
+      "dynamodb:Query",
     ]
 
     resources = [
-      aws_dynamodb_table.strikes-table.arn
+      aws_dynamodb_table.board-strikes-table.arn,
+      aws_dynamodb_table.strike-events-table.arn
     ]
   }
 }
@@ -885,7 +1729,7 @@ output "delete_strikes_lambda_function_name" {
 }
 
 output "strikes_db_stream_arn" {
-  value = aws_dynamodb_table.strikes-table.stream_arn
+  value = aws_dynamodb_table.board-strikes-table.stream_arn
 }
 
 output "website_lambda_invoke_arn" {
@@ -0,0 +1,11 @@
+use lambda_http::{run, service_fn, tracing, Error};
+use lib::handlers::get_strike_history::function_handler;
+use lib::repository::DynamoDbRepository;
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    tracing::init_default_subscriber();
+
+    let repository = DynamoDbRepository::from_env().await;
+    run(service_fn(|request| function_handler(request, &repository))).await
+}
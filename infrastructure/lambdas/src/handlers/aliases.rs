@@ -0,0 +1,93 @@
+use crate::repository::StrikesRepository;
+use crate::strikes_db::{resolve_alias, DEFAULT_BOARD};
+use lambda_http::{http::Method, Body, Error, Request, RequestExt, Response};
+use strikes_core::{
+    name::Name,
+    wire::{AliasRequest, AliasResponse, MergeResponse},
+};
+
+fn respond(status: u16, body: String) -> Result<Response<Body>, Error> {
+    Ok(Response::builder()
+        .status(status)
+        .body(Body::Text(body))
+        .expect("Failed to render response"))
+}
+
+// Serves every route below /boards/{board}/aliases, they share the table and its validation.
+pub async fn function_handler(
+    request: Request,
+    repository: &dyn StrikesRepository,
+) -> Result<Response<Body>, Error> {
+    let params = request.path_parameters();
+    let Ok(board) = Name::parse(params.first("board").unwrap_or(DEFAULT_BOARD)) else {
+        return respond(400, "Invalid board".to_string());
+    };
+    let alias = match params.first("alias").map(Name::parse).transpose() {
+        Ok(alias) => alias,
+        Err(_) => return respond(400, "Invalid alias".to_string()),
+    };
+    let board = board.as_str();
+    let alias = alias.as_deref();
+
+    let aliases = repository.get_aliases(board).await?;
+
+    match (request.method().clone(), alias) {
+        (Method::GET, None) => {
+            let body = aliases
+                .iter()
+                .map(|(alias, name)| AliasResponse {
+                    alias: alias.clone(),
+                    name: name.clone(),
+                })
+                .collect::<Vec<_>>();
+
+            Ok(Response::builder()
+                .status(200)
+                .header("Content-Type", "application/json")
+                .body(Body::Text(serde_json::to_string(&body).unwrap()))
+                .expect("Failed to render response"))
+        }
+        (Method::PUT, Some(alias)) => {
+            let name = match serde_json::from_slice::<AliasRequest>(request.body().as_ref())
+                .map(|body| Name::parse(&body.name))
+            {
+                Ok(Ok(name)) => name,
+                _ => return respond(400, "Invalid request body".to_string()),
+            };
+
+            // Aliases always point at a name without aliases, so they never form a chain.
+            let name = resolve_alias(&aliases, &name);
+            if name == alias || aliases.values().any(|canonical| canonical == alias) {
+                return respond(400, format!("{} can't be an alias of {}", alias, name));
+            }
+
+            repository.put_alias(board, alias, name).await?;
+
+            respond(200, format!("Strikes for {} now go to {}", alias, name))
+        }
+        (Method::DELETE, Some(alias)) => match repository.delete_alias(board, alias).await? {
+            true => respond(200, format!("Removed the alias {}", alias)),
+            false => respond(404, format!("No alias {}", alias)),
+        },
+        (Method::POST, Some(alias)) if request.uri().path().ends_with("/merge") => {
+            let Some(name) = aliases.get(alias) else {
+                return respond(404, format!("No alias {}", alias));
+            };
+
+            let merged = repository.merge_strikes(board, alias, name).await?;
+
+            Ok(Response::builder()
+                .status(200)
+                .header("Content-Type", "application/json")
+                .body(Body::Text(
+                    serde_json::to_string(&MergeResponse {
+                        name: name.clone(),
+                        merged,
+                    })
+                    .unwrap(),
+                ))
+                .expect("Failed to render response"))
+        }
+        _ => respond(405, "Method not allowed".to_string()),
+    }
+}
@@ -0,0 +1,136 @@
+use crate::repository::{count_strikes, StrikesRepository};
+use crate::strikes_db::{alias_spellings, resolve_alias, DEFAULT_BOARD};
+use lambda_http::{Body, Error, Request, RequestExt, Response};
+use strikes_core::{name::Name, wire::StrikeCountResponse};
+
+pub async fn function_handler(
+    request: Request,
+    repository: &dyn StrikesRepository,
+) -> Result<Response<Body>, Error> {
+    let params = request.path_parameters();
+    let user = params.first("user");
+    let Ok(board) = Name::parse(params.first("board").unwrap_or(DEFAULT_BOARD)) else {
+        return Ok(Response::builder()
+            .status(400)
+            .body(Body::Text("Invalid board".to_string()))
+            .expect("Failed to render response"));
+    };
+    let board = board.as_str();
+
+    match user {
+        Some(username) => {
+            let Ok(username) = Name::parse(username) else {
+                return Ok(Response::builder()
+                    .status(400)
+                    .body(Body::Text("Invalid username".to_string()))
+                    .expect("Failed to render response"));
+            };
+            let aliases = repository.get_aliases(board).await?;
+            let username = resolve_alias(&aliases, &username);
+
+            // The latest strike may still be recorded under an alias.
+            let mut latest: Option<(String, &str)> = None;
+            for spelling in alias_spellings(&aliases, username) {
+                let events = repository.get_strike_events(board, spelling).await?;
+                if let Some(event) = events.last().filter(|event| {
+                    latest
+                        .as_ref()
+                        .is_none_or(|(timestamp, _)| event.timestamp > *timestamp)
+                }) {
+                    latest = Some((event.timestamp.clone(), spelling));
+                }
+            }
+            let spelling = latest.map_or(username, |(_, spelling)| spelling);
+
+            let strike_count = repository.remove_strike(board, spelling).await?;
+            let strike_count =
+                count_strikes(repository, board, username, &aliases, strike_count).await?;
+            Ok(Response::builder()
+                .status(200)
+                .body(Body::Text(
+                    serde_json::to_string(&StrikeCountResponse {
+                        name: username.to_string(),
+                        strike_count,
+                        threshold_reached: None,
+                    })
+                    .unwrap(),
+                ))
+                .expect("Failed to render response"))
+        }
+        None => Ok(Response::builder()
+            .status(400)
+            .body(Body::Text("Missing user parameter".to_string()))
+            .expect("Failed to render response")),
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use crate::repository::InMemoryRepository;
+    use std::collections::HashMap;
+
+    #[tokio::test]
+    async fn it_should_remove_a_strike_given_under_an_alias() {
+        let repository = InMemoryRepository::default();
+        repository
+            .put_alias(DEFAULT_BOARD, "gue", "guenther")
+            .await
+            .unwrap();
+        repository
+            .record_strike(DEFAULT_BOARD, "guenther", None, None)
+            .await
+            .unwrap();
+        repository
+            .record_strike(DEFAULT_BOARD, "guenther", Some("late"), None)
+            .await
+            .unwrap();
+        let request = Request::default()
+            .with_path_parameters(HashMap::from([("user".to_string(), "gue".to_string())]));
+
+        let response = function_handler(request, &repository).await.unwrap();
+
+        assert_eq!(
+            serde_json::from_slice::<StrikeCountResponse>(response.body().as_ref()).unwrap(),
+            StrikeCountResponse {
+                name: "guenther".to_string(),
+                strike_count: 1,
+                threshold_reached: None,
+            }
+        );
+        assert_eq!(
+            repository
+                .get_strike_events(DEFAULT_BOARD, "guenther")
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn it_should_take_back_a_strike_given_in_another_case() {
+        let repository = InMemoryRepository::default();
+        repository
+            .record_strike("coffee", "guenther", None, None)
+            .await
+            .unwrap();
+        let request = |user: &str| {
+            Request::default().with_path_parameters(HashMap::from([
+                ("user".to_string(), user.to_string()),
+                ("board".to_string(), "Coffee".to_string()),
+            ]))
+        };
+
+        let too_long = function_handler(request("guentherguentherguenther"), &repository)
+            .await
+            .unwrap();
+        let response = function_handler(request("Guenther"), &repository)
+            .await
+            .unwrap();
+
+        assert_eq!(too_long.status(), 400);
+        assert_eq!(response.status(), 200);
+        assert!(repository.get_strikes("coffee").await.unwrap().is_empty());
+    }
+}
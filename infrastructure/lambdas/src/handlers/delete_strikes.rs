@@ -0,0 +1,90 @@
+use crate::repository::StrikesRepository;
+use crate::strikes_db::{DeleteReport, DEFAULT_BOARD};
+use lambda_http::{Body, Error, Request, RequestExt, Response};
+use strikes_core::{name::Name, wire::DeleteResponse};
+
+pub async fn function_handler(
+    event: Request,
+    repository: &dyn StrikesRepository,
+) -> Result<Response<Body>, Error> {
+    let params = event.path_parameters();
+    let Ok(board) = Name::parse(params.first("board").unwrap_or(DEFAULT_BOARD)) else {
+        return Ok(Response::builder()
+            .status(400)
+            .body(Body::Text("Invalid board".to_string()))
+            .expect("Failed to render response"));
+    };
+    let board = board.as_str();
+
+    let report = repository.delete_all_strikes(board).await?
+        + repository.delete_all_strike_events(board).await?;
+
+    Ok(respond(report))
+}
+
+// Whatever is left over stays until the next clear, the client gets told how much that is.
+fn respond(report: DeleteReport) -> Response<Body> {
+    Response::builder()
+        .status(if report.failed > 0 { 500 } else { 200 })
+        .header("Content-Type", "application/json")
+        .body(Body::Text(
+            serde_json::to_string(&DeleteResponse {
+                removed: report.removed,
+                failed: report.failed,
+            })
+            .unwrap(),
+        ))
+        .expect("Failed to render response")
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use crate::repository::InMemoryRepository;
+
+    fn delete_response(response: &Response<Body>) -> DeleteResponse {
+        serde_json::from_slice(response.body().as_ref()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn it_should_count_the_removed_entries() {
+        let repository = InMemoryRepository::default();
+        repository
+            .increment_strikes(DEFAULT_BOARD, "guenther", None)
+            .await
+            .unwrap();
+        repository
+            .add_strike_event(DEFAULT_BOARD, "guenther", None, None)
+            .await
+            .unwrap();
+        repository
+            .increment_strikes("other", "heinz", None)
+            .await
+            .unwrap();
+
+        let response = function_handler(Request::default(), &repository)
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), 200);
+        assert_eq!(
+            delete_response(&response),
+            DeleteResponse {
+                removed: 2,
+                failed: 0,
+            }
+        );
+        assert_eq!(repository.get_all_strikes().await.unwrap().len(), 1);
+    }
+
+    #[test]
+    fn it_should_answer_a_partial_failure_with_a_server_error() {
+        let response = respond(DeleteReport {
+            removed: 20,
+            failed: 5,
+        });
+
+        assert_eq!(response.status(), 500);
+        assert_eq!(delete_response(&response).failed, 5);
+    }
+}
@@ -0,0 +1,85 @@
+use crate::repository::StrikesRepository;
+use crate::strikes_db::{alias_spellings, resolve_alias, DEFAULT_BOARD};
+use lambda_http::{Body, Error, Request, RequestExt, Response};
+use strikes_core::name::Name;
+
+pub async fn function_handler(
+    request: Request,
+    repository: &dyn StrikesRepository,
+) -> Result<Response<Body>, Error> {
+    let params = request.path_parameters();
+    let user = params.first("user");
+    let Ok(board) = Name::parse(params.first("board").unwrap_or(DEFAULT_BOARD)) else {
+        return Ok(Response::builder()
+            .status(400)
+            .body(Body::Text("Invalid board".to_string()))
+            .expect("Failed to render response"));
+    };
+    let board = board.as_str();
+
+    match user {
+        Some(username) => {
+            let Ok(username) = Name::parse(username) else {
+                return Ok(Response::builder()
+                    .status(400)
+                    .body(Body::Text("Invalid username".to_string()))
+                    .expect("Failed to render response"));
+            };
+            let aliases = repository.get_aliases(board).await?;
+            let username = resolve_alias(&aliases, &username);
+            for spelling in alias_spellings(&aliases, username) {
+                repository.delete_strikes(board, spelling).await?;
+                repository.delete_strike_events(board, spelling).await?;
+            }
+
+            Ok(Response::builder()
+                .status(200)
+                .body(Body::Text(format!("All strikes of {} deleted", username)))
+                .expect("Failed to render response"))
+        }
+        None => Ok(Response::builder()
+            .status(400)
+            .body(Body::Text("Missing user parameter".to_string()))
+            .expect("Failed to render response")),
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use crate::repository::InMemoryRepository;
+    use std::collections::HashMap;
+
+    #[tokio::test]
+    async fn it_should_clear_a_name_and_its_aliases() {
+        let repository = InMemoryRepository::default();
+        repository
+            .record_strike(DEFAULT_BOARD, "gue", None, None)
+            .await
+            .unwrap();
+        repository
+            .put_alias(DEFAULT_BOARD, "gue", "guenther")
+            .await
+            .unwrap();
+        repository
+            .record_strike(DEFAULT_BOARD, "guenther", None, None)
+            .await
+            .unwrap();
+        let request = Request::default()
+            .with_path_parameters(HashMap::from([("user".to_string(), "gue".to_string())]));
+
+        let response = function_handler(request, &repository).await.unwrap();
+
+        assert_eq!(response.status(), 200);
+        assert!(repository
+            .get_strikes(DEFAULT_BOARD)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(repository
+            .get_strike_events(DEFAULT_BOARD, "gue")
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
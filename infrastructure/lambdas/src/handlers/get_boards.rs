@@ -0,0 +1,35 @@
+use crate::repository::StrikesRepository;
+use crate::strikes_db::{expire_after_days, sort_strikes_desc};
+use chrono::Utc;
+use lambda_http::{Body, Error, Request, RequestExt, Response};
+use strikes_core::wire::BoardStrikesResponse;
+
+pub async fn function_handler(
+    event: Request,
+    repository: &dyn StrikesRepository,
+) -> Result<Response<Body>, Error> {
+    let include_expired = event
+        .query_string_parameters()
+        .first("include_expired")
+        .is_some_and(|include_expired| include_expired == "true");
+
+    let strikes = match expire_after_days() {
+        Some(_) if !include_expired => {
+            repository
+                .get_active_strikes(None, Utc::now().timestamp())
+                .await?
+        }
+        _ => repository.get_all_strikes().await?,
+    };
+    let body = sort_strikes_desc(&strikes)
+        .into_iter()
+        .map(BoardStrikesResponse::from)
+        .collect::<Vec<_>>();
+
+    Ok(Response::builder()
+        .status(200)
+        .header("Content-Type", "application/json")
+        .header("Access-Control-Allow-Origin", "*")
+        .body(Body::Text(serde_json::to_string(&body).unwrap()))
+        .expect("Failed to render response"))
+}
@@ -0,0 +1,93 @@
+use crate::repository::StrikesRepository;
+use crate::strikes_db::{alias_spellings, resolve_alias, DEFAULT_BOARD};
+use lambda_http::{Body, Error, Request, RequestExt, Response};
+use strikes_core::{name::Name, strike::Strike};
+
+pub async fn function_handler(
+    request: Request,
+    repository: &dyn StrikesRepository,
+) -> Result<Response<Body>, Error> {
+    let params = request.path_parameters();
+    let user = params.first("user");
+    let Ok(board) = Name::parse(params.first("board").unwrap_or(DEFAULT_BOARD)) else {
+        return Ok(Response::builder()
+            .status(400)
+            .body(Body::Text("Invalid board".to_string()))
+            .expect("Failed to render response"));
+    };
+    let board = board.as_str();
+
+    match user {
+        Some(username) => {
+            let Ok(username) = Name::parse(username) else {
+                return Ok(Response::builder()
+                    .status(400)
+                    .body(Body::Text("Invalid username".to_string()))
+                    .expect("Failed to render response"));
+            };
+            let aliases = repository.get_aliases(board).await?;
+            let username = resolve_alias(&aliases, &username);
+            let mut strikes = vec![];
+            for spelling in alias_spellings(&aliases, username) {
+                strikes.extend(
+                    repository
+                        .get_strike_events(board, spelling)
+                        .await?
+                        .into_iter()
+                        .map(|event| Strike {
+                            timestamp: event.timestamp.parse().ok(),
+                            reason: event.reason,
+                        }),
+                );
+            }
+            let body = Strike::sort_asc_by_timestamp(strikes);
+
+            Ok(Response::builder()
+                .status(200)
+                .header("Content-Type", "application/json")
+                .body(Body::Text(serde_json::to_string(&body).unwrap()))
+                .expect("Failed to render response"))
+        }
+        None => Ok(Response::builder()
+            .status(400)
+            .body(Body::Text("Missing user parameter".to_string()))
+            .expect("Failed to render response")),
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use crate::repository::InMemoryRepository;
+    use std::collections::HashMap;
+
+    #[tokio::test]
+    async fn it_should_list_the_history_of_an_alias_for_its_name() {
+        let repository = InMemoryRepository::default();
+        repository
+            .record_strike(DEFAULT_BOARD, "gue", Some("typo"), None)
+            .await
+            .unwrap();
+        repository
+            .put_alias(DEFAULT_BOARD, "gue", "guenther")
+            .await
+            .unwrap();
+        repository
+            .record_strike(DEFAULT_BOARD, "guenther", Some("late"), None)
+            .await
+            .unwrap();
+        let request = Request::default()
+            .with_path_parameters(HashMap::from([("user".to_string(), "gue".to_string())]));
+
+        let response = function_handler(request, &repository).await.unwrap();
+
+        assert_eq!(
+            serde_json::from_slice::<Vec<Strike>>(response.body().as_ref())
+                .unwrap()
+                .into_iter()
+                .map(|strike| strike.reason)
+                .collect::<Vec<_>>(),
+            vec![Some("typo".to_string()), Some("late".to_string())]
+        );
+    }
+}
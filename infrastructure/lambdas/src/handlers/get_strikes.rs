@@ -0,0 +1,317 @@
+use crate::repository::StrikesRepository;
+use crate::strikes_db::{
+    expire_after_days, fold_aliases, is_active, strikes_from_events, StrikeEntity, DEFAULT_BOARD,
+};
+use chrono::Utc;
+use lambda_http::{Body, Error, Request, RequestExt, Response};
+use std::collections::BTreeMap;
+use strikes_core::{
+    name::Name,
+    render::html_list,
+    tarnished::Tarnished,
+    wire::{StrikesPage, StrikesResponse, DEFAULT_PAGE_SIZE},
+};
+
+fn bad_request(message: &str) -> Result<Response<Body>, Error> {
+    Ok(Response::builder()
+        .status(400)
+        .body(Body::Text(message.to_string()))
+        .expect("Failed to render response"))
+}
+
+// Counts only the events that haven't expired yet, querying the events of each name instead of
+// scanning all of them. Names without an active strike are left out.
+async fn active_strikes(
+    repository: &dyn StrikesRepository,
+    strikes: Vec<StrikeEntity>,
+    now: i64,
+) -> Result<Vec<StrikeEntity>, Error> {
+    let mut active = vec![];
+    for strike in strikes {
+        let events = repository
+            .get_strike_events(&strike.board, &strike.user_id)
+            .await?
+            .into_iter()
+            .filter(|event| is_active(event, now))
+            .collect();
+        active.extend(strikes_from_events(events));
+    }
+
+    Ok(active)
+}
+
+// Reads the names after the cursor from the table until the page is full, skipping aliases.
+// Their strikes are read for the names on the page instead, wherever they are sorted.
+async fn strikes_page(
+    repository: &dyn StrikesRepository,
+    board: &str,
+    aliases: &BTreeMap<String, String>,
+    cursor: Option<&str>,
+    limit: usize,
+    now: Option<i64>,
+) -> Result<StrikesPage, Error> {
+    let mut names = vec![];
+    let mut after = cursor.map(str::to_string);
+    // One more than the page holds tells whether another page follows.
+    let more = loop {
+        let wanted = limit + 1 - names.len();
+        let strikes = repository
+            .get_strikes_after(board, after.as_deref(), Some(wanted))
+            .await?;
+        let exhausted = strikes.len() < wanted;
+        after = strikes
+            .last()
+            .map(|strike| strike.user_id.clone())
+            .or(after);
+        names.extend(
+            strikes
+                .into_iter()
+                .filter(|strike| !aliases.contains_key(&strike.user_id)),
+        );
+
+        if names.len() > limit {
+            break true;
+        }
+        if exhausted {
+            break false;
+        }
+    };
+    names.truncate(limit);
+    let next_cursor = names
+        .last()
+        .filter(|_| more)
+        .map(|strike| strike.user_id.clone());
+
+    let mut strikes = names;
+    for (alias, name) in aliases {
+        if strikes.iter().any(|strike| &strike.user_id == name) {
+            strikes.extend(repository.get_strike(board, alias).await?);
+        }
+    }
+    let strikes = match now {
+        Some(now) => active_strikes(repository, strikes, now).await?,
+        None => strikes,
+    };
+
+    Ok(StrikesPage {
+        strikes: fold_aliases(strikes, aliases)
+            .into_iter()
+            .map(Tarnished::from)
+            .map(StrikesResponse::from)
+            .collect(),
+        next_cursor,
+    })
+}
+
+pub async fn function_handler(
+    event: Request,
+    repository: &dyn StrikesRepository,
+) -> Result<Response<Body>, Error> {
+    let params = event.path_parameters();
+    let Ok(board) = Name::parse(params.first("board").unwrap_or(DEFAULT_BOARD)) else {
+        return bad_request("Invalid board");
+    };
+    let board = board.as_str();
+    let query = event.query_string_parameters();
+    let include_expired = query
+        .first("include_expired")
+        .is_some_and(|include_expired| include_expired == "true");
+    let cursor = query.first("cursor");
+    let limit = match query.first("limit").map(str::parse::<usize>) {
+        Some(Ok(0)) | Some(Err(_)) => return bad_request("Invalid limit"),
+        Some(Ok(limit)) => Some(limit),
+        None => None,
+    };
+
+    let aliases = repository.get_aliases(board).await?;
+    let now = match expire_after_days() {
+        Some(_) if !include_expired => Some(Utc::now().timestamp()),
+        _ => None,
+    };
+
+    let accept = event
+        .headers()
+        .get("accept")
+        .and_then(|accept| accept.to_str().ok());
+
+    let body = match (accept, limit, cursor) {
+        (Some("text/html"), _, _) | (_, None, None) => {
+            let strikes = repository.get_strikes(board).await?;
+            let strikes = match now {
+                Some(now) => active_strikes(repository, strikes, now).await?,
+                None => strikes,
+            };
+            let tarnished = Tarnished::sort_desc_by_strike(
+                fold_aliases(strikes, &aliases)
+                    .into_iter()
+                    .map(Tarnished::from)
+                    .collect(),
+            );
+
+            if accept == Some("text/html") {
+                return Ok(Response::builder()
+                    .status(200)
+                    .header("Content-Type", "text/html")
+                    .header("Access-Control-Allow-Origin", "*")
+                    .body(Body::Text(html_list(&tarnished)))
+                    .expect("Failed to render response"));
+            }
+
+            serde_json::to_string(
+                &tarnished
+                    .into_iter()
+                    .map(StrikesResponse::from)
+                    .collect::<Vec<_>>(),
+            )
+            .unwrap()
+        }
+        (_, limit, cursor) => {
+            let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE);
+            let page = strikes_page(repository, board, &aliases, cursor, limit, now).await?;
+            serde_json::to_string(&page).unwrap()
+        }
+    };
+
+    Ok(Response::builder()
+        .status(200)
+        .header("Content-Type", "application/json")
+        .header("Access-Control-Allow-Origin", "*")
+        .body(Body::Text(body))
+        .expect("Failed to render response"))
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use crate::repository::InMemoryRepository;
+    use std::collections::HashMap;
+
+    async fn repository() -> InMemoryRepository {
+        let repository = InMemoryRepository::default();
+        repository
+            .increment_strikes(DEFAULT_BOARD, "guenther", None)
+            .await
+            .unwrap();
+        repository
+            .add_strikes(DEFAULT_BOARD, "heinz", &["late".to_string()], 2)
+            .await
+            .unwrap();
+        repository
+            .increment_strikes(DEFAULT_BOARD, "guenni", None)
+            .await
+            .unwrap();
+        repository
+            .put_alias(DEFAULT_BOARD, "guenni", "guenther")
+            .await
+            .unwrap();
+        repository
+    }
+
+    #[tokio::test]
+    async fn it_should_list_the_tarnished_as_json() {
+        let repository = repository().await;
+
+        let response = function_handler(Request::default(), &repository)
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), 200);
+        assert_eq!(
+            serde_json::from_slice::<Vec<StrikesResponse>>(response.body().as_ref()).unwrap(),
+            vec![
+                StrikesResponse {
+                    name: "guenther".to_string(),
+                    strike_count: 2,
+                    reasons: vec![],
+                },
+                StrikesResponse {
+                    name: "heinz".to_string(),
+                    strike_count: 2,
+                    reasons: vec!["late".to_string()],
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn it_should_list_the_tarnished_page_by_page() {
+        let repository = repository().await;
+        let page = |query: &[(&str, &str)]| {
+            Request::default().with_query_string_parameters(
+                query
+                    .iter()
+                    .map(|(key, value)| (key.to_string(), value.to_string()))
+                    .collect::<HashMap<_, _>>(),
+            )
+        };
+
+        let first = function_handler(page(&[("limit", "1")]), &repository)
+            .await
+            .unwrap();
+        let first = serde_json::from_slice::<StrikesPage>(first.body().as_ref()).unwrap();
+        let last = function_handler(
+            page(&[
+                ("limit", "1"),
+                ("cursor", first.next_cursor.as_deref().unwrap()),
+            ]),
+            &repository,
+        )
+        .await
+        .unwrap();
+        let last = serde_json::from_slice::<StrikesPage>(last.body().as_ref()).unwrap();
+        let invalid = function_handler(page(&[("limit", "0")]), &repository)
+            .await
+            .unwrap();
+
+        assert_eq!(first.strikes[0].name, "guenther");
+        assert_eq!(last.strikes[0].name, "heinz");
+        assert_eq!(last.next_cursor, None);
+        assert_eq!(invalid.status(), 400);
+    }
+
+    #[tokio::test]
+    async fn it_should_count_an_alias_before_the_cursor_on_a_later_page() {
+        let repository = repository().await;
+        repository
+            .increment_strikes(DEFAULT_BOARD, "anton", None)
+            .await
+            .unwrap();
+        repository
+            .put_alias(DEFAULT_BOARD, "anton", "heinz")
+            .await
+            .unwrap();
+        let request = Request::default().with_query_string_parameters(HashMap::from([
+            ("limit".to_string(), "1".to_string()),
+            ("cursor".to_string(), "guenther".to_string()),
+        ]));
+
+        let response = function_handler(request, &repository).await.unwrap();
+        let page = serde_json::from_slice::<StrikesPage>(response.body().as_ref()).unwrap();
+
+        assert_eq!(
+            page.strikes,
+            vec![StrikesResponse {
+                name: "heinz".to_string(),
+                strike_count: 3,
+                reasons: vec!["late".to_string()],
+            }]
+        );
+        assert_eq!(page.next_cursor, None);
+    }
+
+    #[tokio::test]
+    async fn it_should_render_the_tarnished_for_the_website() {
+        let repository = repository().await;
+        let mut request = Request::default();
+        request
+            .headers_mut()
+            .insert("accept", "text/html".parse().unwrap());
+
+        let response = function_handler(request, &repository).await.unwrap();
+
+        assert_eq!(response.headers()["Content-Type"], "text/html");
+        assert!(
+            matches!(response.body(), Body::Text(html) if html.contains("<li>guenther: 2</li>"))
+        );
+    }
+}
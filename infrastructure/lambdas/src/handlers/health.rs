@@ -0,0 +1,9 @@
+use lambda_http::{Body, Error, Request, Response};
+
+pub async fn function_handler(_request: Request) -> Result<Response<Body>, Error> {
+    Ok(Response::builder()
+        .status(200)
+        .header("Content-Type", "text/plain")
+        .body(Body::from("OK"))
+        .unwrap())
+}
@@ -0,0 +1,164 @@
+use crate::repository::StrikesRepository;
+use crate::strikes_db::{expire_after_days, resolve_alias, StrikeEventEntity, DEFAULT_BOARD};
+use chrono::{Duration, SecondsFormat};
+use lambda_http::{Body, Error, Request, RequestExt, Response};
+use strikes_core::{
+    name::Name,
+    wire::{ExportedTarnished, ImportResponse},
+};
+
+pub async fn function_handler(
+    request: Request,
+    repository: &dyn StrikesRepository,
+) -> Result<Response<Body>, Error> {
+    let params = request.path_parameters();
+    let Ok(board) = Name::parse(params.first("board").unwrap_or(DEFAULT_BOARD)) else {
+        return Ok(Response::builder()
+            .status(400)
+            .body(Body::Text("Invalid board".to_string()))
+            .expect("Failed to render response"));
+    };
+    let board = board.as_str();
+
+    let imports = match serde_json::from_slice::<Vec<ExportedTarnished>>(request.body().as_ref()) {
+        Ok(imports) => imports,
+        Err(_) => {
+            return Ok(Response::builder()
+                .status(400)
+                .body(Body::Text("Invalid request body".to_string()))
+                .expect("Failed to render response"))
+        }
+    };
+
+    let Ok(names) = imports
+        .iter()
+        .map(|import| Name::parse(&import.name))
+        .collect::<Result<Vec<_>, _>>()
+    else {
+        return Ok(Response::builder()
+            .status(400)
+            .body(Body::Text("Invalid username".to_string()))
+            .expect("Failed to render response"));
+    };
+
+    let expire_after_days = expire_after_days();
+    let aliases = repository.get_aliases(board).await?;
+
+    let mut imported = 0;
+    for (name, import) in names.into_iter().zip(imports) {
+        // Strikes exported under an alias are counted for the name it stands for.
+        let name = resolve_alias(&aliases, &name);
+        let mut reasons = vec![];
+        let mut count = 0;
+
+        // Strikes without a timestamp can't be told apart and have no event, so their count is
+        // authoritative: only as many are counted as the name has fewer of them than the import.
+        let counter = repository
+            .get_strikes(board)
+            .await?
+            .into_iter()
+            .find(|strike| strike.user_id == name)
+            .map_or(0, |strike| strike.strikes);
+        let events = repository.get_strike_events(board, name).await?.len() as u32;
+        let mut untimed = counter.saturating_sub(events);
+
+        for strike in import.strikes {
+            let Some(timestamp) = strike.timestamp else {
+                match untimed {
+                    0 => {
+                        reasons.extend(strike.reason);
+                        count += 1;
+                    }
+                    _ => untimed -= 1,
+                }
+                continue;
+            };
+            let event = StrikeEventEntity {
+                board: board.to_string(),
+                user_id: name.to_string(),
+                timestamp: timestamp.to_rfc3339_opts(SecondsFormat::Micros, true),
+                reason: strike.reason,
+                expires_at: expire_after_days
+                    .map(|days| (timestamp + Duration::days(days.into())).timestamp()),
+            };
+
+            if repository.import_strike_event(&event).await? {
+                reasons.extend(event.reason);
+                count += 1;
+            }
+        }
+
+        if count > 0 {
+            repository.add_strikes(board, name, &reasons, count).await?;
+            imported += count as usize;
+        }
+    }
+
+    Ok(Response::builder()
+        .status(200)
+        .body(Body::Text(
+            serde_json::to_string(&ImportResponse { imported }).unwrap(),
+        ))
+        .expect("Failed to render response"))
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use crate::repository::InMemoryRepository;
+
+    #[tokio::test]
+    async fn it_should_import_the_strikes_of_an_alias_for_its_name() {
+        let repository = InMemoryRepository::default();
+        repository
+            .put_alias(DEFAULT_BOARD, "guenni", "guenther")
+            .await
+            .unwrap();
+
+        let response = function_handler(
+            Request::new(Body::Text(
+                r#"[{"name": "guenni", "strikes": [{"reason": "late"}, {}]}]"#.to_string(),
+            )),
+            &repository,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(response.status(), 200);
+        let strikes = repository.get_strikes(DEFAULT_BOARD).await.unwrap();
+        assert_eq!(strikes.len(), 1);
+        assert_eq!(strikes[0].user_id, "guenther");
+        assert_eq!(strikes[0].strikes, 2);
+        assert_eq!(
+            repository
+                .get_strike_events(DEFAULT_BOARD, "guenther")
+                .await
+                .unwrap()
+                .len(),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn it_should_not_count_strikes_without_a_timestamp_twice() {
+        let repository = InMemoryRepository::default();
+        let import = || {
+            Request::new(Body::Text(
+                r#"[{"name": "guenther", "strikes": [{}, {"timestamp": "2024-09-01T08:30:00Z"}]}]"#
+                    .to_string(),
+            ))
+        };
+
+        function_handler(import(), &repository).await.unwrap();
+        let response = function_handler(import(), &repository).await.unwrap();
+
+        assert_eq!(
+            serde_json::from_slice::<ImportResponse>(response.body().as_ref()).unwrap(),
+            ImportResponse { imported: 0 }
+        );
+        assert_eq!(
+            repository.get_strikes(DEFAULT_BOARD).await.unwrap()[0].strikes,
+            2
+        );
+    }
+}
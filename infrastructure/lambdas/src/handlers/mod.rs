@@ -0,0 +1,13 @@
+// The HTTP lambdas, one module per function in main.tf. Their binaries and local-dev only wire
+// them up with a repository.
+pub mod aliases;
+pub mod delete_latest_strike;
+pub mod delete_strikes;
+pub mod delete_user_strikes;
+pub mod get_boards;
+pub mod get_strike_history;
+pub mod get_strikes;
+pub mod health;
+pub mod import_strikes;
+pub mod put_strike;
+pub mod website;
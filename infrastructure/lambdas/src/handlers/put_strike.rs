@@ -0,0 +1,163 @@
+use crate::repository::{count_strikes, StrikesRepository};
+use crate::strikes_db::{expire_after_days, resolve_alias, DEFAULT_BOARD};
+use lambda_http::{Body, Error, Request, RequestExt, Response};
+use strikes_core::{
+    name::Name,
+    wire::{StrikeCountResponse, StrikeRequest},
+};
+
+// Comma separated strike counts, e.g. "3,5". Three strikes are the default.
+fn thresholds() -> Vec<u32> {
+    std::env::var("STRIKE_THRESHOLDS")
+        .unwrap_or_else(|_| "3".to_string())
+        .split(',')
+        .filter_map(|threshold| threshold.trim().parse().ok())
+        .collect()
+}
+
+pub async fn function_handler(
+    request: Request,
+    repository: &dyn StrikesRepository,
+) -> Result<Response<Body>, Error> {
+    let params = request.path_parameters();
+    let user = params.first("user");
+    let board = params.first("board").unwrap_or(DEFAULT_BOARD);
+
+    match user {
+        Some(username) => {
+            let Ok(username) = Name::parse(username) else {
+                return Ok(Response::builder()
+                    .status(400)
+                    .body(Body::Text("Invalid username".to_string()))
+                    .expect("Failed to render response"));
+            };
+
+            let Ok(board) = Name::parse(board) else {
+                return Ok(Response::builder()
+                    .status(400)
+                    .body(Body::Text("Invalid board".to_string()))
+                    .expect("Failed to render response"));
+            };
+            let board = board.as_str();
+
+            let body = match request.body().as_ref() {
+                [] => StrikeRequest::default(),
+                raw => match serde_json::from_slice::<StrikeRequest>(raw) {
+                    Ok(body) => body,
+                    Err(_) => {
+                        return Ok(Response::builder()
+                            .status(400)
+                            .body(Body::Text("Invalid request body".to_string()))
+                            .expect("Failed to render response"))
+                    }
+                },
+            };
+
+            let aliases = repository.get_aliases(board).await?;
+            let username = resolve_alias(&aliases, &username);
+
+            let strike_count = repository
+                .record_strike(board, username, body.reason.as_deref(), expire_after_days())
+                .await?;
+            let strike_count =
+                count_strikes(repository, board, username, &aliases, strike_count).await?;
+            Ok(Response::builder()
+                .status(200)
+                .body(Body::Text(
+                    serde_json::to_string(&StrikeCountResponse {
+                        name: username.to_string(),
+                        strike_count,
+                        threshold_reached: Some(thresholds().contains(&strike_count)),
+                    })
+                    .unwrap(),
+                ))
+                .expect("Failed to render response"))
+        }
+        None => Ok(Response::builder()
+            .status(400)
+            .body(Body::Text("Missing user parameter".to_string()))
+            .expect("Failed to render response")),
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use crate::repository::InMemoryRepository;
+    use std::collections::HashMap;
+
+    fn put_strike(user: &str, body: &str) -> Request {
+        Request::new(Body::Text(body.to_string()))
+            .with_path_parameters(HashMap::from([("user".to_string(), user.to_string())]))
+    }
+
+    fn strike_count(response: Response<Body>) -> StrikeCountResponse {
+        serde_json::from_slice(response.body().as_ref()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn it_should_count_the_strikes_of_a_name() {
+        let repository = InMemoryRepository::default();
+
+        function_handler(put_strike("Guenther", ""), &repository)
+            .await
+            .unwrap();
+        let response =
+            function_handler(put_strike("guenther", r#"{"reason":"late"}"#), &repository)
+                .await
+                .unwrap();
+
+        assert_eq!(response.status(), 200);
+        assert_eq!(
+            strike_count(response),
+            StrikeCountResponse {
+                name: "guenther".to_string(),
+                strike_count: 2,
+                threshold_reached: Some(false),
+            }
+        );
+        assert_eq!(
+            repository.get_strikes(DEFAULT_BOARD).await.unwrap()[0].reasons,
+            vec!["late".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn it_should_give_the_strike_of_an_alias_to_its_name() {
+        let repository = InMemoryRepository::default();
+        repository
+            .put_alias(DEFAULT_BOARD, "guenni", "guenther")
+            .await
+            .unwrap();
+
+        let response = function_handler(put_strike("guenni", ""), &repository)
+            .await
+            .unwrap();
+
+        assert_eq!(strike_count(response).name, "guenther");
+        assert_eq!(
+            repository.get_strikes(DEFAULT_BOARD).await.unwrap()[0].user_id,
+            "guenther"
+        );
+    }
+
+    #[tokio::test]
+    async fn it_should_reject_an_invalid_request() {
+        let repository = InMemoryRepository::default();
+
+        let too_long = function_handler(put_strike("guentherguentherguenther", ""), &repository)
+            .await
+            .unwrap();
+        let invalid_body = function_handler(put_strike("guenther", "late"), &repository)
+            .await
+            .unwrap();
+
+        assert_eq!(too_long.status(), 400);
+        assert_eq!(invalid_body.status(), 400);
+        assert!(repository
+            .get_strikes(DEFAULT_BOARD)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
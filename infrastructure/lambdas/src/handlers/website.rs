@@ -0,0 +1,81 @@
+use aws_config::BehaviorVersion;
+use handlebars::Handlebars;
+use lambda_http::{Error, IntoResponse, Request, Response};
+use serde_json::json;
+use std::path::PathBuf;
+
+fn api_url(scheme: &str, id_var: &str) -> Option<String> {
+    std::env::var(id_var).ok().map(|id| {
+        format!(
+            "{}://{}.execute-api.eu-central-1.amazonaws.com/v1",
+            scheme, id
+        )
+    })
+}
+
+// What the page is rendered from and the APIs it talks to.
+pub struct Website {
+    pub rest_api_url: String,
+    pub websocket_api_url: Option<String>,
+    // local-dev renders the template from the repository instead of the deployed bucket.
+    pub template_path: Option<PathBuf>,
+}
+
+impl Website {
+    // The APIs created by main.tf, whose ids are handed to the lambda.
+    pub fn from_env() -> Self {
+        Website {
+            rest_api_url: api_url("https", "REST_API_ID").unwrap(),
+            websocket_api_url: api_url("wss", "WEBSOCKET_API_ID"),
+            template_path: None,
+        }
+    }
+
+    async fn read_template(&self) -> Result<String, Error> {
+        if let Some(path) = &self.template_path {
+            return Ok(std::fs::read_to_string(path)?);
+        }
+
+        let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+        let s3_client = aws_sdk_s3::Client::new(&config);
+
+        let s3_response = s3_client
+            .get_object()
+            .bucket("website-483220362587")
+            .key("index.html.hbs")
+            .send()
+            .await?;
+
+        let data = s3_response.body.collect().await?;
+
+        Ok(String::from_utf8(data.into_bytes().to_vec()).unwrap())
+    }
+}
+
+pub async fn function_handler(
+    _event: Request,
+    website: &Website,
+) -> Result<impl IntoResponse, Error> {
+    let template = website.read_template().await?;
+
+    let mut reg = Handlebars::new();
+    reg.register_template_string("index", template).unwrap();
+    let index = reg
+        .render(
+            "index",
+            &json!(
+                {
+                    "restApiUrl": website.rest_api_url,
+                    "websocketApiUrl": website.websocket_api_url,
+                }
+            ),
+        )
+        .unwrap();
+
+    let resp = Response::builder()
+        .status(200)
+        .header("content-type", "text/html")
+        .body(index)
+        .map_err(Box::new)?;
+    Ok(resp)
+}
@@ -1 +1,3 @@
+pub mod handlers;
+pub mod repository;
 pub mod strikes_db;
@@ -0,0 +1,216 @@
+use aws_config::BehaviorVersion;
+use aws_sdk_dynamodb::{
+    config::{Credentials, Region},
+    types::{AttributeDefinition, BillingMode, KeySchemaElement, KeyType, ScalarAttributeType},
+    Client,
+};
+use axum::{
+    extract::{Path, Query},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{delete, get, post, put},
+    Router,
+};
+use lambda_http::{Body, Error, RequestExt};
+use lib::handlers::{
+    aliases, delete_latest_strike, delete_strikes, delete_user_strikes, get_boards,
+    get_strike_history, get_strikes, health, import_strikes, put_strike,
+    website::{self, Website},
+};
+use lib::repository::{DynamoDbRepository, StrikesRepository};
+use std::{collections::HashMap, future::Future, path::PathBuf, sync::Arc};
+
+// Hands the request to a lambda handler the way API Gateway does, with the matched path
+// parameters and the parsed query string attached.
+async fn invoke<F, Fut, R>(
+    handler: F,
+    params: Option<Path<HashMap<String, String>>>,
+    query: HashMap<String, String>,
+    request: axum::extract::Request,
+) -> axum::response::Response
+where
+    F: FnOnce(lambda_http::Request) -> Fut,
+    Fut: Future<Output = Result<R, Error>>,
+    R: lambda_http::IntoResponse,
+{
+    let (parts, body) = request.into_parts();
+    let body = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(bytes) if bytes.is_empty() => Body::Empty,
+        Ok(bytes) => match String::from_utf8(bytes.to_vec()) {
+            Ok(text) => Body::Text(text),
+            Err(_) => Body::Binary(bytes.to_vec()),
+        },
+        Err(err) => return (StatusCode::BAD_REQUEST, err.to_string()).into_response(),
+    };
+    let request = lambda_http::Request::from_parts(parts, body)
+        .with_path_parameters(params.map(|Path(params)| params).unwrap_or_default())
+        .with_query_string_parameters(query);
+
+    match handler(request).await {
+        Ok(response) => {
+            let (parts, body) = response.into_response().await.into_parts();
+            let body = match body {
+                Body::Empty => axum::body::Body::empty(),
+                Body::Text(text) => text.into(),
+                Body::Binary(bytes) => bytes.into(),
+            };
+            axum::response::Response::from_parts(parts, body)
+        }
+        // API Gateway answers a failed invocation with a bad gateway as well.
+        Err(err) => {
+            eprintln!("{}", err);
+            (StatusCode::BAD_GATEWAY, "Internal server error").into_response()
+        }
+    }
+}
+
+// Wraps a handler for axum, along with the state it reads from if it takes any.
+macro_rules! lambda {
+    ($handler:path) => {
+        |params: Option<Path<HashMap<String, String>>>,
+         Query(query): Query<HashMap<String, String>>,
+         request: axum::extract::Request| async move {
+            invoke($handler, params, query, request).await
+        }
+    };
+    ($handler:path, $state:expr) => {{
+        let state = $state.clone();
+        move |params: Option<Path<HashMap<String, String>>>,
+              Query(query): Query<HashMap<String, String>>,
+              request: axum::extract::Request| {
+            let state = state.clone();
+            async move {
+                let handler = |request| async move { $handler(request, state.as_ref()).await };
+                invoke(handler, params, query, request).await
+            }
+        }
+    }};
+}
+
+// Mirrors the routes of the API Gateway in main.tf, without the API key check.
+fn router(repository: Arc<dyn StrikesRepository>, website: Arc<Website>) -> Router {
+    let strikes = Router::new()
+        .route(
+            "/strikes",
+            get(lambda!(get_strikes::function_handler, repository))
+                .delete(lambda!(delete_strikes::function_handler, repository)),
+        )
+        .route(
+            "/strikes/:user",
+            put(lambda!(put_strike::function_handler, repository))
+                .delete(lambda!(delete_user_strikes::function_handler, repository)),
+        )
+        .route(
+            "/strikes/:user/latest",
+            delete(lambda!(delete_latest_strike::function_handler, repository)),
+        )
+        .route(
+            "/strikes/:user/history",
+            get(lambda!(get_strike_history::function_handler, repository)),
+        );
+    let board = strikes
+        .clone()
+        .route(
+            "/strikes",
+            post(lambda!(import_strikes::function_handler, repository)),
+        )
+        .route(
+            "/aliases",
+            get(lambda!(aliases::function_handler, repository)),
+        )
+        .route(
+            "/aliases/:alias",
+            put(lambda!(aliases::function_handler, repository))
+                .delete(lambda!(aliases::function_handler, repository)),
+        )
+        .route(
+            "/aliases/:alias/merge",
+            post(lambda!(aliases::function_handler, repository)),
+        );
+
+    Router::new()
+        .route("/", get(lambda!(website::function_handler, website)))
+        .route("/health", get(lambda!(health::function_handler)))
+        .route(
+            "/boards",
+            get(lambda!(get_boards::function_handler, repository)),
+        )
+        .nest("/boards/:board", board)
+        .merge(strikes)
+}
+
+async fn create_table(client: &Client, name: &str, hash: &str, range: &str) -> Result<(), Error> {
+    let tables = client
+        .list_tables()
+        .into_paginator()
+        .items()
+        .send()
+        .try_collect()
+        .await?;
+    if tables.iter().any(|table| table == name) {
+        return Ok(());
+    }
+
+    let mut request = client
+        .create_table()
+        .table_name(name)
+        .billing_mode(BillingMode::PayPerRequest);
+    for (attribute, key_type) in [(hash, KeyType::Hash), (range, KeyType::Range)] {
+        request = request
+            .attribute_definitions(
+                AttributeDefinition::builder()
+                    .attribute_name(attribute)
+                    .attribute_type(ScalarAttributeType::S)
+                    .build()?,
+            )
+            .key_schema(
+                KeySchemaElement::builder()
+                    .attribute_name(attribute)
+                    .key_type(key_type)
+                    .build()?,
+            );
+    }
+    request.send().await?;
+
+    Ok(())
+}
+
+// Runs the lambdas on one HTTP server against the DynamoDB Local of tests/docker-compose.yml.
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    let address = std::env::var("LOCAL_DEV_ADDRESS").unwrap_or("127.0.0.1:3000".to_string());
+
+    let endpoint =
+        std::env::var("AWS_ENDPOINT_URL_DYNAMODB").unwrap_or("http://localhost:8000".to_string());
+
+    // DynamoDB Local accepts any credentials.
+    let config = aws_config::defaults(BehaviorVersion::latest())
+        .endpoint_url(endpoint)
+        .region(Region::new("eu-central-1"))
+        .credentials_provider(Credentials::new("local", "local", None, None, "local-dev"))
+        .load()
+        .await;
+    let repository = DynamoDbRepository::new(Client::new(&config));
+    for (table, hash, range) in [
+        (&repository.strikes_table, "Board", "UserId"),
+        (&repository.events_table, "BoardUserId", "Timestamp"),
+        (&repository.aliases_table, "Board", "Alias"),
+    ] {
+        create_table(&repository.client, table, hash, range).await?;
+    }
+    let website = Website {
+        rest_api_url: format!("http://{}", address),
+        websocket_api_url: None,
+        template_path: Some(PathBuf::from(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../website/templates/index.html.hbs"
+        ))),
+    };
+
+    let listener = tokio::net::TcpListener::bind(&address).await?;
+    println!("Serving the lambdas on http://{}", address);
+
+    axum::serve(listener, router(Arc::new(repository), Arc::new(website))).await?;
+
+    Ok(())
+}
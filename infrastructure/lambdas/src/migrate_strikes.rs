@@ -0,0 +1,25 @@
+use lambda_http::Error;
+use lib::repository::DynamoDbRepository;
+use lib::strikes_db::copy_legacy_strikes;
+
+const LEGACY_STRIKES_TABLE: &str = "Strikes";
+
+// Run once with the credentials of the AWS account after `terraform apply` created the
+// BoardStrikes table, the strikes recorded before boards existed end up on the default board.
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    let repository = DynamoDbRepository::from_env().await;
+    let copied = copy_legacy_strikes(
+        LEGACY_STRIKES_TABLE,
+        &repository.strikes_table,
+        &repository.client,
+    )
+    .await?;
+
+    println!(
+        "Copied {} strikes from {} to {}",
+        copied, LEGACY_STRIKES_TABLE, repository.strikes_table
+    );
+
+    Ok(())
+}
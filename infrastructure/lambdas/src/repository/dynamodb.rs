@@ -0,0 +1,230 @@
+use super::StrikesRepository;
+use crate::strikes_db::{self, DeleteReport, StrikeEntity, StrikeEventEntity};
+use async_trait::async_trait;
+use aws_config::BehaviorVersion;
+use aws_sdk_dynamodb::Client;
+use lambda_http::Error;
+use std::collections::BTreeMap;
+
+pub struct DynamoDbRepository {
+    pub client: Client,
+    pub strikes_table: String,
+    pub events_table: String,
+    pub aliases_table: String,
+    pub connections_table: String,
+}
+
+impl DynamoDbRepository {
+    pub fn new(client: Client) -> Self {
+        DynamoDbRepository {
+            client,
+            strikes_table: "BoardStrikes".to_string(),
+            events_table: "StrikeEvents".to_string(),
+            aliases_table: "Aliases".to_string(),
+            connections_table: "Connections".to_string(),
+        }
+    }
+
+    // The tables created by main.tf, reached with the credentials of the lambda.
+    pub async fn from_env() -> Self {
+        let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+        DynamoDbRepository::new(Client::new(&config))
+    }
+}
+
+#[async_trait]
+impl StrikesRepository for DynamoDbRepository {
+    async fn increment_strikes(
+        &self,
+        board: &str,
+        username: &str,
+        reason: Option<&str>,
+    ) -> Result<u32, Error> {
+        strikes_db::increment_strikes(board, username, reason, &self.strikes_table, &self.client)
+            .await
+    }
+
+    async fn record_strike(
+        &self,
+        board: &str,
+        username: &str,
+        reason: Option<&str>,
+        expire_after_days: Option<u32>,
+    ) -> Result<u32, Error> {
+        strikes_db::record_strike(
+            board,
+            username,
+            reason,
+            expire_after_days,
+            &self.strikes_table,
+            &self.events_table,
+            &self.client,
+        )
+        .await
+    }
+
+    async fn remove_strike(&self, board: &str, username: &str) -> Result<u32, Error> {
+        strikes_db::remove_strike(
+            board,
+            username,
+            &self.strikes_table,
+            &self.events_table,
+            &self.client,
+        )
+        .await
+    }
+
+    async fn decrement_strikes(&self, board: &str, username: &str) -> Result<u32, Error> {
+        strikes_db::decrement_strikes(board, username, &self.strikes_table, &self.client).await
+    }
+
+    async fn add_strikes(
+        &self,
+        board: &str,
+        username: &str,
+        reasons: &[String],
+        count: u32,
+    ) -> Result<u32, Error> {
+        strikes_db::add_strikes(
+            board,
+            username,
+            reasons,
+            count,
+            &self.strikes_table,
+            &self.client,
+        )
+        .await
+    }
+
+    async fn get_strikes(&self, board: &str) -> Result<Vec<StrikeEntity>, Error> {
+        strikes_db::get_strikes(board, &self.strikes_table, &self.client).await
+    }
+
+    async fn get_strikes_after(
+        &self,
+        board: &str,
+        after: Option<&str>,
+        limit: Option<usize>,
+    ) -> Result<Vec<StrikeEntity>, Error> {
+        strikes_db::get_strikes_after(board, after, limit, &self.strikes_table, &self.client).await
+    }
+
+    async fn get_strike(&self, board: &str, username: &str) -> Result<Option<StrikeEntity>, Error> {
+        strikes_db::get_strike(board, username, &self.strikes_table, &self.client).await
+    }
+
+    async fn get_all_strikes(&self) -> Result<Vec<StrikeEntity>, Error> {
+        strikes_db::get_all_strikes(&self.strikes_table, &self.client).await
+    }
+
+    async fn delete_strikes(&self, board: &str, username: &str) -> Result<(), Error> {
+        strikes_db::delete_strikes(board, username, &self.strikes_table, &self.client).await
+    }
+
+    async fn delete_all_strikes(&self, board: &str) -> Result<DeleteReport, Error> {
+        strikes_db::delete_all_strikes(board, &self.strikes_table, &self.client).await
+    }
+
+    async fn add_strike_event(
+        &self,
+        board: &str,
+        username: &str,
+        reason: Option<&str>,
+        expire_after_days: Option<u32>,
+    ) -> Result<StrikeEventEntity, Error> {
+        strikes_db::add_strike_event(
+            board,
+            username,
+            reason,
+            expire_after_days,
+            &self.events_table,
+            &self.client,
+        )
+        .await
+    }
+
+    async fn import_strike_event(&self, event: &StrikeEventEntity) -> Result<bool, Error> {
+        strikes_db::import_strike_event(event, &self.events_table, &self.client).await
+    }
+
+    async fn get_strike_events(
+        &self,
+        board: &str,
+        username: &str,
+    ) -> Result<Vec<StrikeEventEntity>, Error> {
+        strikes_db::get_strike_events(board, username, &self.events_table, &self.client).await
+    }
+
+    async fn count_active_strike_events(
+        &self,
+        board: &str,
+        username: &str,
+        now: i64,
+    ) -> Result<u32, Error> {
+        strikes_db::count_active_strike_events(
+            board,
+            username,
+            now,
+            &self.events_table,
+            &self.client,
+        )
+        .await
+    }
+
+    async fn get_active_strikes(
+        &self,
+        board: Option<&str>,
+        now: i64,
+    ) -> Result<Vec<StrikeEntity>, Error> {
+        strikes_db::get_active_strikes(board, now, &self.events_table, &self.client).await
+    }
+
+    async fn delete_latest_strike_event(&self, board: &str, username: &str) -> Result<(), Error> {
+        strikes_db::delete_latest_strike_event(board, username, &self.events_table, &self.client)
+            .await
+    }
+
+    async fn delete_strike_events(&self, board: &str, username: &str) -> Result<(), Error> {
+        strikes_db::delete_strike_events(board, username, &self.events_table, &self.client).await
+    }
+
+    async fn delete_all_strike_events(&self, board: &str) -> Result<DeleteReport, Error> {
+        strikes_db::delete_all_strike_events(board, &self.events_table, &self.client).await
+    }
+
+    async fn get_aliases(&self, board: &str) -> Result<BTreeMap<String, String>, Error> {
+        strikes_db::get_aliases(board, &self.aliases_table, &self.client).await
+    }
+
+    async fn put_alias(&self, board: &str, alias: &str, name: &str) -> Result<(), Error> {
+        strikes_db::put_alias(board, alias, name, &self.aliases_table, &self.client).await
+    }
+
+    async fn delete_alias(&self, board: &str, alias: &str) -> Result<bool, Error> {
+        strikes_db::delete_alias(board, alias, &self.aliases_table, &self.client).await
+    }
+
+    async fn merge_strikes(&self, board: &str, alias: &str, username: &str) -> Result<u32, Error> {
+        strikes_db::merge_strikes(
+            board,
+            alias,
+            username,
+            &self.strikes_table,
+            &self.events_table,
+            &self.client,
+        )
+        .await
+    }
+
+    async fn add_connection(&self, connection_id: &str) -> Result<(), Error> {
+        strikes_db::add_connection(connection_id, &self.connections_table, &self.client).await
+    }
+
+    async fn delete_connection(&self, connection_id: &str) -> Result<(), Error> {
+        strikes_db::delete_connection(connection_id, &self.connections_table, &self.client).await
+    }
+
+    async fn get_connection_ids(&self) -> Result<Vec<String>, Error> {
+        strikes_db::get_connection_ids(&self.connections_table, &self.client).await
+    }
+}
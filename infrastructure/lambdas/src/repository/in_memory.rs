@@ -0,0 +1,510 @@
+use super::StrikesRepository;
+use crate::strikes_db::{
+    drops_reason, is_active, new_strike_event, strikes_from_events, DeleteReport, StrikeEntity,
+    StrikeEventEntity,
+};
+use async_trait::async_trait;
+use lambda_http::Error;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::{Mutex, MutexGuard};
+
+// Keyed like the tables, so lists come out in the order DynamoDB returns them.
+#[derive(Default)]
+struct State {
+    strikes: BTreeMap<(String, String), StrikeEntity>,
+    events: BTreeMap<(String, String, String), StrikeEventEntity>,
+    aliases: BTreeMap<(String, String), String>,
+    connections: BTreeSet<String>,
+}
+
+#[derive(Default)]
+pub struct InMemoryRepository {
+    state: Mutex<State>,
+}
+
+impl InMemoryRepository {
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().expect("Repository lock poisoned")
+    }
+}
+
+fn event_key(event: &StrikeEventEntity) -> (String, String, String) {
+    (
+        event.board.clone(),
+        event.user_id.clone(),
+        event.timestamp.clone(),
+    )
+}
+
+fn add_strikes(
+    state: &mut State,
+    board: &str,
+    username: &str,
+    reasons: &[String],
+    count: u32,
+) -> u32 {
+    let strike = state
+        .strikes
+        .entry((board.to_string(), username.to_string()))
+        .or_insert_with(|| StrikeEntity {
+            board: board.to_string(),
+            user_id: username.to_string(),
+            strikes: 0,
+            reasons: vec![],
+        });
+    strike.strikes += count;
+    strike.reasons.extend_from_slice(reasons);
+    strike.strikes
+}
+
+#[async_trait]
+impl StrikesRepository for InMemoryRepository {
+    async fn increment_strikes(
+        &self,
+        board: &str,
+        username: &str,
+        reason: Option<&str>,
+    ) -> Result<u32, Error> {
+        let reasons = reason.map(str::to_string).into_iter().collect::<Vec<_>>();
+        Ok(add_strikes(&mut self.state(), board, username, &reasons, 1))
+    }
+
+    async fn record_strike(
+        &self,
+        board: &str,
+        username: &str,
+        reason: Option<&str>,
+        expire_after_days: Option<u32>,
+    ) -> Result<u32, Error> {
+        let mut state = self.state();
+        let event = new_strike_event(board, username, reason, expire_after_days);
+        state.events.insert(event_key(&event), event);
+        let reasons = reason.map(str::to_string).into_iter().collect::<Vec<_>>();
+
+        Ok(add_strikes(&mut state, board, username, &reasons, 1))
+    }
+
+    async fn remove_strike(&self, board: &str, username: &str) -> Result<u32, Error> {
+        let mut state = self.state();
+        let key = (board.to_string(), username.to_string());
+        let latest = state
+            .events
+            .values()
+            .rfind(|event| event.board == board && event.user_id == username)
+            .cloned();
+        let Some(strike) = state
+            .strikes
+            .get_mut(&key)
+            .filter(|strike| strike.strikes > 0)
+        else {
+            return Ok(0);
+        };
+
+        if drops_reason(strike, latest.as_ref()) {
+            strike.reasons.pop();
+        }
+        strike.strikes -= 1;
+        let strike_count = strike.strikes;
+        if strike_count == 0 {
+            state.strikes.remove(&key);
+        }
+        if let Some(latest) = latest {
+            state.events.remove(&event_key(&latest));
+        }
+
+        Ok(strike_count)
+    }
+
+    async fn decrement_strikes(&self, board: &str, username: &str) -> Result<u32, Error> {
+        let mut state = self.state();
+        let key = (board.to_string(), username.to_string());
+        let Some(strike) = state
+            .strikes
+            .get_mut(&key)
+            .filter(|strike| strike.strikes > 0)
+        else {
+            return Ok(0);
+        };
+
+        strike.strikes -= 1;
+        let strike_count = strike.strikes;
+        if strike_count == 0 {
+            state.strikes.remove(&key);
+        }
+
+        Ok(strike_count)
+    }
+
+    async fn add_strikes(
+        &self,
+        board: &str,
+        username: &str,
+        reasons: &[String],
+        count: u32,
+    ) -> Result<u32, Error> {
+        Ok(add_strikes(
+            &mut self.state(),
+            board,
+            username,
+            reasons,
+            count,
+        ))
+    }
+
+    async fn get_strikes(&self, board: &str) -> Result<Vec<StrikeEntity>, Error> {
+        self.get_strikes_after(board, None, None).await
+    }
+
+    async fn get_strikes_after(
+        &self,
+        board: &str,
+        after: Option<&str>,
+        limit: Option<usize>,
+    ) -> Result<Vec<StrikeEntity>, Error> {
+        Ok(self
+            .state()
+            .strikes
+            .values()
+            .filter(|strike| strike.board == board)
+            .filter(|strike| after.is_none_or(|after| strike.user_id.as_str() > after))
+            .take(limit.unwrap_or(usize::MAX))
+            .cloned()
+            .collect())
+    }
+
+    async fn get_strike(&self, board: &str, username: &str) -> Result<Option<StrikeEntity>, Error> {
+        Ok(self
+            .state()
+            .strikes
+            .get(&(board.to_string(), username.to_string()))
+            .cloned())
+    }
+
+    async fn get_all_strikes(&self) -> Result<Vec<StrikeEntity>, Error> {
+        Ok(self.state().strikes.values().cloned().collect())
+    }
+
+    async fn delete_strikes(&self, board: &str, username: &str) -> Result<(), Error> {
+        self.state()
+            .strikes
+            .remove(&(board.to_string(), username.to_string()));
+
+        Ok(())
+    }
+
+    async fn delete_all_strikes(&self, board: &str) -> Result<DeleteReport, Error> {
+        let mut state = self.state();
+        let before = state.strikes.len();
+        state
+            .strikes
+            .retain(|(strike_board, _), _| strike_board != board);
+
+        Ok(DeleteReport {
+            removed: (before - state.strikes.len()) as u32,
+            failed: 0,
+        })
+    }
+
+    async fn add_strike_event(
+        &self,
+        board: &str,
+        username: &str,
+        reason: Option<&str>,
+        expire_after_days: Option<u32>,
+    ) -> Result<StrikeEventEntity, Error> {
+        let event = new_strike_event(board, username, reason, expire_after_days);
+        self.state().events.insert(event_key(&event), event.clone());
+
+        Ok(event)
+    }
+
+    async fn import_strike_event(&self, event: &StrikeEventEntity) -> Result<bool, Error> {
+        let mut state = self.state();
+        if state.events.contains_key(&event_key(event)) {
+            return Ok(false);
+        }
+        state.events.insert(event_key(event), event.clone());
+
+        Ok(true)
+    }
+
+    async fn get_strike_events(
+        &self,
+        board: &str,
+        username: &str,
+    ) -> Result<Vec<StrikeEventEntity>, Error> {
+        Ok(self
+            .state()
+            .events
+            .values()
+            .filter(|event| event.board == board && event.user_id == username)
+            .cloned()
+            .collect())
+    }
+
+    async fn count_active_strike_events(
+        &self,
+        board: &str,
+        username: &str,
+        now: i64,
+    ) -> Result<u32, Error> {
+        Ok(self
+            .get_strike_events(board, username)
+            .await?
+            .iter()
+            .filter(|event| is_active(event, now))
+            .count() as u32)
+    }
+
+    async fn get_active_strikes(
+        &self,
+        board: Option<&str>,
+        now: i64,
+    ) -> Result<Vec<StrikeEntity>, Error> {
+        let events = self
+            .state()
+            .events
+            .values()
+            .filter(|event| board.is_none_or(|board| event.board == board))
+            .filter(|event| is_active(event, now))
+            .cloned()
+            .collect();
+
+        Ok(strikes_from_events(events))
+    }
+
+    async fn delete_latest_strike_event(&self, board: &str, username: &str) -> Result<(), Error> {
+        let mut state = self.state();
+        let latest = state
+            .events
+            .values()
+            .rfind(|event| event.board == board && event.user_id == username)
+            .map(event_key);
+        if let Some(key) = latest {
+            state.events.remove(&key);
+        }
+
+        Ok(())
+    }
+
+    async fn delete_strike_events(&self, board: &str, username: &str) -> Result<(), Error> {
+        self.state()
+            .events
+            .retain(|_, event| event.board != board || event.user_id != username);
+
+        Ok(())
+    }
+
+    async fn delete_all_strike_events(&self, board: &str) -> Result<DeleteReport, Error> {
+        let mut state = self.state();
+        let before = state.events.len();
+        state.events.retain(|_, event| event.board != board);
+
+        Ok(DeleteReport {
+            removed: (before - state.events.len()) as u32,
+            failed: 0,
+        })
+    }
+
+    async fn get_aliases(&self, board: &str) -> Result<BTreeMap<String, String>, Error> {
+        Ok(self
+            .state()
+            .aliases
+            .iter()
+            .filter(|((alias_board, _), _)| alias_board == board)
+            .map(|((_, alias), name)| (alias.clone(), name.clone()))
+            .collect())
+    }
+
+    async fn put_alias(&self, board: &str, alias: &str, name: &str) -> Result<(), Error> {
+        self.state()
+            .aliases
+            .insert((board.to_string(), alias.to_string()), name.to_string());
+
+        Ok(())
+    }
+
+    async fn delete_alias(&self, board: &str, alias: &str) -> Result<bool, Error> {
+        Ok(self
+            .state()
+            .aliases
+            .remove(&(board.to_string(), alias.to_string()))
+            .is_some())
+    }
+
+    async fn merge_strikes(&self, board: &str, alias: &str, username: &str) -> Result<u32, Error> {
+        let mut state = self.state();
+
+        let events = state
+            .events
+            .values()
+            .filter(|event| event.board == board && event.user_id == alias)
+            .cloned()
+            .collect::<Vec<_>>();
+        for event in &events {
+            state.events.remove(&event_key(event));
+            let moved = StrikeEventEntity {
+                user_id: username.to_string(),
+                ..event.clone()
+            };
+            state.events.entry(event_key(&moved)).or_insert(moved);
+        }
+
+        let Some(strike) = state
+            .strikes
+            .remove(&(board.to_string(), alias.to_string()))
+        else {
+            return Ok(events.len() as u32);
+        };
+        add_strikes(&mut state, board, username, &strike.reasons, strike.strikes);
+
+        Ok(strike.strikes)
+    }
+
+    async fn add_connection(&self, connection_id: &str) -> Result<(), Error> {
+        self.state().connections.insert(connection_id.to_string());
+
+        Ok(())
+    }
+
+    async fn delete_connection(&self, connection_id: &str) -> Result<(), Error> {
+        self.state().connections.remove(connection_id);
+
+        Ok(())
+    }
+
+    async fn get_connection_ids(&self) -> Result<Vec<String>, Error> {
+        Ok(self.state().connections.iter().cloned().collect())
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn it_should_remove_the_counter_with_the_last_strike() {
+        let repository = InMemoryRepository::default();
+        repository
+            .increment_strikes("default", "guenther", Some("late"))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            repository
+                .decrement_strikes("default", "guenther")
+                .await
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            repository
+                .decrement_strikes("default", "guenther")
+                .await
+                .unwrap(),
+            0
+        );
+        assert!(repository.get_strikes("default").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn it_should_take_back_the_reason_of_the_latest_strike() {
+        let repository = InMemoryRepository::default();
+        repository
+            .record_strike("default", "guenther", Some("late"), None)
+            .await
+            .unwrap();
+        repository
+            .record_strike("default", "guenther", None, None)
+            .await
+            .unwrap();
+        repository
+            .record_strike("default", "guenther", Some("typo"), None)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            repository
+                .remove_strike("default", "guenther")
+                .await
+                .unwrap(),
+            2
+        );
+        assert_eq!(
+            repository
+                .remove_strike("default", "guenther")
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            repository.get_strikes("default").await.unwrap()[0].reasons,
+            vec!["late".to_string()]
+        );
+        assert_eq!(
+            repository
+                .get_strike_events("default", "guenther")
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn it_should_import_an_event_only_once() {
+        let repository = InMemoryRepository::default();
+        let event = new_strike_event("default", "guenther", None, None);
+
+        assert!(repository.import_strike_event(&event).await.unwrap());
+        assert!(!repository.import_strike_event(&event).await.unwrap());
+        assert_eq!(
+            repository
+                .count_active_strike_events("default", "guenther", 0)
+                .await
+                .unwrap(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn it_should_merge_the_strikes_of_an_alias() {
+        let repository = InMemoryRepository::default();
+        repository
+            .increment_strikes("default", "guenni", Some("late"))
+            .await
+            .unwrap();
+        repository
+            .add_strike_event("default", "guenni", Some("late"), None)
+            .await
+            .unwrap();
+        repository
+            .increment_strikes("default", "guenther", None)
+            .await
+            .unwrap();
+
+        let merged = repository
+            .merge_strikes("default", "guenni", "guenther")
+            .await
+            .unwrap();
+
+        assert_eq!(merged, 1);
+        assert_eq!(
+            repository.get_strikes("default").await.unwrap(),
+            vec![StrikeEntity {
+                board: "default".to_string(),
+                user_id: "guenther".to_string(),
+                strikes: 2,
+                reasons: vec!["late".to_string()],
+            }]
+        );
+        assert_eq!(
+            repository
+                .get_strike_events("default", "guenther")
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+}
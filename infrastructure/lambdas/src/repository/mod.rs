@@ -0,0 +1,130 @@
+pub mod dynamodb;
+pub mod in_memory;
+
+use crate::strikes_db::{
+    expire_after_days, fold_aliases, DeleteReport, StrikeEntity, StrikeEventEntity,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use lambda_http::Error;
+use std::collections::BTreeMap;
+
+pub use dynamodb::DynamoDbRepository;
+pub use in_memory::InMemoryRepository;
+
+// Everything the handlers read and write, so they can run against DynamoDB in a lambda and
+// against memory in their tests.
+#[async_trait]
+pub trait StrikesRepository: Send + Sync {
+    async fn increment_strikes(
+        &self,
+        board: &str,
+        username: &str,
+        reason: Option<&str>,
+    ) -> Result<u32, Error>;
+    // Counts the strike and stores its event as one write, returning the counter afterwards.
+    async fn record_strike(
+        &self,
+        board: &str,
+        username: &str,
+        reason: Option<&str>,
+        expire_after_days: Option<u32>,
+    ) -> Result<u32, Error>;
+    // Lowers the counter, drops the reason of the latest strike and deletes its event as one
+    // write, returning the counter afterwards. Answers zero for unknown names.
+    async fn remove_strike(&self, board: &str, username: &str) -> Result<u32, Error>;
+    // Removes the counter once it drops to zero and answers zero for unknown names.
+    async fn decrement_strikes(&self, board: &str, username: &str) -> Result<u32, Error>;
+    async fn add_strikes(
+        &self,
+        board: &str,
+        username: &str,
+        reasons: &[String],
+        count: u32,
+    ) -> Result<u32, Error>;
+    // Ordered by name, like the sort key of the BoardStrikes table.
+    async fn get_strikes(&self, board: &str) -> Result<Vec<StrikeEntity>, Error>;
+    async fn get_strikes_after(
+        &self,
+        board: &str,
+        after: Option<&str>,
+        limit: Option<usize>,
+    ) -> Result<Vec<StrikeEntity>, Error>;
+    async fn get_strike(&self, board: &str, username: &str) -> Result<Option<StrikeEntity>, Error>;
+    async fn get_all_strikes(&self) -> Result<Vec<StrikeEntity>, Error>;
+    async fn delete_strikes(&self, board: &str, username: &str) -> Result<(), Error>;
+    async fn delete_all_strikes(&self, board: &str) -> Result<DeleteReport, Error>;
+
+    async fn add_strike_event(
+        &self,
+        board: &str,
+        username: &str,
+        reason: Option<&str>,
+        expire_after_days: Option<u32>,
+    ) -> Result<StrikeEventEntity, Error>;
+    // Answers false when an event with the same timestamp is already stored.
+    async fn import_strike_event(&self, event: &StrikeEventEntity) -> Result<bool, Error>;
+    async fn get_strike_events(
+        &self,
+        board: &str,
+        username: &str,
+    ) -> Result<Vec<StrikeEventEntity>, Error>;
+    async fn count_active_strike_events(
+        &self,
+        board: &str,
+        username: &str,
+        now: i64,
+    ) -> Result<u32, Error>;
+    async fn get_active_strikes(
+        &self,
+        board: Option<&str>,
+        now: i64,
+    ) -> Result<Vec<StrikeEntity>, Error>;
+    async fn delete_latest_strike_event(&self, board: &str, username: &str) -> Result<(), Error>;
+    async fn delete_strike_events(&self, board: &str, username: &str) -> Result<(), Error>;
+    async fn delete_all_strike_events(&self, board: &str) -> Result<DeleteReport, Error>;
+
+    async fn get_aliases(&self, board: &str) -> Result<BTreeMap<String, String>, Error>;
+    async fn put_alias(&self, board: &str, alias: &str, name: &str) -> Result<(), Error>;
+    async fn delete_alias(&self, board: &str, alias: &str) -> Result<bool, Error>;
+    // Moves the counter and the events of an alias over to its name, returning how many
+    // strikes were moved.
+    async fn merge_strikes(&self, board: &str, alias: &str, username: &str) -> Result<u32, Error>;
+
+    // The websocket connections that get told about every change of the strikes.
+    async fn add_connection(&self, connection_id: &str) -> Result<(), Error>;
+    async fn delete_connection(&self, connection_id: &str) -> Result<(), Error>;
+    async fn get_connection_ids(&self) -> Result<Vec<String>, Error>;
+}
+
+// The count a handler answers with after changing the strikes of a name. Strikes still recorded
+// under an alias count as well, like in the list, and with decay only the active ones count.
+pub async fn count_strikes(
+    repository: &dyn StrikesRepository,
+    board: &str,
+    username: &str,
+    aliases: &BTreeMap<String, String>,
+    counter: u32,
+) -> Result<u32, Error> {
+    let has_aliases = aliases.values().any(|name| name == username);
+    let now = Utc::now().timestamp();
+
+    Ok(match expire_after_days() {
+        _ if has_aliases => {
+            let strikes = match expire_after_days() {
+                Some(_) => repository.get_active_strikes(Some(board), now).await?,
+                None => repository.get_strikes(board).await?,
+            };
+            fold_aliases(strikes, aliases)
+                .into_iter()
+                .find(|strike| strike.user_id == username)
+                .map_or(0, |strike| strike.strikes)
+        }
+        Some(_) => {
+            repository
+                .count_active_strike_events(board, username, now)
+                .await?
+        }
+        None => counter,
+    })
+}
@@ -1,65 +1,261 @@
 use aws_sdk_dynamodb::{
     error::ProvideErrorMetadata,
-    operation::scan::ScanOutput,
-    types::{AttributeValue, ReturnValue},
+    types::{
+        AttributeValue, Delete, DeleteRequest, Put, ReturnValue, TransactWriteItem, Update,
+        WriteRequest,
+    },
     Client,
 };
-use lambda_http::Error;
-use std::collections::HashMap;
+use chrono::{Duration, SecondsFormat, Utc};
+use lambda_http::{tracing, Error};
+use std::collections::{BTreeMap, HashMap};
+use strikes_core::tarnished::Tarnished;
+use strikes_core::wire::{BoardStrikesResponse, StrikesResponse};
+use tokio::task::JoinSet;
+
+pub const DEFAULT_BOARD: &str = "default";
+pub const STRIKE_EXPIRY_DAYS: &str = "STRIKE_EXPIRY_DAYS";
+
+// The most keys a single BatchWriteItem takes.
+const BATCH_SIZE: usize = 25;
+const BATCH_ATTEMPTS: u32 = 5;
+const TRANSACTION_ATTEMPTS: u32 = 3;
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct StrikeEntity {
+    pub board: String,
     pub user_id: String,
-    pub strikes: u8,
+    pub strikes: u32,
+    pub reasons: Vec<String>,
 }
 
-pub async fn delete_all_strikes(table_name: &str, client: &Client) -> Result<(), Error> {
-    let strikes = get_strikes(table_name, client).await?;
+impl From<StrikeEntity> for Tarnished {
+    fn from(strike: StrikeEntity) -> Self {
+        Tarnished {
+            name: strike.user_id,
+            strikes: strike.strikes,
+            reasons: strike.reasons,
+        }
+    }
+}
 
-    for strike in strikes {
-        client
-            .delete_item()
-            .table_name(table_name)
-            .key("UserId", AttributeValue::S(strike.user_id))
-            .send()
-            .await?;
+impl From<StrikeEntity> for BoardStrikesResponse {
+    fn from(strike: StrikeEntity) -> Self {
+        BoardStrikesResponse {
+            board: strike.board.clone(),
+            strikes: StrikesResponse::from(Tarnished::from(strike)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct StrikeEventEntity {
+    pub board: String,
+    pub user_id: String,
+    pub timestamp: String,
+    pub reason: Option<String>,
+    pub expires_at: Option<i64>,
+}
+
+// Entries that are still there after every retry count as failed, the rest as removed.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct DeleteReport {
+    pub removed: u32,
+    pub failed: u32,
+}
+
+impl std::ops::Add for DeleteReport {
+    type Output = DeleteReport;
+
+    fn add(self, other: DeleteReport) -> DeleteReport {
+        DeleteReport {
+            removed: self.removed + other.removed,
+            failed: self.failed + other.failed,
+        }
     }
+}
+
+// Strikes only decay when the lambdas are deployed with a positive expiry period.
+pub fn expire_after_days() -> Option<u32> {
+    std::env::var(STRIKE_EXPIRY_DAYS)
+        .ok()
+        .and_then(|days| days.parse().ok())
+        .filter(|days| *days > 0)
+}
+
+pub async fn delete_all_strikes(
+    board: &str,
+    table_name: &str,
+    client: &Client,
+) -> Result<DeleteReport, Error> {
+    let keys = get_strikes(board, table_name, client)
+        .await?
+        .into_iter()
+        .map(|strike| {
+            HashMap::from([
+                ("Board".to_string(), AttributeValue::S(strike.board)),
+                ("UserId".to_string(), AttributeValue::S(strike.user_id)),
+            ])
+        })
+        .collect();
+
+    delete_in_batches(keys, table_name, client).await
+}
+
+pub async fn delete_strikes(
+    board: &str,
+    username: &str,
+    table_name: &str,
+    client: &Client,
+) -> Result<(), Error> {
+    client
+        .delete_item()
+        .table_name(table_name)
+        .key("Board", AttributeValue::S(board.to_string()))
+        .key("UserId", AttributeValue::S(username.to_string()))
+        .send()
+        .await?;
 
     Ok(())
 }
 
-pub async fn get_strikes(table_name: &str, client: &Client) -> Result<Vec<StrikeEntity>, Error> {
-    let request: ScanOutput = client.scan().table_name(table_name).send().await?;
+pub async fn get_strikes(
+    board: &str,
+    table_name: &str,
+    client: &Client,
+) -> Result<Vec<StrikeEntity>, Error> {
+    get_strikes_after(board, None, None, table_name, client).await
+}
 
-    request
+// Starts reading after the given name, so a page of GET /strikes skips what was already read,
+// and stops reading once the limit is reached.
+pub async fn get_strikes_after(
+    board: &str,
+    after: Option<&str>,
+    limit: Option<usize>,
+    table_name: &str,
+    client: &Client,
+) -> Result<Vec<StrikeEntity>, Error> {
+    let start_key = after.map(|after| {
+        HashMap::from([
+            ("Board".to_string(), AttributeValue::S(board.to_string())),
+            ("UserId".to_string(), AttributeValue::S(after.to_string())),
+        ])
+    });
+    let mut items = client
+        .query()
+        .table_name(table_name)
+        .key_condition_expression("Board = :board")
+        .expression_attribute_values(":board", AttributeValue::S(board.to_string()))
+        .set_exclusive_start_key(start_key)
+        .set_limit(limit.map(|limit| limit as i32))
+        .into_paginator()
         .items()
-        .iter()
-        .map(|item| {
-            let user_id = item.get("UserId").unwrap().as_s().unwrap().to_string();
-            let strikes = item
-                .get("Strikes")
-                .unwrap()
-                .as_n()
-                .unwrap()
-                .parse()
-                .unwrap();
+        .send();
 
-            Ok(StrikeEntity { user_id, strikes })
-        })
-        .collect()
+    let mut strikes = vec![];
+    while let Some(item) = items.next().await {
+        strikes.push(extract_strike(&item?));
+        if limit.is_some_and(|limit| strikes.len() >= limit) {
+            break;
+        }
+    }
+
+    Ok(strikes)
+}
+
+pub async fn get_strike(
+    board: &str,
+    username: &str,
+    table_name: &str,
+    client: &Client,
+) -> Result<Option<StrikeEntity>, Error> {
+    let strike = client
+        .get_item()
+        .table_name(table_name)
+        .key("Board", AttributeValue::S(board.to_string()))
+        .key("UserId", AttributeValue::S(username.to_string()))
+        .send()
+        .await?;
+
+    Ok(strike.item().map(extract_strike))
+}
+
+pub async fn get_all_strikes(
+    table_name: &str,
+    client: &Client,
+) -> Result<Vec<StrikeEntity>, Error> {
+    let items = client
+        .scan()
+        .table_name(table_name)
+        .into_paginator()
+        .items()
+        .send()
+        .try_collect()
+        .await?;
+
+    Ok(items.iter().map(extract_strike).collect())
 }
 
 pub async fn increment_strikes(
+    board: &str,
+    username: &str,
+    reason: Option<&str>,
+    table_name: &str,
+    client: &Client,
+) -> Result<u32, Error> {
+    let update = client
+        .update_item()
+        .table_name(table_name.to_string())
+        .key("Board", AttributeValue::S(board.to_string()))
+        .key("UserId", AttributeValue::S(username.to_string()))
+        .expression_attribute_values(":value", AttributeValue::N("1".to_string()))
+        .return_values(ReturnValue::UpdatedNew);
+
+    let update = match reason {
+        Some(reason) => update
+            .update_expression(
+                "set Strikes = Strikes + :value, Reasons = list_append(if_not_exists(Reasons, :empty), :reason)",
+            )
+            .expression_attribute_values(":empty", AttributeValue::L(vec![]))
+            .expression_attribute_values(
+                ":reason",
+                AttributeValue::L(vec![AttributeValue::S(reason.to_string())]),
+            ),
+        None => update.update_expression("set Strikes = Strikes + :value"),
+    };
+
+    let request = update.send().await.map_err(|err| err.into_service_error());
+
+    match request {
+        Ok(response) => {
+            let strike_count = extract_strike_count(response.attributes().unwrap());
+            Ok(strike_count)
+        }
+        Err(err) => match ProvideErrorMetadata::code(&err) {
+            Some("ValidationException") => {
+                add_user(board, username, reason, table_name, client).await
+            }
+            _ => Err(err.into()),
+        },
+    }
+}
+
+pub async fn decrement_strikes(
+    board: &str,
     username: &str,
     table_name: &str,
     client: &Client,
-) -> Result<u8, Error> {
+) -> Result<u32, Error> {
     let request = client
         .update_item()
         .table_name(table_name.to_string())
+        .key("Board", AttributeValue::S(board.to_string()))
         .key("UserId", AttributeValue::S(username.to_string()))
-        .update_expression("set Strikes = Strikes + :value")
+        .update_expression("set Strikes = Strikes - :value")
+        .condition_expression("Strikes > :zero")
         .expression_attribute_values(":value", AttributeValue::N("1".to_string()))
+        .expression_attribute_values(":zero", AttributeValue::N("0".to_string()))
         .return_values(ReturnValue::UpdatedNew)
         .send()
         .await
@@ -68,33 +264,991 @@ pub async fn increment_strikes(
     match request {
         Ok(response) => {
             let strike_count = extract_strike_count(response.attributes().unwrap());
+            if strike_count == 0 {
+                remove_user(board, username, table_name, client).await?;
+            }
             Ok(strike_count)
         }
-        Err(err) => match ProvideErrorMetadata::code(&err) {
-            Some("ValidationException") => add_user(username, table_name, client).await,
-            _ => Err(err.into()),
-        },
+        Err(err) if err.is_conditional_check_failed_exception() => Ok(0),
+        Err(err) => Err(err.into()),
+    }
+}
+
+pub async fn add_strike_event(
+    board: &str,
+    username: &str,
+    reason: Option<&str>,
+    expire_after_days: Option<u32>,
+    table_name: &str,
+    client: &Client,
+) -> Result<StrikeEventEntity, Error> {
+    let event = new_strike_event(board, username, reason, expire_after_days);
+
+    client
+        .put_item()
+        .table_name(table_name.to_string())
+        .set_item(Some(strike_event_item(&event)))
+        .send()
+        .await?;
+
+    Ok(event)
+}
+
+// Counts a strike and records its event in one transaction, so a failed request leaves neither
+// behind and the counter never drifts from the history. Returns the counter after the strike.
+pub async fn record_strike(
+    board: &str,
+    username: &str,
+    reason: Option<&str>,
+    expire_after_days: Option<u32>,
+    strikes_table_name: &str,
+    events_table_name: &str,
+    client: &Client,
+) -> Result<u32, Error> {
+    for _ in 0..TRANSACTION_ATTEMPTS {
+        // A transaction can't return the updated item, so the counter is read beforehand and
+        // must not have changed when the strike is written.
+        let strikes = client
+            .get_item()
+            .table_name(strikes_table_name)
+            .key("Board", AttributeValue::S(board.to_string()))
+            .key("UserId", AttributeValue::S(username.to_string()))
+            .consistent_read(true)
+            .send()
+            .await?
+            .item()
+            .map(extract_strike_count);
+        let event = new_strike_event(board, username, reason, expire_after_days);
+
+        let counter = Update::builder()
+            .table_name(strikes_table_name)
+            .key("Board", AttributeValue::S(board.to_string()))
+            .key("UserId", AttributeValue::S(username.to_string()))
+            .expression_attribute_values(
+                ":next",
+                AttributeValue::N((strikes.unwrap_or(0) + 1).to_string()),
+            );
+        let counter = match strikes {
+            Some(strikes) => counter
+                .condition_expression("Strikes = :count")
+                .expression_attribute_values(":count", AttributeValue::N(strikes.to_string())),
+            None => counter.condition_expression("attribute_not_exists(Strikes)"),
+        };
+        let counter = match reason {
+            Some(reason) => counter
+                .update_expression(
+                    "set Strikes = :next, Reasons = list_append(if_not_exists(Reasons, :empty), :reason)",
+                )
+                .expression_attribute_values(":empty", AttributeValue::L(vec![]))
+                .expression_attribute_values(
+                    ":reason",
+                    AttributeValue::L(vec![AttributeValue::S(reason.to_string())]),
+                ),
+            None => counter.update_expression("set Strikes = :next"),
+        };
+        let event = Put::builder()
+            .table_name(events_table_name)
+            .set_item(Some(strike_event_item(&event)))
+            .condition_expression("attribute_not_exists(BoardUserId)");
+
+        match client
+            .transact_write_items()
+            .transact_items(
+                TransactWriteItem::builder()
+                    .update(counter.build()?)
+                    .build(),
+            )
+            .transact_items(TransactWriteItem::builder().put(event.build()?).build())
+            .send()
+            .await
+            .map_err(|err| err.into_service_error())
+        {
+            Ok(_) => return Ok(strikes.unwrap_or(0) + 1),
+            Err(err) if err.is_transaction_canceled_exception() => continue,
+            Err(err) => return Err(err.into()),
+        }
+    }
+
+    Err(format!("The strikes of {} kept changing while adding one", username).into())
+}
+
+// The removed strike takes its reason along when it had one. Strikes from before the history
+// was kept only tell that when every one of them has a reason.
+pub fn drops_reason(strike: &StrikeEntity, latest: Option<&StrikeEventEntity>) -> bool {
+    !strike.reasons.is_empty()
+        && latest.map_or(strike.reasons.len() as u32 >= strike.strikes, |event| {
+            event.reason.is_some()
+        })
+}
+
+// Takes back the latest strike: lowers the counter, drops its reason and deletes its event in
+// one transaction. The counter must not have changed since it was read, otherwise the wrong
+// reason could be dropped, so a strike added meanwhile makes it start over. Returns the counter
+// after the strike was removed.
+pub async fn remove_strike(
+    board: &str,
+    username: &str,
+    strikes_table_name: &str,
+    events_table_name: &str,
+    client: &Client,
+) -> Result<u32, Error> {
+    for _ in 0..TRANSACTION_ATTEMPTS {
+        let strike = client
+            .get_item()
+            .table_name(strikes_table_name)
+            .key("Board", AttributeValue::S(board.to_string()))
+            .key("UserId", AttributeValue::S(username.to_string()))
+            .consistent_read(true)
+            .send()
+            .await?;
+        let Some(strike) = strike
+            .item()
+            .map(extract_strike)
+            .filter(|strike| strike.strikes > 0)
+        else {
+            return Ok(0);
+        };
+        let latest = client
+            .query()
+            .table_name(events_table_name)
+            .key_condition_expression("BoardUserId = :key")
+            .expression_attribute_values(":key", AttributeValue::S(board_user_id(board, username)))
+            .scan_index_forward(false)
+            .limit(1)
+            .consistent_read(true)
+            .send()
+            .await?
+            .items()
+            .first()
+            .map(extract_strike_event);
+
+        let count = AttributeValue::N(strike.strikes.to_string());
+        let counter = match strike.strikes {
+            1 => TransactWriteItem::builder()
+                .delete(
+                    Delete::builder()
+                        .table_name(strikes_table_name)
+                        .key("Board", AttributeValue::S(board.to_string()))
+                        .key("UserId", AttributeValue::S(username.to_string()))
+                        .condition_expression("Strikes = :count")
+                        .expression_attribute_values(":count", count)
+                        .build()?,
+                )
+                .build(),
+            _ => {
+                let update = Update::builder()
+                    .table_name(strikes_table_name)
+                    .key("Board", AttributeValue::S(board.to_string()))
+                    .key("UserId", AttributeValue::S(username.to_string()))
+                    .expression_attribute_values(":count", count)
+                    .expression_attribute_values(":value", AttributeValue::N("1".to_string()));
+                let update = match drops_reason(&strike, latest.as_ref()) {
+                    true => update
+                        .update_expression(format!(
+                            "set Strikes = Strikes - :value remove Reasons[{}]",
+                            strike.reasons.len() - 1
+                        ))
+                        .condition_expression("Strikes = :count AND size(Reasons) = :reasons")
+                        .expression_attribute_values(
+                            ":reasons",
+                            AttributeValue::N(strike.reasons.len().to_string()),
+                        ),
+                    false => update
+                        .update_expression("set Strikes = Strikes - :value")
+                        .condition_expression("Strikes = :count"),
+                };
+                TransactWriteItem::builder().update(update.build()?).build()
+            }
+        };
+
+        let mut transaction = client.transact_write_items().transact_items(counter);
+        if let Some(event) = &latest {
+            transaction = transaction.transact_items(
+                TransactWriteItem::builder()
+                    .delete(
+                        Delete::builder()
+                            .table_name(events_table_name)
+                            .key(
+                                "BoardUserId",
+                                AttributeValue::S(board_user_id(&event.board, &event.user_id)),
+                            )
+                            .key("Timestamp", AttributeValue::S(event.timestamp.clone()))
+                            .build()?,
+                    )
+                    .build(),
+            );
+        }
+
+        match transaction
+            .send()
+            .await
+            .map_err(|err| err.into_service_error())
+        {
+            Ok(_) => return Ok(strike.strikes - 1),
+            Err(err) if err.is_transaction_canceled_exception() => continue,
+            Err(err) => return Err(err.into()),
+        }
+    }
+
+    Err(format!(
+        "The strikes of {} kept changing while removing one",
+        username
+    )
+    .into())
+}
+
+// Events are keyed by their timestamp, so importing the same strike twice only stores it once.
+pub async fn import_strike_event(
+    event: &StrikeEventEntity,
+    table_name: &str,
+    client: &Client,
+) -> Result<bool, Error> {
+    let request = client
+        .put_item()
+        .table_name(table_name.to_string())
+        .set_item(Some(strike_event_item(event)))
+        .condition_expression("attribute_not_exists(BoardUserId)")
+        .send()
+        .await
+        .map_err(|err| err.into_service_error());
+
+    match request {
+        Ok(_) => Ok(true),
+        Err(err) if err.is_conditional_check_failed_exception() => Ok(false),
+        Err(err) => Err(err.into()),
+    }
+}
+
+// Copies the counters of the table from before boards existed onto the default board. A name
+// that already has a counter there keeps it, so the copy can be run again.
+pub async fn copy_legacy_strikes(
+    legacy_table_name: &str,
+    table_name: &str,
+    client: &Client,
+) -> Result<u32, Error> {
+    let items: Vec<HashMap<String, AttributeValue>> = client
+        .scan()
+        .table_name(legacy_table_name)
+        .into_paginator()
+        .items()
+        .send()
+        .try_collect()
+        .await?;
+
+    let mut copied = 0;
+    for mut item in items {
+        item.insert(
+            "Board".to_string(),
+            AttributeValue::S(DEFAULT_BOARD.to_string()),
+        );
+        let request = client
+            .put_item()
+            .table_name(table_name)
+            .set_item(Some(item))
+            .condition_expression("attribute_not_exists(UserId)")
+            .send()
+            .await
+            .map_err(|err| err.into_service_error());
+
+        match request {
+            Ok(_) => copied += 1,
+            Err(err) if err.is_conditional_check_failed_exception() => {}
+            Err(err) => return Err(err.into()),
+        }
+    }
+
+    Ok(copied)
+}
+
+pub async fn add_strikes(
+    board: &str,
+    username: &str,
+    reasons: &[String],
+    count: u32,
+    table_name: &str,
+    client: &Client,
+) -> Result<u32, Error> {
+    let response = client
+        .update_item()
+        .table_name(table_name.to_string())
+        .key("Board", AttributeValue::S(board.to_string()))
+        .key("UserId", AttributeValue::S(username.to_string()))
+        .update_expression(
+            "set Strikes = if_not_exists(Strikes, :zero) + :count, Reasons = list_append(if_not_exists(Reasons, :empty), :reasons)",
+        )
+        .expression_attribute_values(":zero", AttributeValue::N("0".to_string()))
+        .expression_attribute_values(":count", AttributeValue::N(count.to_string()))
+        .expression_attribute_values(":empty", AttributeValue::L(vec![]))
+        .expression_attribute_values(
+            ":reasons",
+            AttributeValue::L(
+                reasons
+                    .iter()
+                    .map(|reason| AttributeValue::S(reason.clone()))
+                    .collect(),
+            ),
+        )
+        .return_values(ReturnValue::UpdatedNew)
+        .send()
+        .await?;
+
+    Ok(extract_strike_count(response.attributes().unwrap()))
+}
+
+pub async fn get_strike_events(
+    board: &str,
+    username: &str,
+    table_name: &str,
+    client: &Client,
+) -> Result<Vec<StrikeEventEntity>, Error> {
+    let items = client
+        .query()
+        .table_name(table_name)
+        .key_condition_expression("BoardUserId = :key")
+        .expression_attribute_values(":key", AttributeValue::S(board_user_id(board, username)))
+        .into_paginator()
+        .items()
+        .send()
+        .try_collect()
+        .await?;
+
+    Ok(items.iter().map(extract_strike_event).collect())
+}
+
+// DynamoDB removes expired events lazily, so anything past ExpiresAt is filtered out explicitly.
+pub fn is_active(event: &StrikeEventEntity, now: i64) -> bool {
+    event.expires_at.is_none_or(|expires_at| expires_at > now)
+}
+
+pub async fn count_active_strike_events(
+    board: &str,
+    username: &str,
+    now: i64,
+    table_name: &str,
+    client: &Client,
+) -> Result<u32, Error> {
+    let pages = client
+        .query()
+        .table_name(table_name)
+        .key_condition_expression("BoardUserId = :key")
+        .filter_expression("attribute_not_exists(ExpiresAt) OR ExpiresAt > :now")
+        .expression_attribute_values(":key", AttributeValue::S(board_user_id(board, username)))
+        .expression_attribute_values(":now", AttributeValue::N(now.to_string()))
+        .into_paginator()
+        .send()
+        .try_collect()
+        .await?;
+
+    Ok(pages.iter().map(|page| page.count() as u32).sum())
+}
+
+pub async fn get_active_strikes(
+    board: Option<&str>,
+    now: i64,
+    table_name: &str,
+    client: &Client,
+) -> Result<Vec<StrikeEntity>, Error> {
+    let request = client
+        .scan()
+        .table_name(table_name)
+        .expression_attribute_values(":now", AttributeValue::N(now.to_string()));
+
+    let request = match board {
+        Some(board) => request
+            .filter_expression(
+                "Board = :board AND (attribute_not_exists(ExpiresAt) OR ExpiresAt > :now)",
+            )
+            .expression_attribute_values(":board", AttributeValue::S(board.to_string())),
+        None => request.filter_expression("attribute_not_exists(ExpiresAt) OR ExpiresAt > :now"),
+    };
+
+    let items = request
+        .into_paginator()
+        .items()
+        .send()
+        .try_collect()
+        .await?;
+
+    Ok(strikes_from_events(
+        items.iter().map(extract_strike_event).collect(),
+    ))
+}
+
+pub fn new_strike_event(
+    board: &str,
+    username: &str,
+    reason: Option<&str>,
+    expire_after_days: Option<u32>,
+) -> StrikeEventEntity {
+    let now = Utc::now();
+    StrikeEventEntity {
+        board: board.to_string(),
+        user_id: username.to_string(),
+        timestamp: now.to_rfc3339_opts(SecondsFormat::Micros, true),
+        reason: reason.map(str::to_string),
+        expires_at: expire_after_days.map(|days| (now + Duration::days(days.into())).timestamp()),
+    }
+}
+
+// Counts the events per board and name, keeping the reasons in the order they were given.
+pub fn strikes_from_events(events: Vec<StrikeEventEntity>) -> Vec<StrikeEntity> {
+    let mut events = events;
+    events.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+
+    events
+        .into_iter()
+        .fold(BTreeMap::new(), |mut strikes, event| {
+            let strike = strikes
+                .entry((event.board.clone(), event.user_id.clone()))
+                .or_insert_with(|| StrikeEntity {
+                    board: event.board,
+                    user_id: event.user_id,
+                    strikes: 0,
+                    reasons: vec![],
+                });
+            strike.strikes += 1;
+            strike.reasons.extend(event.reason);
+            strikes
+        })
+        .into_values()
+        .collect()
+}
+
+pub async fn delete_latest_strike_event(
+    board: &str,
+    username: &str,
+    table_name: &str,
+    client: &Client,
+) -> Result<(), Error> {
+    let response = client
+        .query()
+        .table_name(table_name)
+        .key_condition_expression("BoardUserId = :key")
+        .expression_attribute_values(":key", AttributeValue::S(board_user_id(board, username)))
+        .scan_index_forward(false)
+        .limit(1)
+        .send()
+        .await?;
+
+    if let Some(event) = response.items().first().map(extract_strike_event) {
+        delete_strike_event(&event, table_name, client).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn delete_strike_events(
+    board: &str,
+    username: &str,
+    table_name: &str,
+    client: &Client,
+) -> Result<(), Error> {
+    for event in get_strike_events(board, username, table_name, client).await? {
+        delete_strike_event(&event, table_name, client).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn delete_all_strike_events(
+    board: &str,
+    table_name: &str,
+    client: &Client,
+) -> Result<DeleteReport, Error> {
+    let items = client
+        .scan()
+        .table_name(table_name)
+        .filter_expression("Board = :board")
+        .expression_attribute_values(":board", AttributeValue::S(board.to_string()))
+        .into_paginator()
+        .items()
+        .send()
+        .try_collect()
+        .await?;
+    let keys = items
+        .into_iter()
+        .map(|mut item| {
+            ["BoardUserId", "Timestamp"]
+                .into_iter()
+                .filter_map(|key| item.remove_entry(key))
+                .collect()
+        })
+        .collect();
+
+    delete_in_batches(keys, table_name, client).await
+}
+
+pub async fn get_aliases(
+    board: &str,
+    table_name: &str,
+    client: &Client,
+) -> Result<BTreeMap<String, String>, Error> {
+    let items = client
+        .query()
+        .table_name(table_name)
+        .key_condition_expression("Board = :board")
+        .expression_attribute_values(":board", AttributeValue::S(board.to_string()))
+        .into_paginator()
+        .items()
+        .send()
+        .try_collect()
+        .await?;
+
+    Ok(items
+        .iter()
+        .map(|item| {
+            (
+                item.get("Alias").unwrap().as_s().unwrap().to_string(),
+                item.get("Name").unwrap().as_s().unwrap().to_string(),
+            )
+        })
+        .collect())
+}
+
+pub async fn put_alias(
+    board: &str,
+    alias: &str,
+    name: &str,
+    table_name: &str,
+    client: &Client,
+) -> Result<(), Error> {
+    client
+        .put_item()
+        .table_name(table_name)
+        .item("Board", AttributeValue::S(board.to_string()))
+        .item("Alias", AttributeValue::S(alias.to_string()))
+        .item("Name", AttributeValue::S(name.to_string()))
+        .send()
+        .await?;
+
+    Ok(())
+}
+
+pub async fn delete_alias(
+    board: &str,
+    alias: &str,
+    table_name: &str,
+    client: &Client,
+) -> Result<bool, Error> {
+    let response = client
+        .delete_item()
+        .table_name(table_name)
+        .key("Board", AttributeValue::S(board.to_string()))
+        .key("Alias", AttributeValue::S(alias.to_string()))
+        .return_values(ReturnValue::AllOld)
+        .send()
+        .await?;
+
+    Ok(response.attributes().is_some())
+}
+
+pub async fn add_connection(
+    connection_id: &str,
+    table_name: &str,
+    client: &Client,
+) -> Result<(), Error> {
+    client
+        .put_item()
+        .table_name(table_name)
+        .item("ConnectionId", AttributeValue::S(connection_id.to_string()))
+        .send()
+        .await?;
+
+    Ok(())
+}
+
+pub async fn delete_connection(
+    connection_id: &str,
+    table_name: &str,
+    client: &Client,
+) -> Result<(), Error> {
+    client
+        .delete_item()
+        .table_name(table_name)
+        .key("ConnectionId", AttributeValue::S(connection_id.to_string()))
+        .send()
+        .await?;
+
+    Ok(())
+}
+
+pub async fn get_connection_ids(table_name: &str, client: &Client) -> Result<Vec<String>, Error> {
+    let items = client
+        .scan()
+        .table_name(table_name)
+        .into_paginator()
+        .items()
+        .send()
+        .try_collect()
+        .await?;
+
+    Ok(items
+        .iter()
+        .map(|item| {
+            item.get("ConnectionId")
+                .unwrap()
+                .as_s()
+                .unwrap()
+                .to_string()
+        })
+        .collect())
+}
+
+pub fn resolve_alias<'a>(aliases: &'a BTreeMap<String, String>, username: &'a str) -> &'a str {
+    aliases.get(username).map_or(username, String::as_str)
+}
+
+// The name followed by every alias of it, the strikes of a name may be recorded under each.
+pub fn alias_spellings<'a>(
+    aliases: &'a BTreeMap<String, String>,
+    username: &'a str,
+) -> Vec<&'a str> {
+    std::iter::once(username)
+        .chain(
+            aliases
+                .iter()
+                .filter(|(_, name)| name.as_str() == username)
+                .map(|(alias, _)| alias.as_str()),
+        )
+        .collect()
+}
+
+// Strikes given under an alias before it was registered count for the name it stands for.
+pub fn fold_aliases(
+    strikes: Vec<StrikeEntity>,
+    aliases: &BTreeMap<String, String>,
+) -> Vec<StrikeEntity> {
+    strikes
+        .into_iter()
+        .fold(BTreeMap::new(), |mut folded, strike| {
+            let user_id = resolve_alias(aliases, &strike.user_id).to_string();
+            let entry = folded
+                .entry((strike.board.clone(), user_id.clone()))
+                .or_insert_with(|| StrikeEntity {
+                    board: strike.board.clone(),
+                    user_id,
+                    strikes: 0,
+                    reasons: vec![],
+                });
+            entry.strikes += strike.strikes;
+            entry.reasons.extend(strike.reasons);
+            folded
+        })
+        .into_values()
+        .collect()
+}
+
+// Moves the counter and the events of an alias over to its name, returning how many strikes
+// were moved.
+pub async fn merge_strikes(
+    board: &str,
+    alias: &str,
+    username: &str,
+    strikes_table_name: &str,
+    events_table_name: &str,
+    client: &Client,
+) -> Result<u32, Error> {
+    let events = get_strike_events(board, alias, events_table_name, client).await?;
+    for event in &events {
+        let moved = StrikeEventEntity {
+            user_id: username.to_string(),
+            ..event.clone()
+        };
+        import_strike_event(&moved, events_table_name, client).await?;
+        delete_strike_event(event, events_table_name, client).await?;
     }
+
+    let strike = get_strikes(board, strikes_table_name, client)
+        .await?
+        .into_iter()
+        .find(|strike| strike.user_id == alias);
+    let Some(strike) = strike else {
+        return Ok(events.len() as u32);
+    };
+
+    add_strikes(
+        board,
+        username,
+        &strike.reasons,
+        strike.strikes,
+        strikes_table_name,
+        client,
+    )
+    .await?;
+    delete_strikes(board, alias, strikes_table_name, client).await?;
+
+    Ok(strike.strikes)
 }
 
 pub fn sort_strikes_desc(strikes: &[StrikeEntity]) -> Vec<StrikeEntity> {
     let mut strikes = strikes.to_vec().clone();
-    strikes.sort_by(|a, b| b.strikes.cmp(&a.strikes));
+    strikes.sort_by_key(|strike| std::cmp::Reverse(strike.strikes));
     strikes.to_vec()
 }
 
-async fn add_user(username: &str, table_name: &str, client: &Client) -> Result<u8, Error> {
+async fn add_user(
+    board: &str,
+    username: &str,
+    reason: Option<&str>,
+    table_name: &str,
+    client: &Client,
+) -> Result<u32, Error> {
+    let reasons = reason
+        .map(|reason| vec![AttributeValue::S(reason.to_string())])
+        .unwrap_or_default();
+
     client
         .put_item()
         .table_name(table_name.to_string())
+        .item("Board", AttributeValue::S(board.to_string()))
         .item("UserId", AttributeValue::S(username.to_string()))
         .item("Strikes", AttributeValue::N("1".to_string()))
+        .item("Reasons", AttributeValue::L(reasons))
         .send()
         .await?;
 
     Ok(1)
 }
 
-fn extract_strike_count(map: &HashMap<String, AttributeValue>) -> u8 {
+async fn remove_user(
+    board: &str,
+    username: &str,
+    table_name: &str,
+    client: &Client,
+) -> Result<(), Error> {
+    let request = client
+        .delete_item()
+        .table_name(table_name.to_string())
+        .key("Board", AttributeValue::S(board.to_string()))
+        .key("UserId", AttributeValue::S(username.to_string()))
+        .condition_expression("Strikes = :zero")
+        .expression_attribute_values(":zero", AttributeValue::N("0".to_string()))
+        .send()
+        .await
+        .map_err(|err| err.into_service_error());
+
+    match request {
+        Ok(_) => Ok(()),
+        Err(err) if err.is_conditional_check_failed_exception() => Ok(()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+async fn delete_strike_event(
+    event: &StrikeEventEntity,
+    table_name: &str,
+    client: &Client,
+) -> Result<(), Error> {
+    client
+        .delete_item()
+        .table_name(table_name)
+        .key(
+            "BoardUserId",
+            AttributeValue::S(board_user_id(&event.board, &event.user_id)),
+        )
+        .key("Timestamp", AttributeValue::S(event.timestamp.clone()))
+        .send()
+        .await?;
+
+    Ok(())
+}
+
+// The batches go out side by side, so clearing a big board doesn't run into the lambda timeout.
+async fn delete_in_batches(
+    keys: Vec<HashMap<String, AttributeValue>>,
+    table_name: &str,
+    client: &Client,
+) -> Result<DeleteReport, Error> {
+    let mut batches = JoinSet::new();
+    for chunk in keys.chunks(BATCH_SIZE) {
+        let requests = chunk
+            .iter()
+            .map(|key| {
+                DeleteRequest::builder()
+                    .set_key(Some(key.clone()))
+                    .build()
+                    .map(|request| WriteRequest::builder().delete_request(request).build())
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        batches.spawn(write_batch(
+            requests,
+            table_name.to_string(),
+            client.clone(),
+        ));
+    }
+
+    // A batch that panicked took its report with it, so whatever wasn't reported counts as
+    // failed.
+    let mut report = DeleteReport::default();
+    while let Some(batch) = batches.join_next().await {
+        if let Ok(batch) = batch {
+            report = report + batch;
+        }
+    }
+
+    Ok(DeleteReport {
+        removed: report.removed,
+        failed: keys.len() as u32 - report.removed,
+    })
+}
+
+// DynamoDB hands back what it couldn't write when it's throttled, which is tried again after a
+// growing pause. A request that fails outright leaves the rest of the batch as failed, so the
+// other batches still go through.
+async fn write_batch(
+    requests: Vec<WriteRequest>,
+    table_name: String,
+    client: Client,
+) -> DeleteReport {
+    let total = requests.len() as u32;
+    let mut pending = requests;
+
+    for attempt in 0..BATCH_ATTEMPTS {
+        if attempt > 0 {
+            tokio::time::sleep(std::time::Duration::from_millis(50 << attempt)).await;
+        }
+
+        let response = match client
+            .batch_write_item()
+            .request_items(&table_name, pending.clone())
+            .send()
+            .await
+        {
+            Ok(response) => response,
+            Err(err) => {
+                tracing::warn!("Failed to delete a batch from {}: {}", table_name, err);
+                break;
+            }
+        };
+        pending = response
+            .unprocessed_items
+            .and_then(|mut unprocessed| unprocessed.remove(&table_name))
+            .unwrap_or_default();
+        if pending.is_empty() {
+            break;
+        }
+    }
+
+    DeleteReport {
+        removed: total - pending.len() as u32,
+        failed: pending.len() as u32,
+    }
+}
+
+fn strike_event_item(event: &StrikeEventEntity) -> HashMap<String, AttributeValue> {
+    let mut item = HashMap::from([
+        (
+            "BoardUserId".to_string(),
+            AttributeValue::S(board_user_id(&event.board, &event.user_id)),
+        ),
+        ("Board".to_string(), AttributeValue::S(event.board.clone())),
+        (
+            "UserId".to_string(),
+            AttributeValue::S(event.user_id.clone()),
+        ),
+        (
+            "Timestamp".to_string(),
+            AttributeValue::S(event.timestamp.clone()),
+        ),
+    ]);
+
+    if let Some(reason) = &event.reason {
+        item.insert("Reason".to_string(), AttributeValue::S(reason.clone()));
+    }
+
+    if let Some(expires_at) = event.expires_at {
+        item.insert(
+            "ExpiresAt".to_string(),
+            AttributeValue::N(expires_at.to_string()),
+        );
+    }
+
+    item
+}
+
+fn board_user_id(board: &str, username: &str) -> String {
+    format!("{}#{}", board, username)
+}
+
+fn extract_strike(item: &HashMap<String, AttributeValue>) -> StrikeEntity {
+    StrikeEntity {
+        board: item.get("Board").unwrap().as_s().unwrap().to_string(),
+        user_id: item.get("UserId").unwrap().as_s().unwrap().to_string(),
+        strikes: extract_strike_count(item),
+        reasons: extract_reasons(item),
+    }
+}
+
+fn extract_strike_event(item: &HashMap<String, AttributeValue>) -> StrikeEventEntity {
+    StrikeEventEntity {
+        board: item.get("Board").unwrap().as_s().unwrap().to_string(),
+        user_id: item.get("UserId").unwrap().as_s().unwrap().to_string(),
+        timestamp: item.get("Timestamp").unwrap().as_s().unwrap().to_string(),
+        reason: item
+            .get("Reason")
+            .and_then(|reason| reason.as_s().ok())
+            .cloned(),
+        expires_at: item
+            .get("ExpiresAt")
+            .and_then(|expires_at| expires_at.as_n().ok())
+            .and_then(|expires_at| expires_at.parse().ok()),
+    }
+}
+
+fn extract_strike_count(map: &HashMap<String, AttributeValue>) -> u32 {
     map.get("Strikes").unwrap().as_n().unwrap().parse().unwrap()
 }
+
+fn extract_reasons(map: &HashMap<String, AttributeValue>) -> Vec<String> {
+    map.get("Reasons")
+        .and_then(|reasons| reasons.as_l().ok())
+        .map(|reasons| {
+            reasons
+                .iter()
+                .filter_map(|reason| reason.as_s().ok().cloned())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use aws_sdk_dynamodb::config::{BehaviorVersion, Credentials, Region};
+
+    #[tokio::test]
+    async fn it_should_count_the_keys_of_a_failed_batch_as_failed() {
+        let config = aws_sdk_dynamodb::Config::builder()
+            .behavior_version(BehaviorVersion::latest())
+            .region(Region::new("eu-central-1"))
+            .credentials_provider(Credentials::new("x", "y", None, None, "unit_tests"))
+            .endpoint_url("http://127.0.0.1:1")
+            .build();
+        let keys = (0..30)
+            .map(|user| {
+                HashMap::from([
+                    (
+                        "Board".to_string(),
+                        AttributeValue::S("default".to_string()),
+                    ),
+                    (
+                        "UserId".to_string(),
+                        AttributeValue::S(format!("user{}", user)),
+                    ),
+                ])
+            })
+            .collect();
+
+        let report = delete_in_batches(keys, "Strikes", &Client::from_conf(config))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            report,
+            DeleteReport {
+                removed: 0,
+                failed: 30,
+            }
+        );
+    }
+}
@@ -1,12 +1,11 @@
 use ::serde::Serialize;
-use aws_config::BehaviorVersion;
-use aws_sdk_dynamodb::{types::AttributeValue, Client};
 use lambda_http::{
     aws_lambda_events::apigw::ApiGatewayWebsocketProxyRequest,
     lambda_runtime::{self},
     tracing,
 };
 use lambda_runtime::{service_fn, Error, LambdaEvent};
+use lib::repository::{DynamoDbRepository, StrikesRepository};
 
 #[derive(Debug, Serialize)]
 struct Response {
@@ -16,26 +15,10 @@ struct Response {
 
 async fn function_handler(
     event: LambdaEvent<ApiGatewayWebsocketProxyRequest>,
+    repository: &dyn StrikesRepository,
 ) -> Result<Response, Error> {
-    let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
-    let client = Client::new(&config);
-
-    add_connection_id(event, &client, "Connections").await
-}
-
-async fn add_connection_id(
-    event: LambdaEvent<ApiGatewayWebsocketProxyRequest>,
-    client: &Client,
-    table_name: &str,
-) -> Result<Response, Error> {
-    client
-        .put_item()
-        .table_name(table_name)
-        .item(
-            "ConnectionId",
-            AttributeValue::S(event.payload.request_context.connection_id.unwrap()),
-        )
-        .send()
+    repository
+        .add_connection(&event.payload.request_context.connection_id.unwrap())
         .await?;
 
     Ok(Response { status_code: 200 })
@@ -46,10 +29,46 @@ async fn add_connection_id(
 async fn main() -> Result<(), Error> {
     tracing::init_default_subscriber();
 
-    lambda_runtime::run(service_fn(function_handler)).await?;
+    let repository = DynamoDbRepository::from_env().await;
+    lambda_runtime::run(service_fn(|event| function_handler(event, &repository))).await?;
     Ok(())
 }
 
+#[cfg(test)]
+mod unit_tests {
+    use aws_lambda_events::apigw::{
+        ApiGatewayWebsocketProxyRequest, ApiGatewayWebsocketProxyRequestContext,
+    };
+    use lambda_http::{lambda_runtime, Context};
+    use lambda_runtime::LambdaEvent;
+    use lib::repository::{InMemoryRepository, StrikesRepository};
+
+    use crate::function_handler;
+
+    #[tokio::test]
+    async fn it_should_store_the_connection_id() {
+        let repository = InMemoryRepository::default();
+        let event = LambdaEvent {
+            payload: ApiGatewayWebsocketProxyRequest {
+                request_context: ApiGatewayWebsocketProxyRequestContext {
+                    connection_id: Some("abcdefghijkl".to_string()),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            context: Context::default(),
+        };
+
+        let response = function_handler(event, &repository).await.unwrap();
+
+        assert_eq!(response.status_code, 200);
+        assert_eq!(
+            repository.get_connection_ids().await.unwrap(),
+            vec!["abcdefghijkl".to_string()]
+        );
+    }
+}
+
 #[cfg(test)]
 mod integration_tests {
     use aws_config::BehaviorVersion;
@@ -63,9 +82,10 @@ mod integration_tests {
     };
     use lambda_http::{lambda_runtime, Context};
     use lambda_runtime::LambdaEvent;
+    use lib::repository::DynamoDbRepository;
     use uuid::Uuid;
 
-    use crate::add_connection_id;
+    use crate::function_handler;
 
     async fn create_random_table(client: &Client) -> Result<String, Error> {
         let random_table_name = format!("Connections_{}", Uuid::new_v4());
@@ -100,6 +120,10 @@ mod integration_tests {
 
         let client = Client::from_conf(local_config);
         let table_name = create_random_table(&client).await.unwrap();
+        let repository = DynamoDbRepository {
+            connections_table: table_name.clone(),
+            ..DynamoDbRepository::new(client.clone())
+        };
 
         let request_context = ApiGatewayWebsocketProxyRequestContext {
             connection_id: Some("abcdefghijkl".to_string()),
@@ -117,9 +141,7 @@ mod integration_tests {
             context,
         };
 
-        let response = add_connection_id(event, &client, &table_name)
-            .await
-            .unwrap();
+        let response = function_handler(event, &repository).await.unwrap();
 
         let connection_ids = client
             .scan()
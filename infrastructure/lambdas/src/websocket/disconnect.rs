@@ -1,12 +1,11 @@
 use ::serde::Serialize;
-use aws_config::BehaviorVersion;
-use aws_sdk_dynamodb::{types::AttributeValue, Client};
 use lambda_http::{
     aws_lambda_events::apigw::ApiGatewayWebsocketProxyRequest,
     lambda_runtime::{self},
     tracing,
 };
 use lambda_runtime::{service_fn, Error, LambdaEvent};
+use lib::repository::{DynamoDbRepository, StrikesRepository};
 
 #[derive(Debug, Serialize)]
 struct Response {
@@ -16,18 +15,10 @@ struct Response {
 
 async fn function_handler(
     event: LambdaEvent<ApiGatewayWebsocketProxyRequest>,
+    repository: &dyn StrikesRepository,
 ) -> Result<Response, Error> {
-    let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
-    let client = Client::new(&config);
-
-    client
-        .delete_item()
-        .table_name("Connections".to_string())
-        .key(
-            "ConnectionId",
-            AttributeValue::S(event.payload.request_context.connection_id.unwrap()),
-        )
-        .send()
+    repository
+        .delete_connection(&event.payload.request_context.connection_id.unwrap())
         .await?;
 
     Ok(Response { status_code: 200 })
@@ -38,6 +29,7 @@ async fn function_handler(
 async fn main() -> Result<(), Error> {
     tracing::init_default_subscriber();
 
-    lambda_runtime::run(service_fn(function_handler)).await?;
+    let repository = DynamoDbRepository::from_env().await;
+    lambda_runtime::run(service_fn(|event| function_handler(event, &repository))).await?;
     Ok(())
 }
@@ -1,13 +1,16 @@
 use ::serde::Serialize;
 use aws_config::BehaviorVersion;
 use aws_sdk_apigatewaymanagement::{config, Client};
-use aws_sdk_dynamodb::{primitives::Blob, Client as DynamoDbClient};
+use aws_sdk_dynamodb::primitives::Blob;
+use chrono::Utc;
 use lambda_http::{
     lambda_runtime::{self},
     tracing, LambdaEvent,
 };
 use lambda_runtime::{service_fn, Error};
-use lib::strikes_db::{get_strikes, sort_strikes_desc, StrikeEntity};
+use lib::repository::{DynamoDbRepository, StrikesRepository};
+use lib::strikes_db::{expire_after_days, fold_aliases, DEFAULT_BOARD};
+use strikes_core::{render::html_list, tarnished::Tarnished};
 
 #[derive(Debug, Serialize)]
 struct Response {
@@ -17,63 +20,57 @@ struct Response {
 
 async fn function_handler(
     _event: LambdaEvent<aws_lambda_events::dynamodb::Event>,
+    repository: &dyn StrikesRepository,
 ) -> Result<Response, Error> {
     let websocket_api_id = std::env::var("WEBSOCKET_API_ID").unwrap();
 
-    let endpoint_url = format!("https://{}.execute-api.eu-central-1.amazonaws.com/v1/", websocket_api_id);
+    let endpoint_url = format!(
+        "https://{}.execute-api.eu-central-1.amazonaws.com/v1/",
+        websocket_api_id
+    );
     let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
 
-    let dynamodb_client = DynamoDbClient::new(&config);
-    let connection_ids = connection_ids(&dynamodb_client).await?;
-
-    let strikes = sort_strikes_desc(&get_strikes("Strikes", &dynamodb_client).await?);
-    let message = ul_from_strikes(strikes);
+    let update = prepare_update(repository).await?;
 
     let api_management_config = config::Builder::from(&config)
         .endpoint_url(endpoint_url)
         .build();
     let client = Client::from_conf(api_management_config);
-    for connection_id in connection_ids {
-        send_data(&client, &connection_id, message.as_str()).await?;
+    for connection_id in update.connection_ids {
+        send_data(&client, &connection_id, update.message.as_str()).await?;
     }
 
     Ok(Response { status_code: 200 })
 }
 
-fn ul_from_strikes(strikes: Vec<StrikeEntity>) -> String {
-    let li = strikes
-        .iter()
-        .map(|strike| format!("<li>{}: {}</li>", strike.user_id, strike.strikes))
-        .collect::<Vec<String>>();
-    format!(
-        "<ul hx-swap-oob=\"innerHTML:#content\">{}</ul>",
-        li.join("")
-    )
+// Every open connection gets the list of the default board, rendered and folded like on the
+// website.
+struct Update {
+    connection_ids: Vec<String>,
+    message: String,
 }
 
-async fn connection_ids(
-    client: &aws_sdk_dynamodb::Client,
-) -> Result<Vec<String>, aws_sdk_dynamodb::Error> {
-    let result = client
-        .scan()
-        .table_name("Connections".to_string())
-        .send()
-        .await?;
+async fn prepare_update(repository: &dyn StrikesRepository) -> Result<Update, Error> {
+    let strikes = match expire_after_days() {
+        Some(_) => {
+            repository
+                .get_active_strikes(Some(DEFAULT_BOARD), Utc::now().timestamp())
+                .await?
+        }
+        None => repository.get_strikes(DEFAULT_BOARD).await?,
+    };
+    let aliases = repository.get_aliases(DEFAULT_BOARD).await?;
+    let tarnished = Tarnished::sort_desc_by_strike(
+        fold_aliases(strikes, &aliases)
+            .into_iter()
+            .map(Tarnished::from)
+            .collect(),
+    );
 
-    let connection_ids = result
-        .items
-        .unwrap()
-        .iter()
-        .map(|item| {
-            item.get("ConnectionId")
-                .unwrap()
-                .as_s()
-                .unwrap()
-                .to_string()
-        })
-        .collect();
-
-    Ok(connection_ids)
+    Ok(Update {
+        connection_ids: repository.get_connection_ids().await?,
+        message: html_list(&tarnished),
+    })
 }
 
 async fn send_data(
@@ -96,6 +93,48 @@ async fn send_data(
 async fn main() -> Result<(), Error> {
     tracing::init_default_subscriber();
 
-    lambda_runtime::run(service_fn(function_handler)).await?;
+    let repository = DynamoDbRepository::from_env().await;
+    lambda_runtime::run(service_fn(|event| function_handler(event, &repository))).await?;
     Ok(())
 }
+
+#[cfg(test)]
+mod unit_tests {
+    use lib::repository::{InMemoryRepository, StrikesRepository};
+
+    use crate::prepare_update;
+
+    #[tokio::test]
+    async fn it_should_send_the_sorted_list_to_every_connection() {
+        let repository = InMemoryRepository::default();
+        repository.add_connection("abcdefghijkl").await.unwrap();
+        repository
+            .increment_strikes("default", "guenther", None)
+            .await
+            .unwrap();
+        repository
+            .add_strikes("default", "heinz", &[], 2)
+            .await
+            .unwrap();
+        repository
+            .increment_strikes("other", "guenther", None)
+            .await
+            .unwrap();
+        repository
+            .add_strikes("default", "gue", &[], 2)
+            .await
+            .unwrap();
+        repository
+            .put_alias("default", "gue", "guenther")
+            .await
+            .unwrap();
+
+        let update = prepare_update(&repository).await.unwrap();
+
+        assert_eq!(update.connection_ids, vec!["abcdefghijkl".to_string()]);
+        assert_eq!(
+            update.message,
+            "<ul hx-swap-oob=\"innerHTML:#content\"><li>guenther: 3</li><li>heinz: 2</li></ul>"
+        );
+    }
+}
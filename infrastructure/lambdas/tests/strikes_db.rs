@@ -1,28 +1,140 @@
 use aws_config::BehaviorVersion;
 use aws_sdk_dynamodb::{
     config::Builder,
-    types::{AttributeDefinition, BillingMode, KeySchemaElement, KeyType, ScalarAttributeType},
+    types::{
+        AttributeDefinition, AttributeValue, BillingMode, KeySchemaElement, KeyType,
+        ScalarAttributeType,
+    },
     Client, Error,
 };
-use lib::strikes_db::{delete_all_strikes, get_strikes, increment_strikes, StrikeEntity};
+use lib::strikes_db::{
+    add_strike_event, add_strikes, copy_legacy_strikes, count_active_strike_events,
+    decrement_strikes, delete_alias, delete_all_strike_events, delete_all_strikes,
+    delete_latest_strike_event, delete_strikes, fold_aliases, get_active_strikes, get_aliases,
+    get_all_strikes, get_strike_events, get_strikes, get_strikes_after, import_strike_event,
+    increment_strikes, merge_strikes, put_alias, record_strike, remove_strike, DeleteReport,
+    StrikeEntity, StrikeEventEntity,
+};
+use std::collections::BTreeMap;
 use uuid::Uuid;
 
 async fn create_random_table(client: &Client) -> Result<String, Error> {
     let random_table_name = format!("Strikes_{}", Uuid::new_v4());
     let pk = AttributeDefinition::builder()
+        .attribute_name("Board")
+        .attribute_type(ScalarAttributeType::S)
+        .build()?;
+    let sk = AttributeDefinition::builder()
         .attribute_name("UserId")
         .attribute_type(ScalarAttributeType::S)
         .build()?;
 
-    let ks = KeySchemaElement::builder()
+    let hash = KeySchemaElement::builder()
+        .attribute_name("Board")
+        .key_type(KeyType::Hash)
+        .build()?;
+    let range = KeySchemaElement::builder()
         .attribute_name("UserId")
+        .key_type(KeyType::Range)
+        .build()?;
+
+    client
+        .create_table()
+        .table_name(&random_table_name)
+        .key_schema(hash)
+        .key_schema(range)
+        .attribute_definitions(pk)
+        .attribute_definitions(sk)
+        .billing_mode(BillingMode::PayPerRequest)
+        .send()
+        .await?;
+
+    Ok(random_table_name)
+}
+
+async fn create_random_events_table(client: &Client) -> Result<String, Error> {
+    let random_table_name = format!("StrikeEvents_{}", Uuid::new_v4());
+    let pk = AttributeDefinition::builder()
+        .attribute_name("BoardUserId")
+        .attribute_type(ScalarAttributeType::S)
+        .build()?;
+    let sk = AttributeDefinition::builder()
+        .attribute_name("Timestamp")
+        .attribute_type(ScalarAttributeType::S)
+        .build()?;
+
+    let hash = KeySchemaElement::builder()
+        .attribute_name("BoardUserId")
         .key_type(KeyType::Hash)
         .build()?;
+    let range = KeySchemaElement::builder()
+        .attribute_name("Timestamp")
+        .key_type(KeyType::Range)
+        .build()?;
 
     client
         .create_table()
         .table_name(&random_table_name)
-        .key_schema(ks)
+        .key_schema(hash)
+        .key_schema(range)
+        .attribute_definitions(pk)
+        .attribute_definitions(sk)
+        .billing_mode(BillingMode::PayPerRequest)
+        .send()
+        .await?;
+
+    Ok(random_table_name)
+}
+
+async fn create_random_aliases_table(client: &Client) -> Result<String, Error> {
+    let random_table_name = format!("Aliases_{}", Uuid::new_v4());
+    let pk = AttributeDefinition::builder()
+        .attribute_name("Board")
+        .attribute_type(ScalarAttributeType::S)
+        .build()?;
+    let sk = AttributeDefinition::builder()
+        .attribute_name("Alias")
+        .attribute_type(ScalarAttributeType::S)
+        .build()?;
+
+    let hash = KeySchemaElement::builder()
+        .attribute_name("Board")
+        .key_type(KeyType::Hash)
+        .build()?;
+    let range = KeySchemaElement::builder()
+        .attribute_name("Alias")
+        .key_type(KeyType::Range)
+        .build()?;
+
+    client
+        .create_table()
+        .table_name(&random_table_name)
+        .key_schema(hash)
+        .key_schema(range)
+        .attribute_definitions(pk)
+        .attribute_definitions(sk)
+        .billing_mode(BillingMode::PayPerRequest)
+        .send()
+        .await?;
+
+    Ok(random_table_name)
+}
+
+async fn create_random_legacy_table(client: &Client) -> Result<String, Error> {
+    let random_table_name = format!("LegacyStrikes_{}", Uuid::new_v4());
+    let pk = AttributeDefinition::builder()
+        .attribute_name("UserId")
+        .attribute_type(ScalarAttributeType::S)
+        .build()?;
+    let hash = KeySchemaElement::builder()
+        .attribute_name("UserId")
+        .key_type(KeyType::Hash)
+        .build()?;
+
+    client
+        .create_table()
+        .table_name(&random_table_name)
+        .key_schema(hash)
         .attribute_definitions(pk)
         .billing_mode(BillingMode::PayPerRequest)
         .send()
@@ -41,13 +153,13 @@ async fn it_should_add_some_strikes() -> Result<(), Box<dyn std::error::Error>>
 
     let table_name = create_random_table(&client).await.unwrap();
 
-    let _ = increment_strikes("heinz", &table_name, &client)
+    let _ = increment_strikes("default", "heinz", None, &table_name, &client)
         .await
         .unwrap();
-    let _ = increment_strikes("heinz", &table_name, &client)
+    let _ = increment_strikes("default", "heinz", None, &table_name, &client)
         .await
         .unwrap();
-    let strikes = increment_strikes("heinz", &table_name, &client)
+    let strikes = increment_strikes("default", "heinz", None, &table_name, &client)
         .await
         .unwrap();
 
@@ -66,31 +178,35 @@ async fn it_should_get_a_list_of_strikes() -> Result<(), Box<dyn std::error::Err
 
     let table_name = create_random_table(&client).await.unwrap();
 
-    let _ = increment_strikes("heinz", &table_name, &client)
+    let _ = increment_strikes("default", "heinz", None, &table_name, &client)
         .await
         .unwrap();
-    let _ = increment_strikes("heinz", &table_name, &client)
+    let _ = increment_strikes("default", "heinz", None, &table_name, &client)
         .await
         .unwrap();
-    let _ = increment_strikes("heinz", &table_name, &client)
+    let _ = increment_strikes("default", "heinz", None, &table_name, &client)
         .await
         .unwrap();
-    let _ = increment_strikes("guenther", &table_name, &client)
+    let _ = increment_strikes("default", "guenther", None, &table_name, &client)
         .await
         .unwrap();
 
-    let strikes = get_strikes(&table_name, &client).await.unwrap();
+    let strikes = get_strikes("default", &table_name, &client).await.unwrap();
 
     assert_eq!(
         strikes,
         vec![
             StrikeEntity {
-                user_id: "heinz".to_string(),
-                strikes: 3
+                board: "default".to_string(),
+                user_id: "guenther".to_string(),
+                strikes: 1,
+                reasons: vec![],
             },
             StrikeEntity {
-                user_id: "guenther".to_string(),
-                strikes: 1
+                board: "default".to_string(),
+                user_id: "heinz".to_string(),
+                strikes: 3,
+                reasons: vec![],
             }
         ]
     );
@@ -98,6 +214,110 @@ async fn it_should_get_a_list_of_strikes() -> Result<(), Box<dyn std::error::Err
     Ok(())
 }
 
+#[tokio::test]
+async fn it_should_keep_the_reasons_of_strikes() -> Result<(), Box<dyn std::error::Error>> {
+    let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+    let local_config = Builder::from(&config)
+        .endpoint_url("http://localhost:8000")
+        .build();
+    let client = Client::from_conf(local_config);
+
+    let table_name = create_random_table(&client).await.unwrap();
+
+    let _ = increment_strikes(
+        "default",
+        "heinz",
+        Some("broke the build"),
+        &table_name,
+        &client,
+    )
+    .await
+    .unwrap();
+    let _ = increment_strikes("default", "heinz", None, &table_name, &client)
+        .await
+        .unwrap();
+    let _ = increment_strikes(
+        "default",
+        "heinz",
+        Some("forgot the retro"),
+        &table_name,
+        &client,
+    )
+    .await
+    .unwrap();
+
+    let strikes = get_strikes("default", &table_name, &client).await.unwrap();
+
+    assert_eq!(
+        strikes,
+        vec![StrikeEntity {
+            board: "default".to_string(),
+            user_id: "heinz".to_string(),
+            strikes: 3,
+            reasons: vec![
+                "broke the build".to_string(),
+                "forgot the retro".to_string()
+            ],
+        }]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_should_remove_a_strike() -> Result<(), Box<dyn std::error::Error>> {
+    let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+    let local_config = Builder::from(&config)
+        .endpoint_url("http://localhost:8000")
+        .build();
+    let client = Client::from_conf(local_config);
+
+    let table_name = create_random_table(&client).await.unwrap();
+
+    let _ = increment_strikes("default", "heinz", None, &table_name, &client)
+        .await
+        .unwrap();
+    let _ = increment_strikes("default", "heinz", None, &table_name, &client)
+        .await
+        .unwrap();
+    let strikes = decrement_strikes("default", "heinz", &table_name, &client)
+        .await
+        .unwrap();
+
+    assert_eq!(strikes, 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_should_never_remove_more_strikes_than_given() -> Result<(), Box<dyn std::error::Error>>
+{
+    let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+    let local_config = Builder::from(&config)
+        .endpoint_url("http://localhost:8000")
+        .build();
+    let client = Client::from_conf(local_config);
+
+    let table_name = create_random_table(&client).await.unwrap();
+
+    let _ = increment_strikes("default", "heinz", None, &table_name, &client)
+        .await
+        .unwrap();
+    let first = decrement_strikes("default", "heinz", &table_name, &client)
+        .await
+        .unwrap();
+    let second = decrement_strikes("default", "heinz", &table_name, &client)
+        .await
+        .unwrap();
+    let strikes = get_strikes("default", &table_name, &client).await.unwrap();
+
+    assert_eq!(first, 0);
+    assert_eq!(second, 0);
+    assert_eq!(strikes, vec![]);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn it_should_delete_all_items() -> Result<(), Box<dyn std::error::Error>> {
     let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
@@ -108,23 +328,782 @@ async fn it_should_delete_all_items() -> Result<(), Box<dyn std::error::Error>>
 
     let table_name = create_random_table(&client).await.unwrap();
 
-    let _ = increment_strikes("heinz", &table_name, &client)
+    let _ = increment_strikes("default", "heinz", None, &table_name, &client)
         .await
         .unwrap();
-    let _ = increment_strikes("heinz", &table_name, &client)
+    let _ = increment_strikes("default", "heinz", None, &table_name, &client)
         .await
         .unwrap();
-    let _ = increment_strikes("heinz", &table_name, &client)
+    let _ = increment_strikes("default", "heinz", None, &table_name, &client)
         .await
         .unwrap();
-    let _ = increment_strikes("guenther", &table_name, &client)
+    let _ = increment_strikes("default", "guenther", None, &table_name, &client)
         .await
         .unwrap();
 
-    delete_all_strikes(&table_name, &client).await.unwrap();
-    let strikes = get_strikes(&table_name, &client).await.unwrap();
+    delete_all_strikes("default", &table_name, &client)
+        .await
+        .unwrap();
+    let strikes = get_strikes("default", &table_name, &client).await.unwrap();
 
     assert_eq!(strikes, vec![]);
 
     Ok(())
 }
+
+#[tokio::test]
+async fn it_should_delete_the_strikes_of_one_user() -> Result<(), Box<dyn std::error::Error>> {
+    let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+    let local_config = Builder::from(&config)
+        .endpoint_url("http://localhost:8000")
+        .build();
+    let client = Client::from_conf(local_config);
+
+    let table_name = create_random_table(&client).await.unwrap();
+
+    let _ = increment_strikes("default", "heinz", None, &table_name, &client)
+        .await
+        .unwrap();
+    let _ = increment_strikes("default", "guenther", None, &table_name, &client)
+        .await
+        .unwrap();
+
+    delete_strikes("default", "heinz", &table_name, &client)
+        .await
+        .unwrap();
+    let strikes = get_strikes("default", &table_name, &client).await.unwrap();
+
+    assert_eq!(
+        strikes,
+        vec![StrikeEntity {
+            board: "default".to_string(),
+            user_id: "guenther".to_string(),
+            strikes: 1,
+            reasons: vec![],
+        }]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_should_record_strike_events_in_order() -> Result<(), Box<dyn std::error::Error>> {
+    let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+    let local_config = Builder::from(&config)
+        .endpoint_url("http://localhost:8000")
+        .build();
+    let client = Client::from_conf(local_config);
+
+    let table_name = create_random_events_table(&client).await.unwrap();
+
+    let first = add_strike_event(
+        "default",
+        "heinz",
+        Some("broke the build"),
+        None,
+        &table_name,
+        &client,
+    )
+    .await
+    .unwrap();
+    let second = add_strike_event("default", "heinz", None, None, &table_name, &client)
+        .await
+        .unwrap();
+    let _ = add_strike_event("default", "guenther", None, None, &table_name, &client)
+        .await
+        .unwrap();
+
+    let events = get_strike_events("default", "heinz", &table_name, &client)
+        .await
+        .unwrap();
+
+    assert_eq!(events, vec![first, second]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_should_record_the_count_and_event_of_a_strike_together(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+    let local_config = Builder::from(&config)
+        .endpoint_url("http://localhost:8000")
+        .build();
+    let client = Client::from_conf(local_config);
+
+    let table_name = create_random_table(&client).await.unwrap();
+    let events_table_name = create_random_events_table(&client).await.unwrap();
+
+    let first = record_strike(
+        "default",
+        "heinz",
+        Some("broke the build"),
+        None,
+        &table_name,
+        &events_table_name,
+        &client,
+    )
+    .await
+    .unwrap();
+    let second = record_strike(
+        "default",
+        "heinz",
+        None,
+        None,
+        &table_name,
+        &events_table_name,
+        &client,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!((first, second), (1, 2));
+    assert_eq!(
+        get_strikes("default", &table_name, &client).await.unwrap(),
+        vec![StrikeEntity {
+            board: "default".to_string(),
+            user_id: "heinz".to_string(),
+            strikes: 2,
+            reasons: vec!["broke the build".to_string()],
+        }]
+    );
+    assert_eq!(
+        get_strike_events("default", "heinz", &events_table_name, &client)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|event| event.reason)
+            .collect::<Vec<_>>(),
+        vec![Some("broke the build".to_string()), None]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_should_count_strikes_recorded_at_the_same_time(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+    let local_config = Builder::from(&config)
+        .endpoint_url("http://localhost:8000")
+        .build();
+    let client = Client::from_conf(local_config);
+
+    let table_name = create_random_table(&client).await.unwrap();
+    let events_table_name = create_random_events_table(&client).await.unwrap();
+    let strike = || {
+        record_strike(
+            "default",
+            "heinz",
+            None,
+            None,
+            &table_name,
+            &events_table_name,
+            &client,
+        )
+    };
+
+    let (first, second) = tokio::join!(strike(), strike());
+
+    let mut counts = vec![first.unwrap(), second.unwrap()];
+    counts.sort();
+    assert_eq!(counts, vec![1, 2]);
+    assert_eq!(
+        get_strikes("default", &table_name, &client).await.unwrap()[0].strikes,
+        2
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_should_take_back_the_latest_strike_with_its_reason_and_event(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+    let local_config = Builder::from(&config)
+        .endpoint_url("http://localhost:8000")
+        .build();
+    let client = Client::from_conf(local_config);
+
+    let table_name = create_random_table(&client).await.unwrap();
+    let events_table_name = create_random_events_table(&client).await.unwrap();
+    for reason in [Some("broke the build"), None, Some("forgot the retro")] {
+        record_strike(
+            "default",
+            "heinz",
+            reason,
+            None,
+            &table_name,
+            &events_table_name,
+            &client,
+        )
+        .await
+        .unwrap();
+    }
+
+    let first = remove_strike("default", "heinz", &table_name, &events_table_name, &client)
+        .await
+        .unwrap();
+    let second = remove_strike("default", "heinz", &table_name, &events_table_name, &client)
+        .await
+        .unwrap();
+
+    assert_eq!((first, second), (2, 1));
+    assert_eq!(
+        get_strikes("default", &table_name, &client).await.unwrap(),
+        vec![StrikeEntity {
+            board: "default".to_string(),
+            user_id: "heinz".to_string(),
+            strikes: 1,
+            reasons: vec!["broke the build".to_string()],
+        }]
+    );
+    assert_eq!(
+        get_strike_events("default", "heinz", &events_table_name, &client)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|event| event.reason)
+            .collect::<Vec<_>>(),
+        vec![Some("broke the build".to_string())]
+    );
+
+    let last = remove_strike("default", "heinz", &table_name, &events_table_name, &client)
+        .await
+        .unwrap();
+    let unknown = remove_strike("default", "heinz", &table_name, &events_table_name, &client)
+        .await
+        .unwrap();
+
+    assert_eq!((last, unknown), (0, 0));
+    assert!(get_strikes("default", &table_name, &client)
+        .await
+        .unwrap()
+        .is_empty());
+    assert!(
+        get_strike_events("default", "heinz", &events_table_name, &client)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_should_delete_the_latest_strike_event() -> Result<(), Box<dyn std::error::Error>> {
+    let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+    let local_config = Builder::from(&config)
+        .endpoint_url("http://localhost:8000")
+        .build();
+    let client = Client::from_conf(local_config);
+
+    let table_name = create_random_events_table(&client).await.unwrap();
+
+    let first = add_strike_event(
+        "default",
+        "heinz",
+        Some("broke the build"),
+        None,
+        &table_name,
+        &client,
+    )
+    .await
+    .unwrap();
+    let _ = add_strike_event("default", "heinz", Some("typo"), None, &table_name, &client)
+        .await
+        .unwrap();
+
+    delete_latest_strike_event("default", "heinz", &table_name, &client)
+        .await
+        .unwrap();
+    let events = get_strike_events("default", "heinz", &table_name, &client)
+        .await
+        .unwrap();
+
+    assert_eq!(events, vec![first]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_should_keep_boards_apart() -> Result<(), Box<dyn std::error::Error>> {
+    let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+    let local_config = Builder::from(&config)
+        .endpoint_url("http://localhost:8000")
+        .build();
+    let client = Client::from_conf(local_config);
+
+    let table_name = create_random_table(&client).await.unwrap();
+
+    let _ = increment_strikes("coffee", "heinz", None, &table_name, &client)
+        .await
+        .unwrap();
+    let strikes = increment_strikes("builds", "heinz", None, &table_name, &client)
+        .await
+        .unwrap();
+    delete_all_strikes("coffee", &table_name, &client)
+        .await
+        .unwrap();
+
+    let all_strikes = get_all_strikes(&table_name, &client).await.unwrap();
+
+    assert_eq!(strikes, 1);
+    assert_eq!(
+        all_strikes,
+        vec![StrikeEntity {
+            board: "builds".to_string(),
+            user_id: "heinz".to_string(),
+            strikes: 1,
+            reasons: vec![],
+        }]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_should_delete_the_strike_events_of_one_board() -> Result<(), Box<dyn std::error::Error>>
+{
+    let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+    let local_config = Builder::from(&config)
+        .endpoint_url("http://localhost:8000")
+        .build();
+    let client = Client::from_conf(local_config);
+
+    let table_name = create_random_events_table(&client).await.unwrap();
+
+    let _ = add_strike_event("coffee", "heinz", None, None, &table_name, &client)
+        .await
+        .unwrap();
+    let builds = add_strike_event("builds", "heinz", None, None, &table_name, &client)
+        .await
+        .unwrap();
+
+    let report = delete_all_strike_events("coffee", &table_name, &client)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        report,
+        DeleteReport {
+            removed: 1,
+            failed: 0,
+        }
+    );
+    assert!(get_strike_events("coffee", "heinz", &table_name, &client)
+        .await
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        get_strike_events("builds", "heinz", &table_name, &client)
+            .await
+            .unwrap(),
+        vec![builds]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_should_only_count_strikes_that_have_not_expired(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+    let local_config = Builder::from(&config)
+        .endpoint_url("http://localhost:8000")
+        .build();
+    let client = Client::from_conf(local_config);
+
+    let table_name = create_random_events_table(&client).await.unwrap();
+
+    let expiring = add_strike_event(
+        "default",
+        "heinz",
+        Some("broke the build"),
+        Some(30),
+        &table_name,
+        &client,
+    )
+    .await
+    .unwrap();
+    let _ = add_strike_event("default", "heinz", Some("typo"), None, &table_name, &client)
+        .await
+        .unwrap();
+    let _ = add_strike_event("coffee", "guenther", None, Some(30), &table_name, &client)
+        .await
+        .unwrap();
+
+    let after_expiry = expiring.expires_at.unwrap() + 1;
+
+    assert_eq!(
+        count_active_strike_events("default", "heinz", after_expiry - 2, &table_name, &client)
+            .await
+            .unwrap(),
+        2
+    );
+    assert_eq!(
+        count_active_strike_events("default", "heinz", after_expiry, &table_name, &client)
+            .await
+            .unwrap(),
+        1
+    );
+    assert_eq!(
+        get_active_strikes(Some("default"), after_expiry, &table_name, &client)
+            .await
+            .unwrap(),
+        vec![StrikeEntity {
+            board: "default".to_string(),
+            user_id: "heinz".to_string(),
+            strikes: 1,
+            reasons: vec!["typo".to_string()],
+        }]
+    );
+    assert_eq!(
+        get_active_strikes(None, after_expiry - 2, &table_name, &client)
+            .await
+            .unwrap()
+            .len(),
+        2
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_should_import_strikes_only_once() -> Result<(), Box<dyn std::error::Error>> {
+    let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+    let local_config = Builder::from(&config)
+        .endpoint_url("http://localhost:8000")
+        .build();
+    let client = Client::from_conf(local_config);
+
+    let table_name = create_random_table(&client).await.unwrap();
+    let events_table_name = create_random_events_table(&client).await.unwrap();
+
+    let event = StrikeEventEntity {
+        board: "default".to_string(),
+        user_id: "guenther".to_string(),
+        timestamp: "2024-09-01T08:30:00.000000Z".to_string(),
+        reason: Some("broke the build".to_string()),
+        expires_at: None,
+    };
+
+    assert!(import_strike_event(&event, &events_table_name, &client)
+        .await
+        .unwrap());
+    assert!(!import_strike_event(&event, &events_table_name, &client)
+        .await
+        .unwrap());
+    assert_eq!(
+        get_strike_events("default", "guenther", &events_table_name, &client)
+            .await
+            .unwrap(),
+        vec![event]
+    );
+
+    add_strikes(
+        "default",
+        "guenther",
+        &["broke the build".to_string()],
+        1,
+        &table_name,
+        &client,
+    )
+    .await
+    .unwrap();
+    let strikes = add_strikes("default", "guenther", &[], 2, &table_name, &client)
+        .await
+        .unwrap();
+
+    assert_eq!(strikes, 3);
+    assert_eq!(
+        get_strikes("default", &table_name, &client).await.unwrap(),
+        vec![StrikeEntity {
+            board: "default".to_string(),
+            user_id: "guenther".to_string(),
+            strikes: 3,
+            reasons: vec!["broke the build".to_string()],
+        }]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_should_register_aliases_per_board() -> Result<(), Box<dyn std::error::Error>> {
+    let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+    let local_config = Builder::from(&config)
+        .endpoint_url("http://localhost:8000")
+        .build();
+    let client = Client::from_conf(local_config);
+
+    let table_name = create_random_aliases_table(&client).await.unwrap();
+
+    put_alias("default", "gue", "guenther", &table_name, &client)
+        .await
+        .unwrap();
+    put_alias("coffee", "hz", "heinz", &table_name, &client)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        get_aliases("default", &table_name, &client).await.unwrap(),
+        BTreeMap::from([("gue".to_string(), "guenther".to_string())])
+    );
+    assert!(delete_alias("default", "gue", &table_name, &client)
+        .await
+        .unwrap());
+    assert!(!delete_alias("default", "gue", &table_name, &client)
+        .await
+        .unwrap());
+    assert!(get_aliases("default", &table_name, &client)
+        .await
+        .unwrap()
+        .is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_should_merge_the_strikes_of_an_alias() -> Result<(), Box<dyn std::error::Error>> {
+    let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+    let local_config = Builder::from(&config)
+        .endpoint_url("http://localhost:8000")
+        .build();
+    let client = Client::from_conf(local_config);
+
+    let table_name = create_random_table(&client).await.unwrap();
+    let events_table_name = create_random_events_table(&client).await.unwrap();
+    let aliases = BTreeMap::from([("gue".to_string(), "guenther".to_string())]);
+
+    for username in ["gue", "gue", "guenther"] {
+        increment_strikes("default", username, Some("typo"), &table_name, &client)
+            .await
+            .unwrap();
+        add_strike_event(
+            "default",
+            username,
+            Some("typo"),
+            None,
+            &events_table_name,
+            &client,
+        )
+        .await
+        .unwrap();
+    }
+
+    let strikes = get_strikes("default", &table_name, &client).await.unwrap();
+    assert_eq!(fold_aliases(strikes, &aliases)[0].strikes, 3);
+
+    let merged = merge_strikes(
+        "default",
+        "gue",
+        "guenther",
+        &table_name,
+        &events_table_name,
+        &client,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(merged, 2);
+    assert_eq!(
+        get_strikes("default", &table_name, &client).await.unwrap(),
+        vec![StrikeEntity {
+            board: "default".to_string(),
+            user_id: "guenther".to_string(),
+            strikes: 3,
+            reasons: vec!["typo".to_string(); 3],
+        }]
+    );
+    assert_eq!(
+        get_strike_events("default", "guenther", &events_table_name, &client)
+            .await
+            .unwrap()
+            .len(),
+        3
+    );
+    assert!(
+        get_strike_events("default", "gue", &events_table_name, &client)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_should_list_strikes_beyond_the_first_page() -> Result<(), Box<dyn std::error::Error>> {
+    let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+    let local_config = Builder::from(&config)
+        .endpoint_url("http://localhost:8000")
+        .build();
+    let client = Client::from_conf(local_config);
+
+    let table_name = create_random_table(&client).await.unwrap();
+
+    // A scan or query answers with at most 1 MB, so four of these take two pages.
+    let reasons = vec!["x".repeat(350_000)];
+    for username in ["guenther", "heinz", "hans", "peter"] {
+        add_strikes("default", username, &reasons, 1, &table_name, &client)
+            .await
+            .unwrap();
+    }
+
+    let strikes = get_strikes("default", &table_name, &client).await.unwrap();
+    let all_strikes = get_all_strikes(&table_name, &client).await.unwrap();
+    delete_all_strikes("default", &table_name, &client)
+        .await
+        .unwrap();
+
+    assert_eq!(strikes.len(), 4);
+    assert_eq!(all_strikes.len(), 4);
+    assert!(get_strikes("default", &table_name, &client)
+        .await
+        .unwrap()
+        .is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_should_copy_the_strikes_from_before_boards() -> Result<(), Box<dyn std::error::Error>> {
+    let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+    let local_config = Builder::from(&config)
+        .endpoint_url("http://localhost:8000")
+        .build();
+    let client = Client::from_conf(local_config);
+
+    let legacy_table_name = create_random_legacy_table(&client).await.unwrap();
+    let table_name = create_random_table(&client).await.unwrap();
+    for (username, strikes) in [("guenther", "2"), ("heinz", "1")] {
+        client
+            .put_item()
+            .table_name(&legacy_table_name)
+            .item("UserId", AttributeValue::S(username.to_string()))
+            .item("Strikes", AttributeValue::N(strikes.to_string()))
+            .send()
+            .await?;
+    }
+    increment_strikes("default", "heinz", None, &table_name, &client)
+        .await
+        .unwrap();
+    increment_strikes("default", "heinz", None, &table_name, &client)
+        .await
+        .unwrap();
+    increment_strikes("default", "heinz", None, &table_name, &client)
+        .await
+        .unwrap();
+
+    let copied = copy_legacy_strikes(&legacy_table_name, &table_name, &client)
+        .await
+        .unwrap();
+    let copied_again = copy_legacy_strikes(&legacy_table_name, &table_name, &client)
+        .await
+        .unwrap();
+
+    assert_eq!((copied, copied_again), (1, 0));
+    assert_eq!(
+        get_strikes("default", &table_name, &client)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|strike| (strike.user_id, strike.strikes))
+            .collect::<Vec<_>>(),
+        vec![("guenther".to_string(), 2), ("heinz".to_string(), 3)]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_should_read_the_strikes_after_a_name() -> Result<(), Box<dyn std::error::Error>> {
+    let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+    let local_config = Builder::from(&config)
+        .endpoint_url("http://localhost:8000")
+        .build();
+    let client = Client::from_conf(local_config);
+
+    let table_name = create_random_table(&client).await.unwrap();
+    for username in ["guenther", "hans", "heinz"] {
+        increment_strikes("default", username, None, &table_name, &client)
+            .await
+            .unwrap();
+    }
+
+    let strikes = get_strikes_after("default", Some("hans"), None, &table_name, &client)
+        .await
+        .unwrap();
+    let page = get_strikes_after("default", None, Some(2), &table_name, &client)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        strikes
+            .into_iter()
+            .map(|strike| strike.user_id)
+            .collect::<Vec<_>>(),
+        vec!["heinz".to_string()]
+    );
+    assert_eq!(
+        page.into_iter()
+            .map(|strike| strike.user_id)
+            .collect::<Vec<_>>(),
+        vec!["guenther".to_string(), "hans".to_string()]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_should_delete_more_strikes_than_fit_in_one_batch(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+    let local_config = Builder::from(&config)
+        .endpoint_url("http://localhost:8000")
+        .build();
+    let client = Client::from_conf(local_config);
+
+    let table_name = create_random_table(&client).await.unwrap();
+
+    for user in 0..30 {
+        increment_strikes(
+            "default",
+            &format!("user{}", user),
+            None,
+            &table_name,
+            &client,
+        )
+        .await
+        .unwrap();
+    }
+    increment_strikes("builds", "heinz", None, &table_name, &client)
+        .await
+        .unwrap();
+
+    let report = delete_all_strikes("default", &table_name, &client)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        report,
+        DeleteReport {
+            removed: 30,
+            failed: 0,
+        }
+    );
+    assert!(get_strikes("default", &table_name, &client)
+        .await
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        get_strikes("builds", &table_name, &client)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+
+    Ok(())
+}
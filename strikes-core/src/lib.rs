@@ -0,0 +1,5 @@
+pub mod name;
+pub mod render;
+pub mod strike;
+pub mod tarnished;
+pub mod wire;
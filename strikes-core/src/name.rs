@@ -0,0 +1,99 @@
+pub const MAX_NAME_LENGTH: usize = 20;
+
+// Names of the tarnished and of boards, lowercased so "Guenther" and "guenther" are one person.
+#[derive(
+    serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+#[serde(try_from = "String", into = "String")]
+pub struct Name(String);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum NameError {
+    Empty,
+    TooLong,
+}
+
+impl std::fmt::Display for NameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NameError::Empty => write!(f, "cannot be empty"),
+            NameError::TooLong => write!(f, "cannot be longer than {} characters", MAX_NAME_LENGTH),
+        }
+    }
+}
+
+impl std::error::Error for NameError {}
+
+impl Name {
+    pub fn parse(raw: &str) -> Result<Self, NameError> {
+        if raw.is_empty() {
+            return Err(NameError::Empty);
+        }
+
+        if raw.len() > MAX_NAME_LENGTH {
+            return Err(NameError::TooLong);
+        }
+
+        Ok(Name(raw.to_lowercase()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::str::FromStr for Name {
+    type Err = NameError;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        Name::parse(raw)
+    }
+}
+
+impl TryFrom<String> for Name {
+    type Error = NameError;
+
+    fn try_from(raw: String) -> Result<Self, Self::Error> {
+        Name::parse(&raw)
+    }
+}
+
+impl From<Name> for String {
+    fn from(name: Name) -> Self {
+        name.0
+    }
+}
+
+impl std::ops::Deref for Name {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for Name {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    #[test]
+    fn it_should_lowercase_a_name() {
+        assert_eq!(Name::parse("Guenther").unwrap().as_str(), "guenther");
+    }
+
+    #[test]
+    fn it_should_reject_invalid_names() {
+        assert_eq!(Name::parse(""), Err(NameError::Empty));
+        assert_eq!(
+            Name::parse("guentherguentherguenther"),
+            Err(NameError::TooLong)
+        );
+        assert!(serde_json::from_str::<Name>("\"\"").is_err());
+    }
+}
@@ -0,0 +1,31 @@
+use crate::tarnished::Tarnished;
+
+// The website swaps this fragment into #content, both on load and on every websocket update.
+pub fn html_list(tarnished: &[Tarnished]) -> String {
+    let li = tarnished
+        .iter()
+        .map(|tarnished| format!("<li>{}: {}</li>", tarnished.name, tarnished.strikes))
+        .collect::<Vec<String>>()
+        .join("");
+
+    format!("<ul hx-swap-oob=\"innerHTML:#content\">{}</ul>", li)
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    #[test]
+    fn it_should_render_an_html_list() {
+        let tarnished = vec![Tarnished {
+            name: "guenther".to_string(),
+            strikes: 2,
+            reasons: vec![],
+        }];
+
+        assert_eq!(
+            html_list(&tarnished),
+            "<ul hx-swap-oob=\"innerHTML:#content\"><li>guenther: 2</li></ul>"
+        );
+    }
+}
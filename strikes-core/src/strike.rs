@@ -0,0 +1,31 @@
+use chrono::{DateTime, Duration, Utc};
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Strike {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<DateTime<Utc>>,
+}
+
+impl Strike {
+    pub fn now(reason: Option<&str>) -> Self {
+        Self {
+            reason: reason.map(str::to_string),
+            timestamp: Some(Utc::now()),
+        }
+    }
+
+    // Strikes recorded before timestamps existed have no age and therefore never expire.
+    pub fn is_expired(&self, expire_after_days: u32) -> bool {
+        self.timestamp.is_some_and(|timestamp| {
+            timestamp + Duration::days(expire_after_days.into()) <= Utc::now()
+        })
+    }
+
+    pub fn sort_asc_by_timestamp(strikes: Vec<Strike>) -> Vec<Strike> {
+        let mut strikes = strikes;
+        strikes.sort_by_key(|strike| strike.timestamp);
+        strikes
+    }
+}
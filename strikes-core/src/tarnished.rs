@@ -0,0 +1,68 @@
+use std::collections::HashMap;
+
+use crate::strike::Strike;
+use crate::wire::StrikesResponse;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Tarnished {
+    pub name: String,
+    pub strikes: u32,
+    pub reasons: Vec<String>,
+}
+
+impl Tarnished {
+    pub fn sort_desc_by_strike(tarnished: Vec<Tarnished>) -> Vec<Tarnished> {
+        let mut tarnished = tarnished.clone();
+        tarnished.sort_by(|a, b| b.strikes.partial_cmp(&a.strikes).unwrap());
+        tarnished
+    }
+
+    pub fn from_map(db: HashMap<String, u32>) -> Vec<Tarnished> {
+        db.iter()
+            .map(|(name, strikes)| Tarnished {
+                name: name.to_string(),
+                strikes: *strikes,
+                reasons: vec![],
+            })
+            .collect()
+    }
+
+    pub fn from_db(db: HashMap<String, Vec<Strike>>) -> Vec<Tarnished> {
+        db.into_iter()
+            .map(|(name, strikes)| Tarnished {
+                name,
+                strikes: strikes.len() as u32,
+                reasons: strikes
+                    .into_iter()
+                    .filter_map(|strike| strike.reason)
+                    .collect(),
+            })
+            .collect()
+    }
+
+    pub fn from_vec(sr: Vec<StrikesResponse>) -> Vec<Tarnished> {
+        sr.iter()
+            .map(
+                |StrikesResponse {
+                     name,
+                     strike_count,
+                     reasons,
+                 }| Tarnished {
+                    name: name.to_string(),
+                    strikes: *strike_count,
+                    reasons: reasons.clone(),
+                },
+            )
+            .collect()
+    }
+}
+
+impl From<Tarnished> for StrikesResponse {
+    fn from(tarnished: Tarnished) -> Self {
+        StrikesResponse {
+            name: tarnished.name,
+            strike_count: tarnished.strikes,
+            reasons: tarnished.reasons,
+        }
+    }
+}
@@ -0,0 +1,147 @@
+// Bodies exchanged between the CLI and the lambdas.
+use crate::strike::Strike;
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct StrikeRequest {
+    pub reason: Option<String>,
+}
+
+// Answers adding or removing a single strike.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct StrikeCountResponse {
+    pub name: String,
+    pub strike_count: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub threshold_reached: Option<bool>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct StrikesResponse {
+    pub name: String,
+    pub strike_count: u32,
+    // Older servers only answered with the count.
+    #[serde(default)]
+    pub reasons: Vec<String>,
+}
+
+// Answers GET /strikes once a limit or a cursor is asked for, without them it's the whole list.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct StrikesPage {
+    pub strikes: Vec<StrikesResponse>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+pub const DEFAULT_PAGE_SIZE: usize = 100;
+
+impl StrikesPage {
+    // Pages are ordered by name, the sort key of the BoardStrikes table, and the cursor is the last
+    // name of a page. The next page resumes after that name, so strikes added in between don't
+    // shift entries onto a page that was already read.
+    pub fn from_list(
+        mut strikes: Vec<StrikesResponse>,
+        cursor: Option<&str>,
+        limit: usize,
+    ) -> Self {
+        strikes.sort_by(|a, b| a.name.cmp(&b.name));
+        let mut rest = strikes
+            .into_iter()
+            .filter(|strike| cursor.is_none_or(|cursor| strike.name.as_str() > cursor))
+            .peekable();
+        let strikes = rest.by_ref().take(limit).collect::<Vec<_>>();
+
+        StrikesPage {
+            next_cursor: rest
+                .peek()
+                .and(strikes.last())
+                .map(|strike| strike.name.clone()),
+            strikes,
+        }
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct BoardStrikesResponse {
+    pub board: String,
+    #[serde(flatten)]
+    pub strikes: StrikesResponse,
+}
+
+// Also the shape of each tarnished in an export, so an export can be posted as is.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct ExportedTarnished {
+    pub name: String,
+    pub strikes: Vec<Strike>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct ImportResponse {
+    pub imported: usize,
+}
+
+// Answers clearing a board, counting the entries of the strikes and of their history. Any
+// failed entry makes it a partial failure, which is answered with a server error.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct DeleteResponse {
+    pub removed: u32,
+    #[serde(default)]
+    pub failed: u32,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct AliasRequest {
+    pub name: String,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct AliasResponse {
+    pub alias: String,
+    pub name: String,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct MergeResponse {
+    pub name: String,
+    pub merged: u32,
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    fn strikes(names: &[&str]) -> Vec<StrikesResponse> {
+        names
+            .iter()
+            .map(|name| StrikesResponse {
+                name: name.to_string(),
+                strike_count: 1,
+                reasons: vec![],
+            })
+            .collect()
+    }
+
+    #[test]
+    fn it_should_cut_a_list_into_pages() {
+        let list = strikes(&["heinz", "guenther", "hans"]);
+
+        let first = StrikesPage::from_list(list.clone(), None, 2);
+        let last = StrikesPage::from_list(list.clone(), first.next_cursor.as_deref(), 2);
+
+        assert_eq!(first.strikes, strikes(&["guenther", "hans"]));
+        assert_eq!(first.next_cursor, Some("hans".to_string()));
+        assert_eq!(last.strikes, strikes(&["heinz"]));
+        assert_eq!(last.next_cursor, None);
+    }
+
+    #[test]
+    fn it_should_resume_after_the_cursor_when_the_list_changed() {
+        let list = strikes(&["guenther", "hans", "heinz"]);
+        let first = StrikesPage::from_list(list, None, 2);
+
+        let changed = strikes(&["anton", "guenther", "hans", "heinz"]);
+        let last = StrikesPage::from_list(changed, first.next_cursor.as_deref(), 2);
+
+        assert_eq!(last.strikes, strikes(&["heinz"]));
+        assert_eq!(last.next_cursor, None);
+    }
+}